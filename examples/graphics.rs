@@ -1,58 +1,42 @@
+//! Renders a few seconds of an animated collage with the tiny-skia backend, writing each frame
+//! as a PNG within the directory given as the first argument (`elmesque-frames` by default).
+
 extern crate elmesque;
-extern crate find_folder;
-extern crate graphics;
-extern crate num;
-extern crate piston;
-extern crate piston_window;
 
-use elmesque::{Form, Renderer};
-use piston::input::UpdateEvent;
-use piston::window::WindowSettings;
-use piston_window::{PistonWindow, Glyphs};
+use elmesque::Form;
+use elmesque::backend::skia;
+use std::fs;
+use std::path::PathBuf;
+
+/// The size of each frame.
+const WIDTH: u32 = 1180;
+const HEIGHT: u32 = 580;
+
+/// The number of frames rendered per second of animation.
+const FPS: u32 = 30;
 
 fn main() {
 
-    // Construct the window.
-    let window: PistonWindow =
-        WindowSettings::new("Elmesque", [1180, 580])
-            .exit_on_esc(true)
-            .samples(4)
-            .vsync(true)
-            .build()
-            .unwrap();
-
-    // Construct the GlyphCache.
-    let mut glyph_cache = {
-        let assets = find_folder::Search::ParentsThenKids(3, 3).for_folder("assets").unwrap();
-        let font_path = assets.join("NotoSans/NotoSans-Regular.ttf");
-        Glyphs::new(&font_path, window.factory.borrow().clone()).unwrap()
-    };
-
-    // We'll use this to animate our graphics.
-    let mut secs = 0.0;
-
-    // Poll events from the window.
-    for event in window {
-        event.draw_2d(|context, g| {
-            let view_dim = context.get_view_size();
-            let (w, h) = (view_dim[0], view_dim[1]);
-
-            // Construct the elmesque Renderer with our graphics backend and glyph cache.
-            let mut renderer = Renderer::new(context, g).character_cache(&mut glyph_cache);
-
-            // Construct some freeform graphics aka a `Form`.
-            let form = elmesque_demo_form(secs);
-
-            // Convert the form to an `Element` for rendering.
-            let a = elmesque::form::collage(w as i32, h as i32, vec![form])
-                //.crop((secs / 2.0).sin() * (w / 2.0), (secs / 3.0).sin() * (h / 2.0), 400.0, 400.0)
-                .clear(elmesque::color::black());
-
-            a.draw(&mut renderer);
-        });
-        event.update(|args| secs += args.dt);
+    let dir = PathBuf::from(::std::env::args().nth(1).unwrap_or("elmesque-frames".to_string()));
+    fs::create_dir_all(&dir).unwrap();
+
+    for frame in 0..3 * FPS {
+        // We'll use this to animate our graphics.
+        let secs = frame as f64 / FPS as f64;
+
+        // Construct some freeform graphics aka a `Form`.
+        let form = elmesque_demo_form(secs);
+
+        // Convert the form to an `Element` for rendering.
+        let element = elmesque::form::collage(WIDTH as i32, HEIGHT as i32, vec![form])
+            .clear(elmesque::color::black());
+
+        let path = dir.join(format!("frame-{:03}.png", frame));
+        let pixmap = skia::render(&element, WIDTH, HEIGHT).unwrap();
+        pixmap.save_png(&path).unwrap();
     }
 
+    println!("Rendered {} frames to {}", 3 * FPS, dir.display());
 }
 
 
@@ -62,7 +46,6 @@ pub fn elmesque_demo_form(secs: f64) -> Form {
     use elmesque::form::{circle, group, ngon, oval, point_path, rect, solid, text, traced};
     use elmesque::text::Text;
     use elmesque::utils::{degrees};
-    use num::Float;
 
     // Time to get creative!
     group(vec![
@@ -118,7 +101,7 @@ pub fn elmesque_demo_form(secs: f64) -> Form {
             solid(light_blue()),
             point_path(vec![(-500.0, 100.0), (0.0, 250.0 * secs.sin()), (500.0, 100.0)])
         ).alpha(((secs * 0.2).sin() * 0.25 + 0.35) as f32),
-            
+
         traced(
             solid(blue()),
             point_path(vec![(-500.0, 0.0), (0.0, 0.0), (500.0, 0.0)])
@@ -0,0 +1,132 @@
+//!
+//! An angle that knows its own units.
+//!
+//! The functions that take an angle (i.e. `Form::rotate`, `form::ngon_oriented` and
+//! `color::hsl`) accept anything that converts into an `Angle`. Plain floats are taken to be
+//! radians as they always have been, while `Angle::deg` makes an angle in degrees explicit:
+//!
+//!   square(40.0).filled(red()).rotate(Angle::deg(45.0))
+//!   hsl(Angle::deg(120.0), 1.0, 0.5)
+//!
+
+use std::f64::consts::PI;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+use utils;
+
+
+/// An angle, stored in radians. Positive angles turn counterclockwise.
+#[derive(Copy, Clone, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Angle {
+    radians: f64,
+}
+
+
+impl Angle {
+
+    /// An angle of the given number of radians.
+    #[inline]
+    pub fn rad(radians: f64) -> Angle {
+        Angle { radians: radians }
+    }
+
+    /// An angle of the given number of degrees.
+    #[inline]
+    pub fn deg(degrees: f64) -> Angle {
+        Angle::rad(degrees * PI / 180.0)
+    }
+
+    /// An angle of the given number of whole turns.
+    #[inline]
+    pub fn turns(turns: f64) -> Angle {
+        Angle::rad(turns * 2.0 * PI)
+    }
+
+    /// The angle in radians.
+    #[inline]
+    pub fn radians(self) -> f64 {
+        self.radians
+    }
+
+    /// The angle in degrees.
+    #[inline]
+    pub fn degrees(self) -> f64 {
+        self.radians * 180.0 / PI
+    }
+
+    /// The angle in whole turns.
+    #[inline]
+    pub fn in_turns(self) -> f64 {
+        self.radians / (2.0 * PI)
+    }
+
+    /// The same direction, wrapped to within a single turn, i.e. `[0°, 360°)`.
+    #[inline]
+    pub fn wrapped(self) -> Angle {
+        Angle::rad(utils::wrap_angle(self.radians))
+    }
+
+}
+
+
+/// Plain floats are taken to be radians.
+impl From<f64> for Angle {
+    fn from(radians: f64) -> Angle {
+        Angle::rad(radians)
+    }
+}
+
+/// Plain floats are taken to be radians.
+impl From<f32> for Angle {
+    fn from(radians: f32) -> Angle {
+        Angle::rad(radians as f64)
+    }
+}
+
+
+impl Add for Angle {
+    type Output = Angle;
+    fn add(self, other: Angle) -> Angle {
+        Angle::rad(self.radians + other.radians)
+    }
+}
+
+impl Sub for Angle {
+    type Output = Angle;
+    fn sub(self, other: Angle) -> Angle {
+        Angle::rad(self.radians - other.radians)
+    }
+}
+
+impl AddAssign for Angle {
+    fn add_assign(&mut self, other: Angle) {
+        self.radians += other.radians;
+    }
+}
+
+impl SubAssign for Angle {
+    fn sub_assign(&mut self, other: Angle) {
+        self.radians -= other.radians;
+    }
+}
+
+impl Neg for Angle {
+    type Output = Angle;
+    fn neg(self) -> Angle {
+        Angle::rad(-self.radians)
+    }
+}
+
+impl Mul<f64> for Angle {
+    type Output = Angle;
+    fn mul(self, scalar: f64) -> Angle {
+        Angle::rad(self.radians * scalar)
+    }
+}
+
+impl Div<f64> for Angle {
+    type Output = Angle;
+    fn div(self, scalar: f64) -> Angle {
+        Angle::rad(self.radians / scalar)
+    }
+}
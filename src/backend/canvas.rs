@@ -0,0 +1,409 @@
+//!
+//! A `Backend` that draws to an HTML canvas via the Canvas2D API, allowing elmesque to be used
+//! for web demos when compiled to `wasm32-unknown-unknown`.
+//!
+//! Images and textures are loaded via `HtmlImageElement`s where the path is used as the `src`.
+//! As images load asynchronously, an image will not be drawn until it has finished loading, so
+//! you'll usually want to re-render on each animation frame.
+//!
+//! Typefaces can't be loaded from a path in the browser, so the file stem of a `Style`'s
+//! `typeface` is used as the CSS font family instead (i.e. "fonts/NotoSans-Regular.ttf" becomes
+//! "NotoSans-Regular"). This means fonts should be declared via an `@font-face` rule.
+//!
+
+use backend::{self, Backend, RenderOptions};
+use color::{Color, Gradient};
+use element::{Element, ImageStyle};
+use form::{FillStyle, LineCap, LineJoin, LineStyle};
+use length::Insets;
+use js_sys::Array;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use text::{self, Text};
+use transform_2d::{self, Transform2D};
+use utils::clampf32;
+use wasm_bindgen::JsValue;
+use web_sys::{CanvasGradient, CanvasRenderingContext2d, HtmlImageElement};
+
+
+/// The font size used for text that has no height.
+const DEFAULT_FONT_SIZE: f64 = 16.0;
+
+
+/// A `Backend` that draws to a `CanvasRenderingContext2d`.
+pub struct CanvasBackend<'a> {
+    ctx: &'a CanvasRenderingContext2d,
+    width: f64,
+    height: f64,
+    /// The number of clips currently pushed. Each clip is a `save` on the context.
+    clip_depth: usize,
+    /// Images by path, or `None` if an image element could not be created for the path.
+    images: HashMap<PathBuf, Option<HtmlImageElement>>,
+    options: RenderOptions,
+}
+
+
+impl<'a> CanvasBackend<'a> {
+
+    /// Construct a backend that draws to the given context.
+    ///
+    /// The size of the render target is taken from the context's canvas.
+    pub fn new(ctx: &'a CanvasRenderingContext2d) -> CanvasBackend<'a> {
+        let (width, height) = match ctx.canvas() {
+            Some(canvas) => (canvas.width() as f64, canvas.height() as f64),
+            None => (0.0, 0.0),
+        };
+        CanvasBackend {
+            ctx: ctx,
+            width: width,
+            height: height,
+            clip_depth: 0,
+            images: HashMap::new(),
+            options: RenderOptions::default(),
+        }
+    }
+
+    /// Builder method for drawing with the given `RenderOptions`.
+    ///
+    /// The Canvas2D API always anti-aliases paths, so `anti_alias` only controls whether images
+    /// are smoothed when scaled.
+    pub fn options(self, options: RenderOptions) -> CanvasBackend<'a> {
+        self.ctx.set_image_smoothing_enabled(options.anti_alias);
+        CanvasBackend { options: options, ..self }
+    }
+
+    /// The given points snapped to pixels if the options ask for it.
+    fn snapped(&self, transform: &Transform2D, points: &[(f64, f64)]) -> Vec<(f64, f64)> {
+        if self.options.pixel_snap {
+            backend::snap_to_pixels(transform, points, (self.width, self.height))
+        } else {
+            points.to_vec()
+        }
+    }
+
+    /// Set the context's transform to the given local transform mapped to canvas pixels.
+    fn set_transform(&self, transform: &Transform2D) {
+        let Transform2D(m) = backend::view_to_target(self.width, self.height)
+            .multiply(transform.clone());
+        let _ = self.ctx.set_transform(m[0][0], m[1][0], m[0][1], m[1][1], m[0][2], m[1][2]);
+    }
+
+    /// The image at the given path if it has finished loading.
+    fn image(&mut self, path: &Path) -> Option<HtmlImageElement> {
+        if !self.images.contains_key(path) {
+            let image = HtmlImageElement::new().ok().inspect(|image| {
+                image.set_src(&path.to_string_lossy());
+            });
+            self.images.insert(path.to_path_buf(), image);
+        }
+        match self.images[path] {
+            Some(ref image) if image.complete() && image.natural_width() > 0 =>
+                Some(image.clone()),
+            _ => None,
+        }
+    }
+
+    /// Begin a new path along the given points.
+    fn trace_path(&self, points: &[(f64, f64)], closed: bool) {
+        self.ctx.begin_path();
+        for (i, &(x, y)) in points.iter().enumerate() {
+            if i == 0 { self.ctx.move_to(x, y) } else { self.ctx.line_to(x, y) }
+        }
+        if closed {
+            self.ctx.close_path();
+        }
+    }
+
+    /// Apply the given `LineStyle` to the context's stroke state.
+    fn set_line_style(&self, style: &LineStyle) {
+        let LineStyle { color, width, cap, join, ref dashing, dash_offset } = *style;
+        self.ctx.set_stroke_style_str(&css_color(color));
+        self.ctx.set_line_width(width);
+        self.ctx.set_line_cap(match cap {
+            LineCap::Flat => "butt",
+            LineCap::Round => "round",
+            LineCap::Padded => "square",
+        });
+        match join {
+            LineJoin::Smooth => self.ctx.set_line_join("round"),
+            LineJoin::Sharp(limit) => {
+                self.ctx.set_line_join("miter");
+                self.ctx.set_miter_limit(limit);
+            },
+            LineJoin::Clipped => self.ctx.set_line_join("bevel"),
+        }
+        let segments: Array = dashing.iter().map(|&d| JsValue::from_f64(d as f64)).collect();
+        let _ = self.ctx.set_line_dash(&segments);
+        self.ctx.set_line_dash_offset(dash_offset as f64);
+    }
+
+    /// Create a canvas gradient from an elmesque gradient.
+    fn gradient(&self, gradient: &Gradient) -> Option<CanvasGradient> {
+        let linear;
+        let gradient = if self.options.linear_blending {
+            linear = gradient.clone().in_linear_rgb();
+            &linear
+        } else {
+            gradient
+        };
+        let (canvas_gradient, colors) = match *gradient {
+            Gradient::Linear((x0, y0), (x1, y1), ref colors) =>
+                (Some(self.ctx.create_linear_gradient(x0, y0, x1, y1)), colors),
+            Gradient::Radial((x0, y0), r0, (x1, y1), r1, ref colors) =>
+                (self.ctx.create_radial_gradient(x0, y0, r0, x1, y1, r1).ok(), colors),
+        };
+        canvas_gradient.inspect(|canvas_gradient| {
+            for &(t, color) in colors.iter() {
+                let _ = canvas_gradient.add_color_stop(clampf32(t as f32), &css_color(color));
+            }
+        })
+    }
+
+}
+
+
+impl<'a> Backend for CanvasBackend<'a> {
+
+    fn clear(&mut self, color: Color) {
+        let _ = self.ctx.set_transform(1.0, 0.0, 0.0, 1.0, 0.0, 0.0);
+        self.ctx.set_global_alpha(1.0);
+        self.ctx.clear_rect(0.0, 0.0, self.width, self.height);
+        self.ctx.set_fill_style_str(&css_color(color));
+        self.ctx.fill_rect(0.0, 0.0, self.width, self.height);
+    }
+
+    fn fill_polygon(&mut self, transform: &Transform2D, points: &[(f64, f64)],
+                    style: &FillStyle, alpha: f32) {
+        let points = &self.snapped(transform, points)[..];
+        self.ctx.set_global_alpha(alpha as f64);
+        match *style {
+            FillStyle::Solid(color) => {
+                self.set_transform(transform);
+                self.ctx.set_fill_style_str(&css_color(color));
+                self.trace_path(points, true);
+            },
+            FillStyle::Grad(ref gradient) => {
+                let canvas_gradient = match self.gradient(gradient) {
+                    Some(canvas_gradient) => canvas_gradient,
+                    None => return,
+                };
+                self.set_transform(transform);
+                self.ctx.set_fill_style_canvas_gradient(&canvas_gradient);
+                self.trace_path(points, true);
+            },
+            FillStyle::Texture(ref path) => {
+                let image = match self.image(path) {
+                    Some(image) => image,
+                    None => return,
+                };
+                let pattern = match self.ctx.create_pattern_with_html_image_element(&image, "repeat") {
+                    Ok(Some(pattern)) => pattern,
+                    _ => return,
+                };
+                // Patterns are anchored to the top left with the y-axis pointing down, so trace
+                // the polygon in a flipped space to keep the texture upright.
+                self.set_transform(&transform.clone().multiply(transform_2d::scale_y(-1.0)));
+                self.ctx.set_fill_style_canvas_pattern(&pattern);
+                let flipped: Vec<_> = points.iter().map(|&(x, y)| (x, -y)).collect();
+                self.trace_path(&flipped, true);
+            },
+        }
+        self.ctx.fill();
+    }
+
+    fn stroke_path(&mut self, transform: &Transform2D, points: &[(f64, f64)], closed: bool,
+                   style: &LineStyle, alpha: f32) {
+        self.ctx.set_global_alpha(alpha as f64);
+        self.set_transform(transform);
+        self.set_line_style(style);
+        self.trace_path(&self.snapped(transform, points), closed);
+        self.ctx.stroke();
+    }
+
+    fn draw_text(&mut self, transform: &Transform2D, text: &Text, outline: Option<&LineStyle>,
+                 alpha: f32) {
+        self.ctx.set_global_alpha(alpha as f64);
+        // Text is drawn with the y-axis pointing down.
+        self.set_transform(&transform.clone().multiply(transform_2d::scale_y(-1.0)));
+        // The offset from the anchor to the middle of the text, used to position lines.
+        let (text_baseline, middle) = match text.anchor {
+            text::TextAnchor::Baseline => ("alphabetic", -0.3),
+            text::TextAnchor::Middle => ("middle", 0.0),
+            text::TextAnchor::Top => ("top", 0.5),
+            text::TextAnchor::Bottom => ("bottom", -0.5),
+        };
+        self.ctx.set_text_baseline(text_baseline);
+        self.ctx.set_text_align("left");
+        if let Some(style) = outline {
+            self.set_line_style(style);
+        }
+
+        // Measure the total width so that the text may be positioned.
+        let widths: Vec<f64> = text.sequence.iter().map(|unit| {
+            self.ctx.set_font(&css_font(&unit.style));
+            self.ctx.measure_text(&unit.string).map(|metrics| metrics.width()).unwrap_or(0.0)
+        }).collect();
+        let total_width = widths.iter().fold(0.0, |total, &w| total + w);
+        let start_x = -total_width * text.position.fraction();
+
+        // Backgrounds are drawn first so that their padding never covers a neighbouring unit's
+        // glyphs.
+        let mut x = start_x;
+        for (unit, &width) in text.sequence.iter().zip(widths.iter()) {
+            let style = &unit.style;
+            if let Some(background) = style.background {
+                let size = style.height.unwrap_or(DEFAULT_FONT_SIZE);
+                let pad = size * text::BACKGROUND_PADDING;
+                let top = -style.baseline + middle * size - size * 0.5 - pad;
+                self.ctx.set_fill_style_str(&css_color(background));
+                self.ctx.fill_rect(x - pad, top, width + pad * 2.0, size + pad * 2.0);
+            }
+            x += width;
+        }
+
+        let mut x = start_x;
+        for (unit, &width) in text.sequence.iter().zip(widths.iter()) {
+            let style = &unit.style;
+            self.ctx.set_font(&css_font(style));
+            let color = css_color(style.color);
+            // Raising the baseline moves the text up, which is a negative y here.
+            let baseline = -style.baseline;
+            match outline {
+                Some(_) => { let _ = self.ctx.stroke_text(&unit.string, x, baseline); },
+                None => {
+                    self.ctx.set_fill_style_str(&color);
+                    let _ = self.ctx.fill_text(&unit.string, x, baseline);
+                },
+            }
+            if let Some(line) = style.line {
+                let size = style.height.unwrap_or(DEFAULT_FONT_SIZE);
+                let y = baseline + middle * size + match line {
+                    text::Line::Under => size * 0.4,
+                    text::Line::Over => -size * 0.5,
+                    text::Line::Through => 0.0,
+                };
+                let thickness = (size / 16.0).max(1.0);
+                self.ctx.set_fill_style_str(&color);
+                self.ctx.fill_rect(x, y - thickness / 2.0, width, thickness);
+            }
+            x += width;
+        }
+    }
+
+    fn draw_image(&mut self, transform: &Transform2D, style: ImageStyle, (w, h): (i32, i32),
+                  path: &Path, alpha: f32) {
+        let image = match self.image(path) {
+            Some(image) => image,
+            None => return,
+        };
+        self.ctx.set_global_alpha(alpha as f64);
+        // Images are drawn with their top left at the element's top left and the y-axis down.
+        self.set_transform(&transform.clone().multiply(transform_2d::scale_y(-1.0)));
+        let (w, h) = (w as f64, h as f64);
+        let (left, top) = (-w / 2.0, -h / 2.0);
+        let (tex_w, tex_h) = (image.natural_width() as f64, image.natural_height() as f64);
+        let src = match style {
+            ImageStyle::Plain => Some((0.0, 0.0, tex_w, tex_h)),
+            ImageStyle::Fitted => {
+                let scale = (w / tex_w).max(h / tex_h);
+                let (src_w, src_h) = (w / scale, h / scale);
+                Some(((tex_w - src_w) / 2.0, (tex_h - src_h) / 2.0, src_w, src_h))
+            },
+            ImageStyle::Cropped(x, y) => Some((x as f64, y as f64, w, h)),
+            ImageStyle::Tiled => None,
+        };
+        match src {
+            Some((sx, sy, sw, sh)) => {
+                let _ = self.ctx.draw_image_with_html_image_element_and_sw_and_sh_and_dx_and_dy_and_dw_and_dh(
+                    &image, sx, sy, sw, sh, left, top, w, h);
+            },
+            None => {
+                if let Ok(Some(pattern)) = self.ctx.create_pattern_with_html_image_element(&image, "repeat") {
+                    // Anchor the tiles to the image's top left corner.
+                    self.set_transform(&transform.clone()
+                        .multiply(transform_2d::scale_y(-1.0))
+                        .multiply(transform_2d::translation(left, top)));
+                    self.ctx.set_fill_style_canvas_pattern(&pattern);
+                    self.ctx.fill_rect(0.0, 0.0, w, h);
+                }
+            },
+        }
+    }
+
+    fn push_clip(&mut self, (x, y, w, h): (f64, f64, f64, f64)) {
+        self.ctx.save();
+        self.clip_depth += 1;
+        self.set_transform(&transform_2d::identity());
+        self.ctx.begin_path();
+        self.ctx.rect(x - w / 2.0, y - h / 2.0, w.max(0.0), h.max(0.0));
+        self.ctx.clip();
+    }
+
+    fn push_clip_shape(&mut self, transform: &Transform2D, points: &[(f64, f64)]) {
+        self.ctx.save();
+        self.clip_depth += 1;
+        self.set_transform(transform);
+        self.trace_path(points, true);
+        self.ctx.clip();
+    }
+
+    fn pop_clip(&mut self) {
+        if self.clip_depth > 0 {
+            self.clip_depth -= 1;
+            self.ctx.restore();
+        }
+    }
+
+    fn target_size(&self) -> (f64, f64) {
+        (self.width, self.height)
+    }
+
+    fn dpi(&self) -> f64 {
+        self.options.dpi
+    }
+
+    fn time(&self) -> f64 {
+        self.options.time
+    }
+
+    fn dt(&self) -> f64 {
+        self.options.dt
+    }
+
+    fn safe_area(&self) -> Insets {
+        self.options.safe_area
+    }
+
+}
+
+
+/// Render the given `Element` to the given canvas context.
+pub fn render(element: &Element, ctx: &CanvasRenderingContext2d) {
+    let mut backend = CanvasBackend::new(ctx);
+    backend::draw_element(element, 1.0, transform_2d::identity(), &mut backend, &mut None);
+}
+
+
+/// Convert an elmesque color to a CSS color string.
+fn css_color(color: Color) -> String {
+    let [r, g, b, a] = color.to_byte_fsa();
+    format!("rgba({}, {}, {}, {})", r, g, b, a as f32 / 255.0)
+}
+
+
+/// Convert an elmesque text style to a CSS font string.
+fn css_font(style: &text::Style) -> String {
+    // The browser falls back through the family list for each missing character.
+    let mut families: Vec<String> = style.typeface.iter()
+        .chain(style.fallback_typefaces.iter())
+        .filter_map(|path| path.file_stem())
+        .map(|stem| format!("\"{}\"", stem.to_string_lossy()))
+        .collect();
+    families.push(if style.monospace { "monospace" } else { "sans-serif" }.to_string());
+    let family = families.join(", ");
+    format!("{}{}{}px {}",
+            if style.italic { "italic " } else { "" },
+            if style.bold { "bold " } else { "" },
+            style.height.unwrap_or(DEFAULT_FONT_SIZE),
+            family)
+}
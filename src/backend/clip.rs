@@ -0,0 +1,217 @@
+//!
+//! Cropping by clipping geometry on the CPU.
+//!
+//! `GeometricClip` wraps some other `Backend`, handling `push_clip` and `push_clip_shape` itself
+//! by cutting the polygons and paths it is given down to the current clip before handing them
+//! on. This allows crops to work with backends that have no means of clipping (such as a
+//! scissor rectangle), and because the clip is a polygon in view space it composes with any
+//! rotation applied to the cropped `Element`.
+//!
+//! Only convex clips are supported, which covers all clips pushed while drawing an `Element`.
+//! Text and images can't be cut on the CPU, so they are drawn whole if they overlap the clip and
+//! skipped otherwise. Stroked paths are clipped along their center, so a line's width may extend
+//! past the clip by half of its width.
+//!
+
+use color::Color;
+use element::{Element, ImageStyle};
+use form::{FillStyle, LineStyle};
+use geometry::{self, Rect};
+use length::Insets;
+use std::path::Path;
+use super::Backend;
+use text::Text;
+use transform_2d::Transform2D;
+
+#[cfg(feature = "piston-backend")]
+use super::piston::CustomDraw;
+
+
+/// A `Backend` that crops the geometry drawn to some other `Backend` on the CPU.
+pub struct GeometricClip<B> {
+    backend: B,
+    /// The stack of clips in view space, each the intersection of itself and the clip before it.
+    clips: Vec<Vec<(f64, f64)>>,
+}
+
+
+impl<B: Backend> GeometricClip<B> {
+
+    /// Wrap the given `Backend`.
+    pub fn new(backend: B) -> GeometricClip<B> {
+        GeometricClip { backend: backend, clips: Vec::new() }
+    }
+
+    /// Unwrap the inner `Backend`.
+    pub fn into_inner(self) -> B {
+        self.backend
+    }
+
+    /// Whether or not the given polygon, mapped into view space by the given transform,
+    /// overlaps the current clip.
+    fn overlaps_clip(&self, transform: &Transform2D, polygon: &[(f64, f64)]) -> bool {
+        match self.clips.last() {
+            Some(clip) => geometry::polygons_overlap(&map_points(transform, polygon), clip),
+            None => true,
+        }
+    }
+
+    /// Intersect the given polygon in view space with the current clip and push the result.
+    fn push_view_clip(&mut self, polygon: Vec<(f64, f64)>) {
+        let clip = match self.clips.last() {
+            Some(clip) => geometry::clip_polygon(&polygon, clip),
+            None => polygon,
+        };
+        self.clips.push(clip);
+    }
+
+    /// Stroke the given run of points in view space, leaving the run empty.
+    ///
+    /// `inverse` maps view space back to the coordinates described by `transform`.
+    fn stroke_run(&mut self, transform: &Transform2D, inverse: &Transform2D,
+                  run: &mut Vec<(f64, f64)>, style: &LineStyle, alpha: f32) {
+        if run.len() >= 2 {
+            let local = map_points(inverse, run);
+            self.backend.stroke_path(transform, &local, false, style, alpha);
+        }
+        run.clear();
+    }
+
+}
+
+
+impl<B: Backend> Backend for GeometricClip<B> {
+
+    fn clear(&mut self, color: Color) {
+        self.backend.clear(color);
+    }
+
+    fn fill_polygon(&mut self, transform: &Transform2D, points: &[(f64, f64)],
+                    style: &FillStyle, alpha: f32) {
+        let clip = match self.clips.last() {
+            Some(clip) => clip,
+            None => return self.backend.fill_polygon(transform, points, style, alpha),
+        };
+        // Clip in view space, then map the result back so that textures and gradients keep
+        // their place within the polygon.
+        let inverse = match transform.invert() {
+            Some(inverse) => inverse,
+            None => return,
+        };
+        let clipped = geometry::clip_polygon(&map_points(transform, points), clip);
+        if clipped.len() >= 3 {
+            let local = map_points(&inverse, &clipped);
+            self.backend.fill_polygon(transform, &local, style, alpha);
+        }
+    }
+
+    fn stroke_path(&mut self, transform: &Transform2D, points: &[(f64, f64)], closed: bool,
+                   style: &LineStyle, alpha: f32) {
+        let clip = match self.clips.last() {
+            Some(clip) => clip.clone(),
+            None => return self.backend.stroke_path(transform, points, closed, style, alpha),
+        };
+        let inverse = match transform.invert() {
+            Some(inverse) => inverse,
+            None => return,
+        };
+        let mut view = map_points(transform, points);
+        if closed && !view.is_empty() {
+            let first = view[0];
+            view.push(first);
+        }
+
+        // Stroke each run of consecutive visible segments as its own path.
+        let mut run: Vec<(f64, f64)> = Vec::new();
+        for segment in view.windows(2) {
+            match geometry::clip_segment(segment[0], segment[1], &clip) {
+                Some((a, b)) => {
+                    if run.last() != Some(&a) {
+                        self.stroke_run(transform, &inverse, &mut run, style, alpha);
+                        run.push(a);
+                    }
+                    run.push(b);
+                },
+                None => self.stroke_run(transform, &inverse, &mut run, style, alpha),
+            }
+        }
+        // A closed path that is entirely visible remains closed.
+        if closed && run.len() == view.len() {
+            run.pop();
+            let local = map_points(&inverse, &run);
+            self.backend.stroke_path(transform, &local, true, style, alpha);
+        } else {
+            self.stroke_run(transform, &inverse, &mut run, style, alpha);
+        }
+    }
+
+    fn draw_text(&mut self, transform: &Transform2D, text: &Text, outline: Option<&LineStyle>,
+                 alpha: f32) {
+        // Without glyph metrics, text is only culled once its origin leaves the clip.
+        if self.overlaps_clip(transform, &[(0.0, 0.0)]) {
+            self.backend.draw_text(transform, text, outline, alpha);
+        }
+    }
+
+    fn draw_image(&mut self, transform: &Transform2D, style: ImageStyle, size: (i32, i32),
+                  path: &Path, alpha: f32) {
+        let rect = Rect::from_xywh(0.0, 0.0, size.0 as f64, size.1 as f64);
+        if self.overlaps_clip(transform, &rect.corners()) {
+            self.backend.draw_image(transform, style, size, path, alpha);
+        }
+    }
+
+    /// Custom forms draw themselves, so they are handed on uncut.
+    #[cfg(feature = "piston-backend")]
+    fn draw_custom(&mut self, transform: &Transform2D, custom: &CustomDraw) {
+        self.backend.draw_custom(transform, custom);
+    }
+
+    fn push_clip(&mut self, (x, y, w, h): (f64, f64, f64, f64)) {
+        let rect = Rect::from_xywh(x, y, w.max(0.0), h.max(0.0));
+        self.push_view_clip(rect.corners().to_vec());
+    }
+
+    fn push_clip_shape(&mut self, transform: &Transform2D, points: &[(f64, f64)]) {
+        self.push_view_clip(map_points(transform, points));
+    }
+
+    fn pop_clip(&mut self) {
+        self.clips.pop();
+    }
+
+    fn target_size(&self) -> (f64, f64) {
+        self.backend.target_size()
+    }
+
+    fn dpi(&self) -> f64 {
+        self.backend.dpi()
+    }
+
+    fn time(&self) -> f64 {
+        self.backend.time()
+    }
+
+    fn dt(&self) -> f64 {
+        self.backend.dt()
+    }
+
+    fn safe_area(&self) -> Insets {
+        self.backend.safe_area()
+    }
+
+    fn begin_element(&mut self, element: &Element, transform: &Transform2D, opacity: f32) {
+        self.backend.begin_element(element, transform, opacity);
+    }
+
+    fn end_element(&mut self) {
+        self.backend.end_element();
+    }
+
+}
+
+
+/// The given points mapped by the given transform.
+fn map_points(transform: &Transform2D, points: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    points.iter().map(|&p| geometry::transform_point(transform, p)).collect()
+}
@@ -0,0 +1,244 @@
+//!
+//! A flat list of the draw commands produced by an `Element`.
+//!
+//! Walking an `Element` tree resolves its layout, the transforms of its `Form`s and the opacity
+//! of everything within it. A `DisplayList` records the result as a sequence of `Command`s, each
+//! described entirely in view space, which may then be replayed to any `Backend`. This decouples
+//! traversal from rendering, so that frames may be recorded, replayed, compared with one another
+//! or (with the `serde` feature) serialized.
+//!
+
+use color::Color;
+use element::{Element, ImageStyle};
+use form::{FillStyle, LineStyle};
+use length::{self, Insets};
+use std::path::{Path, PathBuf};
+use super::Backend;
+use text::Text;
+use transform_2d::{self, Transform2D};
+
+
+/// A single call to a `Backend`. See the `Backend` method of the same name for each.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Command {
+    Clear(Color),
+    FillPolygon {
+        transform: Transform2D,
+        points: Vec<(f64, f64)>,
+        style: FillStyle,
+        alpha: f32,
+    },
+    StrokePath {
+        transform: Transform2D,
+        points: Vec<(f64, f64)>,
+        closed: bool,
+        style: LineStyle,
+        alpha: f32,
+    },
+    DrawText {
+        transform: Transform2D,
+        text: Text,
+        outline: Option<LineStyle>,
+        alpha: f32,
+    },
+    DrawImage {
+        transform: Transform2D,
+        style: ImageStyle,
+        size: (i32, i32),
+        path: PathBuf,
+        alpha: f32,
+    },
+    PushClip((f64, f64, f64, f64)),
+    PushClipShape {
+        transform: Transform2D,
+        points: Vec<(f64, f64)>,
+    },
+    PopClip,
+}
+
+
+/// The draw commands for a render target of some size.
+///
+/// A `DisplayList` is itself a `Backend`, recording every command drawn to it.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DisplayList {
+    /// The width of the render target in view space.
+    pub width: f64,
+    /// The height of the render target in view space.
+    pub height: f64,
+    /// The DPI of the render target, used to resolve physical `Length`s.
+    pub dpi: f64,
+    /// The time in seconds at which the frame is drawn, used by animated `Element`s.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub time: f64,
+    /// The time in seconds since the previous frame.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub dt: f64,
+    /// The insets from each edge of the render target within which content is unobscured.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub safe_area: Insets,
+    pub commands: Vec<Command>,
+}
+
+
+impl DisplayList {
+
+    /// Construct an empty display list for a render target of the given size.
+    pub fn new(width: f64, height: f64) -> DisplayList {
+        DisplayList {
+            width: width,
+            height: height,
+            dpi: length::DEFAULT_DPI,
+            time: 0.0,
+            dt: 0.0,
+            safe_area: Insets::none(),
+            commands: Vec::new(),
+        }
+    }
+
+    /// Builder method for a display list whose render target has the given DPI.
+    pub fn dpi(self, dpi: f64) -> DisplayList {
+        DisplayList { dpi: dpi, ..self }
+    }
+
+    /// Builder method for a display list drawn at the given time in seconds, such as the time
+    /// since an animation began.
+    pub fn time(self, time: f64) -> DisplayList {
+        DisplayList { time: time, ..self }
+    }
+
+    /// Builder method for a display list drawn the given number of seconds after the previous
+    /// frame.
+    pub fn dt(self, dt: f64) -> DisplayList {
+        DisplayList { dt: dt, ..self }
+    }
+
+    /// Builder method for a display list whose render target has the given safe area, such as
+    /// the area clear of a phone's notch. See `element::with_safe_area`.
+    pub fn safe_area(self, safe_area: Insets) -> DisplayList {
+        DisplayList { safe_area: safe_area, ..self }
+    }
+
+    /// The commands for drawing the given `Element` as the root of a render target of the given
+    /// size.
+    pub fn from_element(element: &Element, width: f64, height: f64) -> DisplayList {
+        DisplayList::new(width, height).record(element)
+    }
+
+    /// Builder method that records the commands for drawing the given `Element` as the root of
+    /// the render target, after any commands already recorded.
+    pub fn record(mut self, element: &Element) -> DisplayList {
+        super::draw_element(element, 1.0, transform_2d::identity(), &mut self, &mut None);
+        self
+    }
+
+    /// Issue each of the commands, in order, to the given `Backend`.
+    pub fn replay<B: Backend>(&self, backend: &mut B) {
+        for command in self.commands.iter() {
+            match *command {
+                Command::Clear(color) => backend.clear(color),
+                Command::FillPolygon { ref transform, ref points, ref style, alpha } =>
+                    backend.fill_polygon(transform, points, style, alpha),
+                Command::StrokePath { ref transform, ref points, closed, ref style, alpha } =>
+                    backend.stroke_path(transform, points, closed, style, alpha),
+                Command::DrawText { ref transform, ref text, ref outline, alpha } =>
+                    backend.draw_text(transform, text, outline.as_ref(), alpha),
+                Command::DrawImage { ref transform, style, size, ref path, alpha } =>
+                    backend.draw_image(transform, style, size, path, alpha),
+                Command::PushClip(rect) => backend.push_clip(rect),
+                Command::PushClipShape { ref transform, ref points } =>
+                    backend.push_clip_shape(transform, points),
+                Command::PopClip => backend.pop_clip(),
+            }
+        }
+    }
+
+}
+
+
+impl Backend for DisplayList {
+
+    fn clear(&mut self, color: Color) {
+        self.commands.push(Command::Clear(color));
+    }
+
+    fn fill_polygon(&mut self, transform: &Transform2D, points: &[(f64, f64)],
+                    style: &FillStyle, alpha: f32) {
+        self.commands.push(Command::FillPolygon {
+            transform: transform.clone(),
+            points: points.to_vec(),
+            style: style.clone(),
+            alpha: alpha,
+        });
+    }
+
+    fn stroke_path(&mut self, transform: &Transform2D, points: &[(f64, f64)], closed: bool,
+                   style: &LineStyle, alpha: f32) {
+        self.commands.push(Command::StrokePath {
+            transform: transform.clone(),
+            points: points.to_vec(),
+            closed: closed,
+            style: style.clone(),
+            alpha: alpha,
+        });
+    }
+
+    fn draw_text(&mut self, transform: &Transform2D, text: &Text, outline: Option<&LineStyle>,
+                 alpha: f32) {
+        self.commands.push(Command::DrawText {
+            transform: transform.clone(),
+            text: text.clone(),
+            outline: outline.cloned(),
+            alpha: alpha,
+        });
+    }
+
+    fn draw_image(&mut self, transform: &Transform2D, style: ImageStyle, size: (i32, i32),
+                  path: &Path, alpha: f32) {
+        self.commands.push(Command::DrawImage {
+            transform: transform.clone(),
+            style: style,
+            size: size,
+            path: path.to_path_buf(),
+            alpha: alpha,
+        });
+    }
+
+    fn push_clip(&mut self, rect: (f64, f64, f64, f64)) {
+        self.commands.push(Command::PushClip(rect));
+    }
+
+    fn push_clip_shape(&mut self, transform: &Transform2D, points: &[(f64, f64)]) {
+        self.commands.push(Command::PushClipShape {
+            transform: transform.clone(),
+            points: points.to_vec(),
+        });
+    }
+
+    fn pop_clip(&mut self) {
+        self.commands.push(Command::PopClip);
+    }
+
+    fn target_size(&self) -> (f64, f64) {
+        (self.width, self.height)
+    }
+
+    fn dpi(&self) -> f64 {
+        self.dpi
+    }
+
+    fn time(&self) -> f64 {
+        self.time
+    }
+
+    fn dt(&self) -> f64 {
+        self.dt
+    }
+
+    fn safe_area(&self) -> Insets {
+        self.safe_area
+    }
+
+}
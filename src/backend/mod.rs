@@ -0,0 +1,552 @@
+//!
+//! Backend-agnostic rendering of `Element`s.
+//!
+//! `draw_element` walks an `Element` tree, resolving the layout of each `Element` and the
+//! transform of each `Form`, and hands the resulting primitives to some `Backend`.
+//!
+//! All coordinates given to a `Backend` are relative to some `Transform2D` that maps them into
+//! "view" space. View space matches the coordinate system used by collages: the origin is at the
+//! center of the render target and the y-axis points up.
+//!
+//! The commands given to a `Backend` may also be recorded as a `display_list::DisplayList` and
+//! replayed later, or resolved along with the layout of each `Element` as a
+//! `resolved::ResolvedElement`.
+//!
+
+use color::Color;
+use element::{self, Background, Direction, Element, ImageStyle, Prim};
+use form::{self, BasicForm, FillStyle, Form, LineStyle, PointPath, Shape, ShapeStyle};
+use geometry;
+use length::{self, Insets, Viewport};
+use stats::{self, RenderStats};
+use std::path::Path;
+use std::time::Instant;
+use text::Text;
+use transform_2d::{self, Transform2D};
+
+#[cfg(feature = "piston-backend")]
+use self::piston::CustomDraw;
+
+#[cfg(feature = "canvas")]
+pub mod canvas;
+pub mod clip;
+pub mod display_list;
+#[cfg(feature = "piston-backend")]
+pub mod piston;
+pub mod recording;
+pub mod resolved;
+#[cfg(feature = "tiny-skia")]
+pub mod skia;
+#[cfg(feature = "piston-backend")]
+pub mod text_cache;
+
+
+/// A target that elmesque primitives can be drawn to.
+pub trait Backend {
+
+    /// Clear the entire render target with the given color.
+    fn clear(&mut self, color: Color);
+
+    /// Fill the polygon described by the given points with the given style.
+    fn fill_polygon(&mut self, transform: &Transform2D, points: &[(f64, f64)],
+                    style: &FillStyle, alpha: f32);
+
+    /// Stroke the path along the given points with the given style. If `closed` is true, the last
+    /// point is joined back to the first.
+    fn stroke_path(&mut self, transform: &Transform2D, points: &[(f64, f64)], closed: bool,
+                   style: &LineStyle, alpha: f32);
+
+    /// Draw the given text centered upon the origin. If some `LineStyle` is given, the text should
+    /// be outlined with it rather than filled.
+    fn draw_text(&mut self, transform: &Transform2D, text: &Text, outline: Option<&LineStyle>,
+                 alpha: f32);
+
+    /// Draw the image at the given path centered upon the origin with the given size.
+    fn draw_image(&mut self, transform: &Transform2D, style: ImageStyle, size: (i32, i32),
+                  path: &Path, alpha: f32);
+
+    /// Draw the given custom form, where `transform` maps the form's coordinates into view space.
+    ///
+    /// Only the piston `Renderer` can call the function of a custom form, so other backends skip
+    /// them, as the default does. See `form::custom`.
+    #[cfg(feature = "piston-backend")]
+    fn draw_custom(&mut self, _transform: &Transform2D, _custom: &CustomDraw) {}
+
+    /// Restrict all drawing to the given rectangle until the matching `pop_clip`.
+    ///
+    /// The rectangle is described by its center `x` and `y` along with its width and height in
+    /// view space. Clips nest, so the new clip is the intersection with any current clip.
+    fn push_clip(&mut self, rect: (f64, f64, f64, f64));
+
+    /// Restrict all drawing to the polygon described by the given points until the matching
+    /// `pop_clip`.
+    ///
+    /// `transform` maps the points into view space. As with `push_clip`, clips nest.
+    fn push_clip_shape(&mut self, transform: &Transform2D, points: &[(f64, f64)]);
+
+    /// Remove the most recently pushed clip.
+    fn pop_clip(&mut self);
+
+    /// The width and height of the render target in view space.
+    fn target_size(&self) -> (f64, f64);
+
+    /// The number of view space units within an inch, used to resolve physical `Length`s.
+    fn dpi(&self) -> f64 {
+        length::DEFAULT_DPI
+    }
+
+    /// The time in seconds at which the frame is drawn, used to select the frames of animations.
+    fn time(&self) -> f64 {
+        0.0
+    }
+
+    /// The time in seconds since the previous frame was drawn.
+    fn dt(&self) -> f64 {
+        0.0
+    }
+
+    /// The insets by which the edges of the render target are obscured.
+    fn safe_area(&self) -> Insets {
+        Insets::none()
+    }
+
+    /// Called before drawing the given `Element`, where `transform` maps the `Element`'s local
+    /// coordinates into view space and `opacity` is that inherited from its ancestors.
+    /// Everything drawn until the matching `end_element` belongs to the `Element`, including the
+    /// `Element`s nested within it.
+    ///
+    /// Backends that only draw may ignore this, as the default does.
+    fn begin_element(&mut self, _element: &Element, _transform: &Transform2D, _opacity: f32) {}
+
+    /// Called after drawing the `Element` of the matching `begin_element`.
+    fn end_element(&mut self) {}
+
+}
+
+
+/// The transform that maps view space to some target of the given size, where the target's
+/// origin is at the top left and the y-axis points down.
+pub fn view_to_target(width: f64, height: f64) -> Transform2D {
+    transform_2d::matrix(1.0, 0.0, 0.0, -1.0, width / 2.0, height / 2.0)
+}
+
+
+/// Options describing how shapes should be rasterized by a renderer.
+///
+/// By default shapes are anti-aliased and drawn exactly where they fall, which suits vector art.
+/// Pixel art usually wants `crisp` rendering instead, where edges are snapped to whole pixels.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RenderOptions {
+    /// Whether or not the points of fills and strokes are snapped to the nearest device pixel.
+    pub pixel_snap: bool,
+    /// Whether or not edges are anti-aliased. This is a preference - renderers that can't control
+    /// anti-aliasing ignore it.
+    pub anti_alias: bool,
+    /// The number of pixels within an inch of the render target, used to resolve physical
+    /// `Length`s.
+    pub dpi: f64,
+    /// The time in seconds at which the frame is drawn, used to select the frames of animations
+    /// such as `element::animated_image`.
+    pub time: f64,
+    /// The time in seconds since the previous frame was drawn.
+    pub dt: f64,
+    /// Whether or not gradients are interpolated in linear RGB rather than sRGB, avoiding the dark
+    /// band between bright colors. See `Gradient::in_linear_rgb`.
+    ///
+    /// Translucent colors are blended with whatever lies beneath them by the render target
+    /// itself. For that blending to happen in linear RGB too, draw to an sRGB render target, e.g.
+    /// a window with an sRGB framebuffer.
+    pub linear_blending: bool,
+    /// The insets by which the edges of the render target are obscured, i.e. by a notch or
+    /// overscan, as reported by the windowing system. See `element::with_safe_area`.
+    pub safe_area: Insets,
+}
+
+impl RenderOptions {
+
+    /// Options for crisp pixel art, with pixel snapping and without anti-aliasing.
+    pub fn crisp() -> RenderOptions {
+        RenderOptions { pixel_snap: true, anti_alias: false, ..RenderOptions::default() }
+    }
+
+    /// Builder method for snapping (or not snapping) shapes to device pixels.
+    pub fn pixel_snap(self, pixel_snap: bool) -> RenderOptions {
+        RenderOptions { pixel_snap: pixel_snap, ..self }
+    }
+
+    /// Builder method for preferring (or not preferring) anti-aliased edges.
+    pub fn anti_alias(self, anti_alias: bool) -> RenderOptions {
+        RenderOptions { anti_alias: anti_alias, ..self }
+    }
+
+    /// Builder method for a render target with the given DPI.
+    pub fn dpi(self, dpi: f64) -> RenderOptions {
+        RenderOptions { dpi: dpi, ..self }
+    }
+
+    /// Builder method for a frame drawn at the given time in seconds.
+    pub fn time(self, time: f64) -> RenderOptions {
+        RenderOptions { time: time, ..self }
+    }
+
+    /// Builder method for a frame drawn the given number of seconds after the previous frame.
+    pub fn dt(self, dt: f64) -> RenderOptions {
+        RenderOptions { dt: dt, ..self }
+    }
+
+    /// Builder method for interpolating (or not interpolating) gradients in linear RGB.
+    pub fn linear_blending(self, linear_blending: bool) -> RenderOptions {
+        RenderOptions { linear_blending: linear_blending, ..self }
+    }
+
+    /// Builder method for a render target whose edges are obscured by the given insets.
+    pub fn safe_area(self, safe_area: Insets) -> RenderOptions {
+        RenderOptions { safe_area: safe_area, ..self }
+    }
+
+}
+
+impl Default for RenderOptions {
+    fn default() -> RenderOptions {
+        RenderOptions {
+            pixel_snap: false,
+            anti_alias: true,
+            dpi: length::DEFAULT_DPI,
+            time: 0.0,
+            dt: 0.0,
+            linear_blending: false,
+            safe_area: Insets::none(),
+        }
+    }
+}
+
+
+/// The given points moved so that they land upon the nearest corners of the pixels of a target
+/// of the given size, where `transform` maps the points into view space.
+///
+/// The snapped points are returned in the same coordinates as the given points, or unchanged if
+/// the transform can't be inverted.
+pub fn snap_to_pixels(transform: &Transform2D, points: &[(f64, f64)], (width, height): (f64, f64))
+    -> Vec<(f64, f64)>
+{
+    let to_target = view_to_target(width, height).multiply(transform.clone());
+    let from_target = match to_target.invert() {
+        Some(inverse) => inverse,
+        None => return points.to_vec(),
+    };
+    points.iter().map(|&p| {
+        let (x, y) = geometry::transform_point(&to_target, p);
+        geometry::transform_point(&from_target, (x.round(), y.round()))
+    }).collect()
+}
+
+
+/// Draw an `Element` to the given `Backend`.
+///
+/// `transform` maps the `Element`'s local coordinates (where the origin is the `Element`'s
+/// center) into view space.
+///
+/// The `Element` is drawn as the root of the frame. Only a root `clear` clears the render
+/// target - `Element`s cleared anywhere within it only fill their own area with the color.
+pub fn draw_element<B: Backend>(
+    element: &Element,
+    opacity: f32,
+    transform: Transform2D,
+    backend: &mut B,
+    maybe_stats: &mut Option<&mut RenderStats>,
+) {
+    draw_node(element, opacity, transform, backend, maybe_stats, true)
+}
+
+
+/// Draw an `Element` that isn't the root of the frame, such as one embedded within a GUI drawn by
+/// other means, so that a `clear` only fills the `Element`'s own area.
+///
+/// `transform` maps the `Element`'s local coordinates into view space, as for `draw_element`.
+pub fn draw_child_element<B: Backend>(
+    element: &Element,
+    opacity: f32,
+    transform: Transform2D,
+    backend: &mut B,
+    maybe_stats: &mut Option<&mut RenderStats>,
+) {
+    draw_node(element, opacity, transform, backend, maybe_stats, false)
+}
+
+
+/// Draw an `Element` that is the root of the frame if `is_root` is true.
+fn draw_node<B: Backend>(
+    element: &Element,
+    opacity: f32,
+    transform: Transform2D,
+    backend: &mut B,
+    maybe_stats: &mut Option<&mut RenderStats>,
+    is_root: bool,
+) {
+    // Hidden Elements are still begun and ended so that they keep their place in the layout
+    // resolved by backends such as `Resolver`, even though nothing is drawn for them.
+    backend.begin_element(element, &transform, opacity);
+    if element.is_hidden() {
+        backend.end_element();
+        return;
+    }
+    let (content_size, (scale_x, scale_y)) = (element.content_size(), element.content_scale());
+    let Element { ref props, ref element } = *element;
+
+    // Only bother timing the subtree if we're collecting stats.
+    let start = maybe_stats.as_ref().map(|_| Instant::now());
+    stats::record(maybe_stats, |stats| stats.begin_element());
+
+    if let Some(rect) = props.crop {
+        backend.push_clip(rect);
+    }
+    if let Some((x, y, w, h)) = props.local_crop {
+        let Shape(ref points) = form::rect(w, h);
+        let crop_transform = transform.clone().multiply(transform_2d::translation(x, y));
+        backend.push_clip_shape(&crop_transform, points);
+    }
+
+    // Content within rounded corners is clipped to the same rounded rectangle as the background.
+    let (width, height) = (props.width as f64, props.height as f64);
+    let Shape(outline) = form::rounded_rect(width, height, props.corner_radius);
+    let rounded = props.corner_radius > 0.0;
+    if rounded {
+        backend.push_clip_shape(&transform, &outline);
+    }
+
+    let opacity = opacity * props.opacity;
+    if let Some(ref background) = props.background {
+        draw_background(background, props.width, props.height, &outline, opacity, &transform,
+                        backend, maybe_stats);
+    }
+
+    // Scale the content to fill the Element if it has been resized.
+    let transform = transform.multiply(transform_2d::matrix(scale_x, 0.0, 0.0, scale_y, 0.0, 0.0));
+
+    match *element {
+
+        Prim::Image(style, w, h, ref path) => {
+            backend.draw_image(&transform, style, (w, h), path, opacity);
+            stats::record(maybe_stats, |stats| stats.draw_calls += 1);
+        },
+
+        Prim::Container(position, ref element) => {
+            let (x, y) = position.offset(content_size, element.get_size());
+            let transform = transform.multiply(transform_2d::translation(x, y));
+            draw_node(element, opacity, transform, backend, maybe_stats, false);
+        },
+
+        Prim::Flow(direction, ref elements) => {
+            let offsets = element::flow_offsets(direction, content_size, elements);
+            let mut children: Vec<_> = elements.iter().zip(offsets).collect();
+            if let Direction::In = direction {
+                children.reverse();
+            }
+            for (element, (x, y)) in children {
+                let transform = transform.clone().multiply(transform_2d::translation(x, y));
+                draw_node(element, opacity, transform, backend, maybe_stats, false);
+            }
+        },
+
+        Prim::Collage(_, _, ref forms) => {
+            for form in forms.iter() {
+                draw_form(form, opacity, transform.clone(), backend, maybe_stats);
+            }
+        },
+
+        Prim::Cleared(color, ref element) => {
+            if is_root {
+                backend.clear(color.alpha(opacity));
+            } else {
+                let Shape(ref points) = form::rect(content_size.0 as f64, content_size.1 as f64);
+                backend.fill_polygon(&transform, points, &FillStyle::Solid(color), opacity);
+            }
+            stats::record(maybe_stats, |stats| stats.draw_calls += 1);
+            draw_node(element, opacity, transform, backend, maybe_stats, false);
+        },
+
+        Prim::Responsive(ref responsive) => {
+            let element = responsive.element(&viewport(backend));
+            draw_node(&element, opacity, transform, backend, maybe_stats, is_root);
+        },
+
+        Prim::Fill(color) => {
+            let (w, h) = backend.target_size();
+            let Shape(ref points) = form::rect(w, h);
+            backend.fill_polygon(&transform_2d::identity(), points, &FillStyle::Solid(color),
+                                 opacity);
+            stats::record(maybe_stats, |stats| stats.draw_calls += 1);
+        },
+
+        Prim::Spacer => {},
+
+    }
+
+    if rounded {
+        backend.pop_clip();
+    }
+    if props.local_crop.is_some() {
+        backend.pop_clip();
+    }
+    if props.crop.is_some() {
+        backend.pop_clip();
+    }
+
+    backend.end_element();
+    if let Some(start) = start {
+        let duration = start.elapsed();
+        stats::record(maybe_stats, |stats| stats.end_element(props.width, props.height, duration));
+    }
+}
+
+
+/// The viewport of the frame being drawn to the given `Backend`.
+fn viewport<B: Backend>(backend: &B) -> Viewport {
+    let (w, h) = backend.target_size();
+    Viewport::new(w, h).dpi(backend.dpi()).time(backend.time()).dt(backend.dt())
+        .safe_area(backend.safe_area())
+}
+
+
+/// Draw a `Form` to the given `Backend`.
+///
+/// `transform` maps the coordinates of the collage in which the `Form` resides into view space.
+pub fn draw_form<B: Backend>(
+    form: &Form,
+    alpha: f32,
+    transform: Transform2D,
+    backend: &mut B,
+    maybe_stats: &mut Option<&mut RenderStats>,
+) {
+    let alpha = form.inherited_alpha(alpha);
+
+    // Skip forms that are hidden or would be invisible anyway.
+    if !form.visible || alpha <= 0.0 {
+        stats::record(maybe_stats, |stats| stats.forms_culled += 1);
+        return;
+    }
+    stats::record(maybe_stats, |stats| stats.forms_drawn += 1);
+
+    let transform = transform.multiply(form.transform());
+
+    match form.form {
+
+        BasicForm::PointPath(ref style, PointPath(ref points)) => {
+            backend.stroke_path(&transform, points, false, style, alpha);
+            stats::record(maybe_stats, |stats| stats.draw_calls += 1);
+        },
+
+        BasicForm::Shape(ShapeStyle::Line(ref style), Shape(ref points)) => {
+            backend.stroke_path(&transform, points, true, style, alpha);
+            stats::record(maybe_stats, |stats| stats.draw_calls += 1);
+        },
+
+        BasicForm::Shape(ShapeStyle::Fill(ref style), Shape(ref points)) => {
+            backend.fill_polygon(&transform, points, style, alpha);
+            stats::record(maybe_stats, |stats| stats.draw_calls += 1);
+        },
+
+        BasicForm::Shape(ShapeStyle::FillAndLine(ref fill, ref line), Shape(ref points)) => {
+            backend.fill_polygon(&transform, points, fill, alpha);
+            backend.stroke_path(&transform, points, true, line, alpha);
+            stats::record(maybe_stats, |stats| stats.draw_calls += 2);
+        },
+
+        BasicForm::OutlinedText(ref style, ref text) => {
+            backend.draw_text(&transform, text, Some(style), alpha);
+            record_text(maybe_stats, text);
+        },
+
+        BasicForm::Text(ref text) => {
+            backend.draw_text(&transform, text, None, alpha);
+            record_text(maybe_stats, text);
+        },
+
+        BasicForm::Image(w, h, (src_x, src_y), ref path) => {
+            backend.draw_image(&transform, ImageStyle::Cropped(src_x, src_y), (w, h), path, alpha);
+            stats::record(maybe_stats, |stats| stats.draw_calls += 1);
+        },
+
+        BasicForm::Element(ref element) =>
+            draw_node(element, alpha, transform, backend, maybe_stats, false),
+
+        BasicForm::Group(ref group_transform, ref forms) => {
+            let transform = transform.multiply(group_transform.clone());
+            for form in forms.iter() {
+                draw_form(form, alpha, transform.clone(), backend, maybe_stats);
+            }
+        },
+
+        BasicForm::Positioned(ref forms) => {
+            for &(ref child_transform, ref form) in forms.iter() {
+                let transform = transform.clone().multiply(child_transform.clone());
+                draw_form(form, alpha, transform, backend, maybe_stats);
+            }
+        },
+
+        BasicForm::Instanced(ref form, ref instances) => {
+            for instance in instances.iter() {
+                let transform = transform.clone().multiply(instance.transform());
+                draw_form(form, alpha, transform, backend, maybe_stats);
+            }
+        },
+
+        BasicForm::Lod(ref levels) => {
+            if let Some(form) = form::lod_level(levels, geometry::transform_scale(&transform)) {
+                draw_form(form, alpha, transform, backend, maybe_stats);
+            }
+        },
+
+        BasicForm::Animated(ref animation) => {
+            let form = animation.form(&viewport(backend));
+            draw_form(&form, alpha, transform, backend, maybe_stats);
+        },
+
+        #[cfg(feature = "piston-backend")]
+        BasicForm::Custom(ref custom) => {
+            backend.draw_custom(&transform, custom);
+            stats::record(maybe_stats, |stats| stats.draw_calls += 1);
+        },
+
+    }
+}
+
+
+/// Fill the area of an `Element` of the given size with its `Background`, within the given
+/// outline.
+fn draw_background<B: Backend>(
+    background: &Background,
+    width: i32,
+    height: i32,
+    outline: &[(f64, f64)],
+    opacity: f32,
+    transform: &Transform2D,
+    backend: &mut B,
+    maybe_stats: &mut Option<&mut RenderStats>,
+) {
+    if opacity <= 0.0 || width <= 0 || height <= 0 {
+        return;
+    }
+    match *background {
+        Background::Solid(color) =>
+            backend.fill_polygon(transform, outline, &FillStyle::Solid(color), opacity),
+        Background::Grad(ref gradient) =>
+            backend.fill_polygon(transform, outline, &FillStyle::Grad(gradient.clone()), opacity),
+        Background::Image(ref path) =>
+            backend.draw_image(transform, ImageStyle::Fitted, (width, height), path, opacity),
+    }
+    stats::record(maybe_stats, |stats| stats.draw_calls += 1);
+}
+
+
+/// Record the draw calls and glyphs used by some text.
+fn record_text(maybe_stats: &mut Option<&mut RenderStats>, text: &Text) {
+    stats::record(maybe_stats, |stats| {
+        stats.draw_calls += text.sequence.len();
+        stats.draw_calls += text.sequence.iter().filter(|unit| unit.style.background.is_some())
+            .count();
+        stats.text_glyphs += text.sequence.iter()
+            .map(|unit| unit.string.chars().filter(|c| !c.is_whitespace()).count())
+            .sum::<usize>();
+    });
+}
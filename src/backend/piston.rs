@@ -0,0 +1,856 @@
+//!
+//! Rendering of `Element`s using the generic [Piston graphics]
+//! (https://github.com/PistonDevelopers/graphics) backend.
+//!
+//! This module is only available with the `piston-backend` feature, which is enabled by default.
+//! The `Renderer` implements `Backend`, so `Element`s are walked exactly as for every other
+//! backend. Unlike the others, it draws text via a piston `CharacterCache` given to the
+//! `Renderer`, and it is the only backend that draws `form::custom` forms.
+//!
+//! As conrod also draws with piston graphics, an `Element` may be embedded within a conrod GUI by
+//! drawing it from a custom widget with `Element::draw_in_rect`.
+//!
+//! The `Renderer` keeps a stack of transforms and a stack of draw states, which drawing an
+//! `Element` pushes to and pops from as it descends the tree. Pushing a transform or scissor
+//! before drawing applies it to everything drawn until it is popped. Rectangular crops are mapped
+//! onto the draw state's scissor, while clip shapes such as rounded corners are drawn into the
+//! stencil buffer, so the render target must have one for them to take effect.
+//!
+
+use backend::{self, Backend, RenderOptions};
+use backend::display_list::DisplayList;
+use backend::resolved::ResolvedElement;
+use color::Color;
+use element::{Element, ImageStyle};
+use error::Error;
+use form::{FillStyle, Form, LineCap, LineStyle};
+use graphics::{self, Context, DrawState, Graphics, Transformed};
+use graphics::character::CharacterCache;
+use graphics::draw_state::state::{self, Comparison, StencilOp, StencilSide};
+use graphics::math::Matrix2d;
+use geometry::{self, Rect};
+use length::Insets;
+use stats::{self, RenderStats};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Range;
+use std::path::Path;
+use std::sync::Arc;
+use text::{BACKGROUND_PADDING, Text, TextAnchor, TextUnit};
+use transform_2d::{self, Transform2D};
+use utils;
+
+#[cfg(feature = "tiny-skia")]
+use backend::skia;
+#[cfg(feature = "tiny-skia")]
+use tiny_skia::Pixmap;
+
+
+/// Used for rendering elmesque `Element`s.
+pub struct Renderer<'a, C: 'a, G: 'a> {
+    context: Context,
+    backend: &'a mut G,
+    maybe_character_cache: Option<&'a mut C>,
+    maybe_stats: Option<&'a mut RenderStats>,
+    options: RenderOptions,
+    /// The transforms to restore upon each `pop_transform`.
+    transforms: Vec<Matrix2d>,
+    /// The draw states (and so scissors) to restore upon each `pop_scissor`.
+    draw_states: Vec<DrawState>,
+    /// The clips pushed by the `Backend` methods, to be undone upon each `pop_clip`.
+    clips: Vec<Clip>,
+    /// The first error met while drawing, after which nothing more is drawn.
+    error: Option<Error>,
+}
+
+/// A clip pushed via the `Backend` methods.
+enum Clip {
+    /// A rectangle, mapped onto the draw state's scissor.
+    Scissor,
+    /// A polygon with the given transform, whose pixels were incremented in the stencil buffer.
+    Stencil(Matrix2d, Vec<[f64; 2]>),
+}
+
+impl<'a, C, G> Renderer<'a, C, G> {
+
+    /// Construct a renderer, used for rendering elmesque `Element`s.
+    pub fn new(context: Context, backend: &'a mut G) -> Renderer<'a, C, G> {
+        Renderer {
+            context: context,
+            backend: backend,
+            maybe_character_cache: None,
+            maybe_stats: None,
+            options: RenderOptions::default(),
+            transforms: Vec::new(),
+            draw_states: Vec::new(),
+            clips: Vec::new(),
+            error: None,
+        }
+    }
+
+    /// A renderer borrowing the parts given to the free drawing functions.
+    fn from_parts<'b>(
+        context: Context,
+        backend: &'b mut G,
+        maybe_character_cache: &'b mut Option<&mut C>,
+        maybe_stats: &'b mut Option<&mut RenderStats>,
+        options: RenderOptions,
+    ) -> Renderer<'b, C, G> {
+        Renderer {
+            context: context,
+            backend: backend,
+            maybe_character_cache: maybe_character_cache.as_mut().map(|cache| &mut **cache),
+            maybe_stats: maybe_stats.as_mut().map(|stats| &mut **stats),
+            options: options,
+            transforms: Vec::new(),
+            draw_states: Vec::new(),
+            clips: Vec::new(),
+            error: None,
+        }
+    }
+
+    /// Builder method for constructing a Renderer with a GlyphCache for drawing text.
+    ///
+    /// Wrap the cache in a `text_cache::TextCache` kept between frames to avoid measuring the same
+    /// text every frame.
+    pub fn character_cache(self, character_cache: &'a mut C) -> Renderer<'a, C, G> {
+        Renderer { maybe_character_cache: Some(character_cache), ..self }
+    }
+
+    /// Builder method for constructing a Renderer that fills the given `RenderStats` while drawing.
+    pub fn stats(self, stats: &'a mut RenderStats) -> Renderer<'a, C, G> {
+        Renderer { maybe_stats: Some(stats), ..self }
+    }
+
+    /// Builder method for constructing a Renderer that rasterizes with the given `RenderOptions`.
+    ///
+    /// Piston graphics leaves anti-aliasing to the window's multisampling, so `anti_alias` is
+    /// ignored. Enable multisampling on the window (or not) to match it.
+    pub fn options(self, options: RenderOptions) -> Renderer<'a, C, G> {
+        Renderer { options: options, ..self }
+    }
+
+    /// Builder method for constructing a Renderer that draws to a render target of the given size
+    /// in pixels, such as a texture or framebuffer object.
+    ///
+    /// Crops and pixel snapping are mapped onto the `Context`'s viewport, which offscreen targets
+    /// may lack. This replaces the viewport and view with those of the target, so that view space
+    /// spans the target with one unit per pixel, keeping the `Context`'s draw state.
+    pub fn target_size(self, width: u32, height: u32) -> Renderer<'a, C, G> {
+        let viewport = graphics::Viewport {
+            rect: [0, 0, width as i32, height as i32],
+            draw_size: [width, height],
+            window_size: [width, height],
+        };
+        let Context { view, transform, .. } = Context::new_abs(width as f64, height as f64);
+        let context = Context {
+            viewport: Some(viewport),
+            view: view,
+            transform: transform,
+            ..self.context
+        };
+        Renderer { context: context, ..self }
+    }
+
+    /// Builder method for constructing a Renderer that draws the frame at the given time, the
+    /// given number of seconds after the previous frame. See `RenderOptions::time`.
+    pub fn time(self, time: f64, dt: f64) -> Renderer<'a, C, G> {
+        let options = self.options.time(time).dt(dt);
+        Renderer { options: options, ..self }
+    }
+
+    /// Render the given Element to a new image the size of the renderer's view, e.g. so that an
+    /// application may export the current collage with `Pixmap::save_png`.
+    ///
+    /// The image is drawn in software by the tiny-skia backend with the renderer's options,
+    /// rather than read back from the render target, so it holds only the Element. Text isn't
+    /// drawn, as the tiny-skia backend can't yet load glyphs. Returns `None` if the view is empty.
+    #[cfg(feature = "tiny-skia")]
+    pub fn screenshot(&self, element: &Element) -> Option<Pixmap> {
+        let [w, h] = self.context.get_view_size();
+        skia::render_with_options(element, w.round() as u32, h.round() as u32, self.options)
+    }
+
+    /// The context within which the renderer currently draws, with every pushed transform and
+    /// scissor applied.
+    pub fn context(&self) -> Context {
+        self.context
+    }
+
+    /// Apply the given transform within the current one to everything drawn until the matching
+    /// call to `pop_transform`.
+    ///
+    /// Outside of drawing an `Element`, the current transform is that of the renderer's
+    /// `Context`, with the origin at the top left of the view and the y-axis pointing down.
+    /// While drawing, it is that of the `Element` or `Form` being drawn.
+    pub fn push_transform(&mut self, transform: Transform2D) {
+        self.transforms.push(self.context.transform);
+        let Transform2D(matrix) = Transform2D(self.context.transform).multiply(transform);
+        self.context.transform = matrix;
+    }
+
+    /// Restore the transform from before the last call to `push_transform`.
+    pub fn pop_transform(&mut self) {
+        if let Some(transform) = self.transforms.pop() {
+            self.context.transform = transform;
+        }
+    }
+
+    /// Restrict everything drawn until the matching call to `pop_scissor` to the given rectangle
+    /// of the view, within the current scissor.
+    ///
+    /// The rectangle is described by its center and size in view space, with the origin at the
+    /// center of the view and the y-axis pointing up, as for `Element::crop`.
+    pub fn push_scissor(&mut self, rect: (f64, f64, f64, f64)) {
+        self.draw_states.push(self.context.draw_state);
+        self.context = scissor(self.context, rect);
+    }
+
+    /// Restore the scissor from before the last call to `push_scissor`.
+    pub fn pop_scissor(&mut self) {
+        if let Some(draw_state) = self.draw_states.pop() {
+            self.context.draw_state = draw_state;
+        }
+    }
+
+    /// Call the given function with the given transform pushed, popping it again afterwards.
+    fn with_transform<F, T>(&mut self, transform: Transform2D, f: F) -> T
+        where
+            F: FnOnce(&mut Self) -> T,
+    {
+        self.push_transform(transform);
+        let result = f(self);
+        self.pop_transform();
+        result
+    }
+
+    /// The number of clip shapes currently drawn into the stencil buffer.
+    fn stencil_depth(&self) -> u8 {
+        let depth = self.clips.iter().filter(|clip| matches!(**clip, Clip::Stencil(..))).count();
+        depth.min(u8::MAX as usize) as u8
+    }
+
+}
+
+
+/// Draw an Element.
+///
+/// The Element is drawn as the root of the frame within the context's coordinates. Only a root
+/// `clear` clears the render target - Elements cleared anywhere within it only fill their own
+/// area with the color.
+pub fn draw_element<C: CharacterCache, G: Graphics<Texture=C::Texture>>(
+    element: &Element,
+    opacity: f32,
+    backend: &mut G,
+    maybe_character_cache: &mut Option<&mut C>,
+    maybe_stats: &mut Option<&mut RenderStats>,
+    options: RenderOptions,
+    context: Context,
+) -> Result<(), Error> {
+    let mut renderer = Renderer::from_parts(context, backend, maybe_character_cache, maybe_stats,
+                                            options);
+    renderer.walk(|renderer, maybe_stats| {
+        backend::draw_element(element, opacity, transform_2d::identity(), renderer, maybe_stats)
+    })
+}
+
+
+/// Restrict drawing to the given rectangle of the view, described by its center and size in the
+/// same centered-origin coordinates as `Element::crop`.
+fn scissor(context: Context, rect: (f64, f64, f64, f64)) -> Context {
+    let [x, y, w, h] = utils::view_rect_to_scissor(&context, rect).unwrap_or([0; 4]);
+    Context { draw_state: context.draw_state.scissor(x, y, w, h), ..context }
+}
+
+
+/// A draw state that applies the given operation to the stencil value of each pixel it covers
+/// where that value equals `value`, without drawing any color.
+fn stencil_write(draw_state: DrawState, value: u8, op: StencilOp) -> DrawState {
+    let side = StencilSide {
+        fun: Comparison::Equal,
+        value: value,
+        mask_read: u8::MAX,
+        mask_write: u8::MAX,
+        op_fail: StencilOp::Keep,
+        op_depth_fail: StencilOp::Keep,
+        op_pass: op,
+    };
+    DrawState {
+        stencil: Some(state::Stencil { front: side, back: side }),
+        color_mask: state::MASK_NONE,
+        ..draw_state
+    }
+}
+
+
+/// The given points in the context's coordinates, snapped to the nearest corners of the device
+/// pixels if the options ask for it.
+fn snapped(context: &Context, options: RenderOptions, points: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    if !options.pixel_snap {
+        return points.to_vec();
+    }
+    let draw_size = match context.viewport {
+        Some(viewport) => (viewport.draw_size[0] as f64, viewport.draw_size[1] as f64),
+        None => {
+            let [w, h] = context.get_view_size();
+            (w, h)
+        },
+    };
+    // The context's transform maps to normalized device coordinates, which span two units.
+    let (w, h) = draw_size;
+    let to_draw = transform_2d::matrix(w / 2.0, 0.0, 0.0, h / 2.0, 0.0, 0.0)
+        .multiply(Transform2D(context.transform));
+    backend::snap_to_pixels(&to_draw, points, draw_size)
+}
+
+
+/// The transform from view space, with the origin at the center of the view and the y-axis
+/// pointing up, to that of the given context.
+fn view_transform(context: &Context) -> Transform2D {
+    let view_size = context.get_view_size();
+    transform_2d::translation(view_size[0] / 2.0, view_size[1] / 2.0)
+        .multiply(transform_2d::scale_y(-1.0))
+}
+
+
+impl Element {
+
+    /// Draw the form with some given graphics backend.
+    ///
+    /// Drawing stops at the first `Element` or `Form` that can't be drawn, returning the `Error`.
+    #[inline]
+    pub fn draw<C, G>(&self, renderer: &mut Renderer<'_, C, G>) -> Result<(), Error>
+        where
+            C: CharacterCache,
+            G: Graphics<Texture=C::Texture>,
+    {
+        self.draw_transformed(renderer, transform_2d::identity())
+    }
+
+    /// Draw the Element as the root of the frame with the given transform applied to the whole
+    /// scene, e.g. to zoom and pan a canvas or to shake the screen.
+    ///
+    /// The transform is in view space, with the origin at the center of the target and the y-axis
+    /// pointing up. `Element::crop` rectangles are also in view space and so aren't transformed,
+    /// while `local_crop`s follow the transform.
+    pub fn draw_transformed<C, G>(&self, renderer: &mut Renderer<'_, C, G>,
+                                  transform: Transform2D) -> Result<(), Error>
+        where
+            C: CharacterCache,
+            G: Graphics<Texture=C::Texture>,
+    {
+        let view = view_transform(&renderer.context);
+        renderer.with_transform(view, |renderer| renderer.walk(|renderer, maybe_stats| {
+            backend::draw_element(self, 1.0, transform, renderer, maybe_stats)
+        }))
+    }
+
+    /// Draw the Element centered within the given rectangle of the render target, clipped to it.
+    ///
+    /// The rectangle is in view space, with the origin at the center of the target and the y-axis
+    /// pointing up - the same coordinates that conrod uses for its widgets' rectangles, so this
+    /// may be called from a custom widget's drawing to embed an Element within a GUI. The Element
+    /// is not the root of the frame, so a `clear` only fills its own area.
+    pub fn draw_in_rect<C, G>(&self, rect: Rect, renderer: &mut Renderer<'_, C, G>)
+        -> Result<(), Error>
+        where
+            C: CharacterCache,
+            G: Graphics<Texture=C::Texture>,
+    {
+        let (x, y) = rect.center();
+        let transform = transform_2d::translation(x, y);
+        let view = view_transform(&renderer.context);
+        renderer.push_scissor((x, y, rect.width(), rect.height()));
+        let result = renderer.with_transform(view, |renderer| renderer.walk(|renderer, stats| {
+            backend::draw_child_element(self, 1.0, transform, renderer, stats)
+        }));
+        renderer.pop_scissor();
+        result
+    }
+
+}
+
+
+impl<'a, C, G> Renderer<'a, C, G>
+    where
+        C: CharacterCache,
+        G: Graphics<Texture=C::Texture>,
+{
+
+    /// Draw each of the commands of the given `DisplayList` in order.
+    ///
+    /// As with `Element::draw`, drawing stops at the first command that can't be drawn.
+    pub fn draw_display_list(&mut self, display_list: &DisplayList) -> Result<(), Error> {
+        let clips = self.clips.len();
+        let root = view_transform(&self.context);
+        self.with_transform(root, |renderer| display_list.replay(renderer));
+        self.finish(clips)
+    }
+
+    /// Draw the frame of the given `ResolvedElement`. See `draw_display_list`.
+    pub fn draw_resolved(&mut self, resolved: &ResolvedElement) -> Result<(), Error> {
+        self.draw_display_list(&resolved.display_list)
+    }
+
+    /// Walk some `Element` or `Form` with the given function, passing it the renderer as the
+    /// `Backend` along with the renderer's stats, and return the first error met.
+    ///
+    /// The walker records the stats itself, so the renderer's own recording is suspended for the
+    /// walk to avoid counting draw calls twice.
+    fn walk<F>(&mut self, f: F) -> Result<(), Error>
+        where
+            F: FnOnce(&mut Self, &mut Option<&mut RenderStats>),
+    {
+        let clips = self.clips.len();
+        let mut maybe_stats = self.maybe_stats.take();
+        f(self, &mut maybe_stats);
+        self.maybe_stats = maybe_stats;
+        self.finish(clips)
+    }
+
+    /// Call the given drawing function with the given transform pushed, unless drawing has
+    /// already failed, keeping the first error.
+    fn draw_with<F>(&mut self, transform: &Transform2D, f: F)
+        where
+            F: FnOnce(&mut Self) -> Result<(), Error>,
+    {
+        if self.error.is_some() {
+            return;
+        }
+        if let Err(error) = self.with_transform(transform.clone(), f) {
+            self.error = Some(error);
+        }
+    }
+
+    /// Pop every clip pushed since there were the given number, e.g. those left by an unbalanced
+    /// `DisplayList`, and return the first error met since the last call.
+    fn finish(&mut self, clips: usize) -> Result<(), Error> {
+        while self.clips.len() > clips {
+            self.pop_clip();
+        }
+        match self.error.take() {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    /// Record the given error, unless drawing has already failed.
+    fn fail(&mut self, error: Error) {
+        if self.error.is_none() {
+            self.error = Some(error);
+        }
+    }
+
+}
+
+
+impl<'a, C, G> Backend for Renderer<'a, C, G>
+    where
+        C: CharacterCache,
+        G: Graphics<Texture=C::Texture>,
+{
+
+    fn clear(&mut self, color: Color) {
+        if self.error.is_none() {
+            self.backend.clear_color(color.to_fsa());
+            stats::record(&mut self.maybe_stats, |stats| stats.draw_calls += 1);
+        }
+    }
+
+    fn fill_polygon(&mut self, transform: &Transform2D, points: &[(f64, f64)],
+                    style: &FillStyle, alpha: f32) {
+        self.draw_with(transform, |renderer| {
+            fill_polygon(points, style, alpha, renderer.backend, &mut renderer.maybe_stats,
+                         renderer.options, renderer.context)
+        });
+    }
+
+    fn stroke_path(&mut self, transform: &Transform2D, points: &[(f64, f64)], closed: bool,
+                   style: &LineStyle, alpha: f32) {
+        self.draw_with(transform, |renderer| {
+            stroke_path(points, closed, style, alpha, renderer.backend, &mut renderer.maybe_stats,
+                        renderer.options, renderer.context)
+        });
+    }
+
+    fn draw_text(&mut self, transform: &Transform2D, text: &Text, outline: Option<&LineStyle>,
+                 alpha: f32) {
+        if outline.is_some() {
+            return self.fail(Error::UnsupportedStyle("outlined text"));
+        }
+        self.draw_with(transform, |renderer| {
+            draw_text(text, alpha, renderer.backend, &mut renderer.maybe_character_cache,
+                      &mut renderer.maybe_stats, renderer.context)
+        });
+    }
+
+    // Textures can't yet be loaded for the piston backend.
+    fn draw_image(&mut self, _transform: &Transform2D, _style: ImageStyle, _size: (i32, i32),
+                  path: &Path, _alpha: f32) {
+        self.fail(Error::MissingTexture(path.to_path_buf()));
+    }
+
+    fn draw_custom(&mut self, transform: &Transform2D, custom: &CustomDraw) {
+        let CustomDraw(ref draw) = *custom;
+        self.draw_with(transform, |renderer| {
+            draw(renderer.context.transform, &renderer.context.draw_state, renderer.backend);
+            Ok(())
+        });
+    }
+
+    fn push_clip(&mut self, rect: (f64, f64, f64, f64)) {
+        self.push_scissor(rect);
+        self.clips.push(Clip::Scissor);
+    }
+
+    /// The shape is drawn into the stencil buffer as piston draws polygons, i.e. as a fan of
+    /// triangles from its first point, so it should be convex as every clip pushed while drawing
+    /// an `Element` is.
+    fn push_clip_shape(&mut self, transform: &Transform2D, points: &[(f64, f64)]) {
+        // Each pixel's stencil value counts the clip shapes covering it, so that only those
+        // within every shape are drawn. The buffer is cleared before the outermost shape.
+        let depth = self.stencil_depth();
+        if depth == 0 {
+            self.backend.clear_stencil(0);
+        }
+        let Transform2D(matrix) = Transform2D(self.context.transform).multiply(transform.clone());
+        let points: Vec<_> = points.iter().map(|&(x, y)| [x, y]).collect();
+        let draw_state = self.context.draw_state;
+        let clip_state = stencil_write(draw_state, depth, StencilOp::IncrementClamp);
+        graphics::Polygon::new([0.0; 4]).draw(&points[..], &clip_state, matrix, self.backend);
+        self.draw_states.push(draw_state);
+        self.context.draw_state = draw_state.stencil(Comparison::Equal, depth.saturating_add(1));
+        self.clips.push(Clip::Stencil(matrix, points));
+    }
+
+    fn pop_clip(&mut self) {
+        match self.clips.pop() {
+            Some(Clip::Scissor) => self.pop_scissor(),
+            Some(Clip::Stencil(matrix, points)) => {
+                // Restore the stencil values within the shape to those of the clip beneath it.
+                self.pop_scissor();
+                let depth = self.stencil_depth().saturating_add(1);
+                let draw_state = stencil_write(self.context.draw_state, depth,
+                                               StencilOp::DecrementClamp);
+                graphics::Polygon::new([0.0; 4])
+                    .draw(&points[..], &draw_state, matrix, self.backend);
+            },
+            None => (),
+        }
+    }
+
+    fn target_size(&self) -> (f64, f64) {
+        let [w, h] = self.context.get_view_size();
+        (w, h)
+    }
+
+    fn dpi(&self) -> f64 {
+        self.options.dpi
+    }
+
+    fn time(&self) -> f64 {
+        self.options.time
+    }
+
+    fn dt(&self) -> f64 {
+        self.options.dt
+    }
+
+    fn safe_area(&self) -> Insets {
+        self.options.safe_area
+    }
+
+}
+
+
+/// This function draws a form with some given transform using the generic [Piston graphics]
+/// (https://github.com/PistonDevelopers/graphics) backend.
+///
+/// Returns an `Error` for forms that the piston backend can't yet draw, such as gradient fills,
+/// or for text when no character cache was given.
+pub fn draw_form<C: CharacterCache, G: Graphics<Texture=C::Texture>>(
+    form: &Form,
+    alpha: f32,
+    backend: &mut G,
+    maybe_character_cache: &mut Option<&mut C>,
+    maybe_stats: &mut Option<&mut RenderStats>,
+    options: RenderOptions,
+    context: Context,
+) -> Result<(), Error> {
+    let mut renderer = Renderer::from_parts(context, backend, maybe_character_cache, maybe_stats,
+                                            options);
+    renderer.walk(|renderer, maybe_stats| {
+        backend::draw_form(form, alpha, transform_2d::identity(), renderer, maybe_stats)
+    })
+}
+
+
+/// The part of `Graphics` available to the function of a custom form. See `form::custom`.
+///
+/// `Graphics` has generic methods and so can't be used as a trait object, but every `Graphics`
+/// is also a `RawGraphics`.
+pub trait RawGraphics {
+    /// Clear the whole render target with the given color.
+    fn clear_color(&mut self, color: [f32; 4]);
+    /// Draw triangles of the given color, described by consecutive pairs of coordinates that
+    /// have already been transformed, as given to `Graphics::tri_list`.
+    fn tri_list(&mut self, draw_state: &DrawState, color: &[f32; 4], vertices: &[f32]);
+}
+
+impl<G: Graphics> RawGraphics for G {
+    fn clear_color(&mut self, color: [f32; 4]) {
+        Graphics::clear_color(self, color);
+    }
+    fn tri_list(&mut self, draw_state: &DrawState, color: &[f32; 4], vertices: &[f32]) {
+        Graphics::tri_list(self, draw_state, color, |f| f(vertices));
+    }
+}
+
+
+/// The function with which a custom form draws itself. See `form::custom`.
+#[derive(Clone)]
+pub struct CustomDraw(pub Arc<CustomFn>);
+
+/// The type of the function with which a custom form draws itself.
+pub type CustomFn = dyn Fn(Matrix2d, &DrawState, &mut dyn RawGraphics) + Send + Sync;
+
+/// Custom forms can't be hashed by what they draw, so they are hashed by the address of their
+/// function.
+impl Hash for CustomDraw {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (&*self.0 as *const _ as *const () as usize).hash(state);
+    }
+}
+
+impl fmt::Debug for CustomDraw {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CustomDraw")
+    }
+}
+
+/// Stroke the path along the given points, joining the last point back to the first if `closed`.
+fn stroke_path<G: Graphics>(
+    points: &[(f64, f64)],
+    closed: bool,
+    line_style: &LineStyle,
+    alpha: f32,
+    backend: &mut G,
+    maybe_stats: &mut Option<&mut RenderStats>,
+    options: RenderOptions,
+    context: Context,
+) -> Result<(), Error> {
+    // NOTE: join is not yet handled properly.
+    let line = piston_line(line_style, alpha)?;
+    let points = snapped(&context, options, points);
+    let dashing: Vec<f64> = line_style.dashing.iter().map(|&len| len as f64).collect();
+    let dashes = geometry::dash_path(&points, closed, &dashing, line_style.dash_offset as f64);
+    for dash in dashes.iter() {
+        for window in dash.windows(2) {
+            let ((x1, y1), (x2, y2)) = (window[0], window[1]);
+            line.draw([x1, y1, x2, y2], &context.draw_state, context.transform, backend);
+            stats::record(maybe_stats, |stats| stats.draw_calls += 1);
+        }
+    }
+    Ok(())
+}
+
+
+/// Fill the polygon described by the given points.
+fn fill_polygon<G: Graphics>(
+    points: &[(f64, f64)],
+    fill_style: &FillStyle,
+    alpha: f32,
+    backend: &mut G,
+    maybe_stats: &mut Option<&mut RenderStats>,
+    options: RenderOptions,
+    context: Context,
+) -> Result<(), Error> {
+    match *fill_style {
+        FillStyle::Solid(color) => {
+            let polygon = graphics::Polygon::new(convert_color(color, alpha));
+            let points: Vec<_> = snapped(&context, options, points).into_iter()
+                .map(|(x, y)| [x, y])
+                .collect();
+            polygon.draw(&points[..], &context.draw_state, context.transform, backend);
+            stats::record(maybe_stats, |stats| stats.draw_calls += 1);
+            Ok(())
+        },
+        FillStyle::Texture(ref path) => Err(Error::MissingTexture(path.clone())),
+        FillStyle::Grad(_) => Err(Error::UnsupportedStyle("gradient fills")),
+    }
+}
+
+
+/// Draw the given text upon the context's origin.
+fn draw_text<C: CharacterCache, G: Graphics<Texture=C::Texture>>(
+    text: &Text,
+    alpha: f32,
+    backend: &mut G,
+    maybe_character_cache: &mut Option<&mut C>,
+    maybe_stats: &mut Option<&mut RenderStats>,
+    context: Context,
+) -> Result<(), Error> {
+    #[cfg(feature = "unicode-bidi")]
+    let text = &text.visual_order();
+    let context = context.scale(1.0, -1.0);
+    let character_cache = match *maybe_character_cache {
+        Some(ref mut character_cache) => character_cache,
+        None => return Err(Error::MissingCharacterCache),
+    };
+    use text::Style as TextStyle;
+    let (x_offset, y_offset) = text_offset(text, *character_cache);
+    let context = context.trans(x_offset, y_offset);
+
+    // Backgrounds are drawn first so that their padding never covers a neighbouring unit's glyphs.
+    let mut x = 0.0;
+    for unit in text.sequence.iter() {
+        let TextUnit { ref string, ref style } = *unit;
+        let height = style.height.unwrap_or(16.0).floor();
+        let width = character_cache.width(height as u32, string);
+        if let Some(background) = style.background {
+            // As in `text_offset`, the ascent and descent are approximated as 0.8 and 0.2 of the
+            // height.
+            let pad = height * BACKGROUND_PADDING;
+            let top = -style.baseline - height * 0.8 - pad;
+            graphics::Rectangle::new(convert_color(background, alpha))
+                .draw([x - pad, top, width + pad * 2.0, height + pad * 2.0], &context.draw_state,
+                      context.transform, backend);
+            stats::record(maybe_stats, |stats| stats.draw_calls += 1);
+        }
+        x += width;
+    }
+
+    let mut x = 0.0;
+    for unit in text.sequence.iter() {
+        let TextUnit { ref string, ref style } = *unit;
+        let TextStyle { height, color, baseline, .. } = *style;
+        let height = height.unwrap_or(16.0).floor();
+        let color = convert_color(color, alpha);
+        // The context's y-axis points down, so raising the baseline is a negative shift.
+        let unit_context = context.trans(x, -baseline);
+        graphics::text::Text::new_color(color, height as u32)
+            .round()
+            .draw(&string[..], *character_cache, &unit_context.draw_state,
+                  unit_context.transform, backend);
+        x += character_cache.width(height as u32, string);
+        stats::record(maybe_stats, |stats| {
+            stats.draw_calls += 1;
+            stats.text_glyphs += string.chars().filter(|c| !c.is_whitespace()).count();
+        });
+    }
+    Ok(())
+}
+
+
+/// The offset of the text's first baseline from the text's origin, within a context whose y-axis
+/// points down.
+fn text_offset<C: CharacterCache>(text: &Text, character_cache: &mut C) -> (f64, f64) {
+    let (total_width, max_height) = text.sequence.iter().fold((0.0, 0.0), |(w, h), unit| {
+        let TextUnit { ref string, ref style } = *unit;
+        let height = style.height.unwrap_or(16.0);
+        let new_total_width = w + character_cache.width(height as u32, string);
+        let new_max_height = if height > h { height } else { h };
+        (new_total_width, new_max_height)
+    });
+    let x_offset = -(total_width * text.position.fraction()).floor();
+    // The character cache provides no vertical metrics, so the ascent and descent are
+    // approximated as 0.8 and 0.2 of the text's height.
+    let y_offset = match text.anchor {
+        TextAnchor::Baseline => 0.0,
+        TextAnchor::Middle => (max_height * 0.3).floor(),
+        TextAnchor::Top => (max_height * 0.8).floor(),
+        TextAnchor::Bottom => -(max_height * 0.2).floor(),
+    };
+    (x_offset, y_offset)
+}
+
+
+impl Text {
+
+    /// The caret drawn before the `char_index`th character of the text, as a zero-width
+    /// rectangle spanning the tallest unit's approximate ascent and descent.
+    ///
+    /// The rectangle is relative to the text's origin, in the collage coordinate system, and
+    /// matches the layout used when drawing the text via the `Renderer`. Indices beyond the end
+    /// of the text place the caret after the last character.
+    pub fn caret_position<C: CharacterCache>(&self, char_index: usize, character_cache: &mut C)
+        -> Rect
+    {
+        let (x, y) = self.caret_offset(char_index, character_cache);
+        let max_height = self.max_height();
+        Rect { min: (x, y - max_height * 0.2), max: (x, y + max_height * 0.8) }
+    }
+
+    /// The rectangles covering the characters within the given range, for highlighting a
+    /// selection.
+    ///
+    /// Text is laid out upon a single line, so at most one rectangle is returned. An empty range
+    /// returns no rectangles.
+    pub fn selection_rects<C: CharacterCache>(&self, range: Range<usize>, character_cache: &mut C)
+        -> Vec<Rect>
+    {
+        if range.start >= range.end {
+            return Vec::new();
+        }
+        let start = self.caret_position(range.start, character_cache);
+        let end = self.caret_position(range.end, character_cache);
+        if start.min.0 == end.min.0 {
+            return Vec::new();
+        }
+        vec![start.union(&end)]
+    }
+
+    /// The height of the text's tallest unit.
+    fn max_height(&self) -> f64 {
+        self.sequence.iter()
+            .map(|unit| unit.style.height.unwrap_or(16.0))
+            .fold(0.0, f64::max)
+    }
+
+    /// The point upon the first baseline before the `char_index`th character, with the y-axis
+    /// pointing up.
+    fn caret_offset<C: CharacterCache>(&self, char_index: usize, character_cache: &mut C)
+        -> (f64, f64)
+    {
+        let (x_offset, y_offset) = text_offset(self, character_cache);
+        let mut x = x_offset;
+        let mut remaining = char_index;
+        let mut baseline = 0.0;
+        for unit in self.sequence.iter() {
+            let TextUnit { ref string, ref style } = *unit;
+            let height = style.height.unwrap_or(16.0).floor() as u32;
+            baseline = style.baseline;
+            let len = string.chars().count();
+            if remaining <= len {
+                let end = string.char_indices().nth(remaining).map_or(string.len(), |(i, _)| i);
+                x += character_cache.width(height, &string[..end]);
+                return (x, -y_offset + baseline);
+            }
+            x += character_cache.width(height, string);
+            remaining -= len;
+        }
+        (x, -y_offset + baseline)
+    }
+
+}
+
+
+/// The piston-graphics line used to draw segments with the given style.
+fn piston_line(line_style: &LineStyle, alpha: f32) -> Result<graphics::Line, Error> {
+    let color = convert_color(line_style.color, alpha);
+    match line_style.cap {
+        LineCap::Flat => Ok(graphics::Line::new(color, line_style.width / 2.0)),
+        LineCap::Round => Ok(graphics::Line::new_round(color, line_style.width / 2.0)),
+        LineCap::Padded => Err(Error::UnsupportedStyle("padded line caps")),
+    }
+}
+
+/// Convert an elmesque color to a piston-graphics color.
+fn convert_color(color: Color, alpha: f32) -> [f32; 4] {
+    use color::hsl_to_rgb;
+    let ((r, g, b), a) = match color {
+        Color::Hsla(h, s, l, a) => (hsl_to_rgb(h, s, l), a),
+        Color::Rgba(r, g, b, a) => ((r, g, b), a),
+    };
+    [r, g, b, a * alpha]
+}
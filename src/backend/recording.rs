@@ -0,0 +1,114 @@
+//!
+//! Recording frames as `DisplayList`s so that they may be replayed later.
+//!
+//! A `Recording` is useful for attaching the exact frames that produced some bug to a report,
+//! or for replaying a real workload within a benchmark. With the `recording` feature, recordings
+//! may be saved to and loaded from JSON files.
+//!
+
+use element::Element;
+use length;
+use super::display_list::DisplayList;
+
+#[cfg(feature = "piston-backend")]
+use error::Error;
+#[cfg(feature = "piston-backend")]
+use graphics::Graphics;
+#[cfg(feature = "piston-backend")]
+use graphics::character::CharacterCache;
+#[cfg(feature = "piston-backend")]
+use super::piston::Renderer;
+#[cfg(feature = "recording")]
+use serde_json;
+#[cfg(feature = "recording")]
+use std::fs::File;
+#[cfg(feature = "recording")]
+use std::io::{self, BufReader, BufWriter};
+#[cfg(feature = "recording")]
+use std::path::Path;
+
+
+/// A sequence of captured frames.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Recording {
+    /// The width of the render target in view space, used for frames captured from now on.
+    pub width: f64,
+    /// The height of the render target in view space, used for frames captured from now on.
+    pub height: f64,
+    /// The DPI of the render target, used for frames captured from now on.
+    pub dpi: f64,
+    /// The captured frames in the order in which they were captured.
+    pub frames: Vec<DisplayList>,
+}
+
+
+impl Recording {
+
+    /// Construct an empty recording of frames for a render target of the given size.
+    pub fn new(width: f64, height: f64) -> Recording {
+        Recording {
+            width: width,
+            height: height,
+            dpi: length::DEFAULT_DPI,
+            frames: Vec::new(),
+        }
+    }
+
+    /// Builder method for a recording whose render target has the given DPI.
+    pub fn dpi(self, dpi: f64) -> Recording {
+        Recording { dpi: dpi, ..self }
+    }
+
+    /// Capture the given `Element` as the next frame.
+    pub fn capture(&mut self, element: &Element) {
+        let frame = DisplayList::new(self.width, self.height).dpi(self.dpi).record(element);
+        self.frames.push(frame);
+    }
+
+    /// Draw every frame in turn with the given `Renderer`, as a workload for benchmarks.
+    ///
+    /// Drawing stops at the first command that can't be drawn.
+    #[cfg(feature = "piston-backend")]
+    pub fn replay<'a, C, G>(&self, renderer: &mut Renderer<'a, C, G>) -> Result<(), Error>
+        where
+            C: CharacterCache,
+            G: Graphics<Texture=C::Texture>,
+    {
+        for frame in self.frames.iter() {
+            renderer.draw_display_list(frame)?;
+        }
+        Ok(())
+    }
+
+    /// Draw the frame at the given index with the given `Renderer`.
+    ///
+    /// Nothing is drawn if there is no frame at the index.
+    #[cfg(feature = "piston-backend")]
+    pub fn replay_frame<'a, C, G>(&self, index: usize, renderer: &mut Renderer<'a, C, G>)
+        -> Result<(), Error>
+        where
+            C: CharacterCache,
+            G: Graphics<Texture=C::Texture>,
+    {
+        match self.frames.get(index) {
+            Some(frame) => renderer.draw_display_list(frame),
+            None => Ok(()),
+        }
+    }
+
+    /// Save the recording to a JSON file at the given path.
+    #[cfg(feature = "recording")]
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer(writer, self).map_err(io::Error::from)
+    }
+
+    /// Load a recording from the JSON file at the given path.
+    #[cfg(feature = "recording")]
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Recording> {
+        let reader = BufReader::new(File::open(path)?);
+        serde_json::from_reader(reader).map_err(io::Error::from)
+    }
+
+}
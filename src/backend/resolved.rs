@@ -0,0 +1,343 @@
+//!
+//! Resolving the layout of an `Element` ahead of drawing it.
+//!
+//! `Element::resolve` walks an `Element` tree once, producing a `ResolvedElement`: the
+//! `DisplayList` of the frame along with the view space rectangle and transform of every
+//! `Element` within the tree. The same `ResolvedElement` may then be drawn over many frames,
+//! searched for hit testing, or handed to exporters, without walking the tree again.
+//!
+//! As view space is centered upon the render target, the layout of most `Element`s doesn't
+//! depend upon the target's size. When the target is resized, `ResolvedElement::update_size`
+//! only resolves again those that do, i.e. `Element`s produced for the viewport (such as by
+//! `responsive` or `size_in`) and `Element`s that fill the target.
+//!
+
+use color::Color;
+use element::{Element, ElementId, ImageStyle, Prim};
+use form::{FillStyle, LineStyle};
+use geometry::{self, Rect};
+use length::{self, Insets};
+use std::ops::Range;
+use std::path::Path;
+use super::Backend;
+use super::display_list::DisplayList;
+use text::Text;
+use transform_2d::{self, Transform2D};
+
+
+/// An `Element` whose layout has been resolved for a render target of some size.
+#[derive(Clone, Debug)]
+pub struct ResolvedElement {
+    /// The commands for drawing the frame.
+    pub display_list: DisplayList,
+    /// The root of the tree of resolved `Element`s.
+    pub root: ResolvedNode,
+}
+
+
+/// The resolved layout of a single `Element` within a `ResolvedElement`.
+#[derive(Clone, Debug)]
+pub struct ResolvedNode {
+    /// The ID of the `Element`, if it was given one.
+    pub id: Option<ElementId>,
+    /// The width and height of the `Element`.
+    pub size: (i32, i32),
+    /// Maps the `Element`'s local coordinates (where the origin is its center) into view space.
+    pub transform: Transform2D,
+    /// The bounds of the `Element` in view space.
+    pub rect: Rect,
+    /// The range of the display list's commands that draw the `Element`, including the
+    /// `Element`s nested within it.
+    pub commands: Range<usize>,
+    /// The `Element`s nested within this one, in the order in which they are drawn.
+    pub children: Vec<ResolvedNode>,
+    /// Whether the `Element` is drawn, i.e. neither hidden nor collapsed. Hidden `Element`s keep
+    /// their place within the tree but have no commands, and neither they nor their children lie
+    /// beneath any point.
+    pub visible: bool,
+    /// The opacity inherited from the `Element`'s ancestors.
+    opacity: f32,
+    /// The `Element` itself, kept if its layout depends upon the size of the render target.
+    sized_by_target: Option<Element>,
+}
+
+
+/// A `Backend` that records a `DisplayList` along with the tree of `Element`s drawn to it.
+struct Resolver {
+    display_list: DisplayList,
+    stack: Vec<ResolvedNode>,
+    root: Option<ResolvedNode>,
+}
+
+
+impl Resolver {
+
+    /// Construct a resolver that appends to the given display list.
+    fn new(display_list: DisplayList) -> Resolver {
+        Resolver { display_list: display_list, stack: Vec::new(), root: None }
+    }
+
+    /// The resolved display list and tree of `Element`s drawn to the resolver.
+    fn finish(self) -> ResolvedElement {
+        let Resolver { display_list, root, .. } = self;
+        ResolvedElement {
+            display_list: display_list,
+            root: root.expect("drawing an Element always begins and ends its root"),
+        }
+    }
+
+}
+
+
+/// Resolve again the subtrees of the given node whose layout depends upon the size of the
+/// display list's render target, replacing their commands within the display list.
+///
+/// `shift` is the number of commands by which the commands of the node have moved due to the
+/// subtrees before it, and is updated by the change in the number of commands of this one.
+fn update_node(node: &mut ResolvedNode, display_list: &mut DisplayList, shift: &mut isize) {
+    let moved = |index: usize, shift: isize| (index as isize + shift) as usize;
+    let start = moved(node.commands.start, *shift);
+    match node.sized_by_target.clone() {
+        Some(element) => {
+            let end = moved(node.commands.end, *shift);
+            let target = empty_target(display_list, display_list.width, display_list.height);
+            let mut resolver = Resolver::new(target);
+            super::draw_node(&element, node.opacity, node.transform.clone(), &mut resolver,
+                             &mut None, false);
+            let ResolvedElement { display_list: resolved, root: mut new_node } = resolver.finish();
+            let new_len = resolved.commands.len();
+            offset_commands(&mut new_node, start);
+            display_list.commands.splice(start..end, resolved.commands);
+            *shift += new_len as isize - (end - start) as isize;
+            *node = new_node;
+        },
+        None => {
+            node.commands.start = start;
+            for child in node.children.iter_mut() {
+                update_node(child, display_list, shift);
+            }
+            node.commands.end = moved(node.commands.end, *shift);
+        },
+    }
+}
+
+
+/// An empty display list for a render target of the given size, drawn with the same DPI, at the
+/// same time and with the same safe area as the given display list.
+fn empty_target(display_list: &DisplayList, width: f64, height: f64) -> DisplayList {
+    DisplayList::new(width, height)
+        .dpi(display_list.dpi)
+        .time(display_list.time)
+        .dt(display_list.dt)
+        .safe_area(display_list.safe_area)
+}
+
+
+/// Move the commands of the node and every node within it along by the given number of commands.
+fn offset_commands(node: &mut ResolvedNode, offset: usize) {
+    node.commands = node.commands.start + offset..node.commands.end + offset;
+    for child in node.children.iter_mut() {
+        offset_commands(child, offset);
+    }
+}
+
+
+impl Element {
+
+    /// Resolve the layout of the Element as the root of a render target of the given size.
+    pub fn resolve(&self, width: f64, height: f64) -> ResolvedElement {
+        self.resolve_with_dpi(width, height, length::DEFAULT_DPI)
+    }
+
+    /// Resolve the layout of the Element as the root of a render target of the given size and
+    /// DPI.
+    pub fn resolve_with_dpi(&self, width: f64, height: f64, dpi: f64) -> ResolvedElement {
+        self.resolve_into(DisplayList::new(width, height).dpi(dpi))
+    }
+
+    /// Resolve the layout of the Element as the root of the render target described by the given
+    /// display list, appending to its commands.
+    ///
+    /// This allows the Element to be resolved at some time (e.g. for `animated_image`) or with
+    /// some safe area (e.g. for `with_safe_area`), by giving a display list such as
+    /// `DisplayList::new(width, height).time(time).safe_area(insets)`.
+    pub fn resolve_into(&self, display_list: DisplayList) -> ResolvedElement {
+        let mut resolver = Resolver::new(display_list);
+        super::draw_element(self, 1.0, transform_2d::identity(), &mut resolver, &mut None);
+        resolver.finish()
+    }
+
+}
+
+
+impl ResolvedElement {
+
+    /// Draw the resolved frame to the given `Backend`.
+    pub fn draw<B: Backend>(&self, backend: &mut B) {
+        self.display_list.replay(backend);
+    }
+
+    /// The topmost, innermost `Element` lying beneath the given point in view space, if any.
+    pub fn node_at(&self, x: f64, y: f64) -> Option<&ResolvedNode> {
+        self.root.node_at(x, y)
+    }
+
+    /// The ID of the innermost `Element` with an ID lying beneath the given point in view space,
+    /// if any.
+    pub fn id_at(&self, x: f64, y: f64) -> Option<&ElementId> {
+        self.root.id_at(x, y)
+    }
+
+    /// Update the layout for a render target of the given size.
+    ///
+    /// Only the subtrees whose layout depends upon the size of the render target are resolved
+    /// again, so resizing a large scene costs little more than resolving those subtrees.
+    pub fn update_size(&mut self, width: f64, height: f64) {
+        if let Some(element) = self.root.sized_by_target.clone() {
+            *self = element.resolve_into(empty_target(&self.display_list, width, height));
+            return;
+        }
+        self.display_list.width = width;
+        self.display_list.height = height;
+        update_node(&mut self.root, &mut self.display_list, &mut 0);
+    }
+
+}
+
+
+impl ResolvedNode {
+
+    /// Whether or not the given point in view space lies within the `Element`.
+    pub fn contains_point(&self, x: f64, y: f64) -> bool {
+        match self.transform.invert() {
+            Some(inverse) => {
+                let (x, y) = geometry::transform_point(&inverse, (x, y));
+                let (w, h) = (self.size.0 as f64, self.size.1 as f64);
+                Rect::from_xywh(0.0, 0.0, w, h).contains_point((x, y))
+            },
+            None => false,
+        }
+    }
+
+    /// The topmost, innermost `Element` at or within this one lying beneath the given point in
+    /// view space, if any.
+    ///
+    /// Nested `Element`s are searched even when the point lies outside of this one, as content
+    /// may overflow its `Element`.
+    pub fn node_at(&self, x: f64, y: f64) -> Option<&ResolvedNode> {
+        if !self.visible {
+            return None;
+        }
+        self.children.iter().rev()
+            .filter_map(|child| child.node_at(x, y))
+            .next()
+            .or_else(|| if self.contains_point(x, y) { Some(self) } else { None })
+    }
+
+    /// The ID of the innermost `Element` with an ID at or within this one lying beneath the given
+    /// point in view space, if any.
+    pub fn id_at(&self, x: f64, y: f64) -> Option<&ElementId> {
+        if !self.visible {
+            return None;
+        }
+        self.children.iter().rev()
+            .filter_map(|child| child.id_at(x, y))
+            .next()
+            .or_else(|| match self.id {
+                Some(ref id) if self.contains_point(x, y) => Some(id),
+                _ => None,
+            })
+    }
+
+}
+
+
+impl Backend for Resolver {
+
+    fn clear(&mut self, color: Color) {
+        self.display_list.clear(color);
+    }
+
+    fn fill_polygon(&mut self, transform: &Transform2D, points: &[(f64, f64)],
+                    style: &FillStyle, alpha: f32) {
+        self.display_list.fill_polygon(transform, points, style, alpha);
+    }
+
+    fn stroke_path(&mut self, transform: &Transform2D, points: &[(f64, f64)], closed: bool,
+                   style: &LineStyle, alpha: f32) {
+        self.display_list.stroke_path(transform, points, closed, style, alpha);
+    }
+
+    fn draw_text(&mut self, transform: &Transform2D, text: &Text, outline: Option<&LineStyle>,
+                 alpha: f32) {
+        self.display_list.draw_text(transform, text, outline, alpha);
+    }
+
+    fn draw_image(&mut self, transform: &Transform2D, style: ImageStyle, size: (i32, i32),
+                  path: &Path, alpha: f32) {
+        self.display_list.draw_image(transform, style, size, path, alpha);
+    }
+
+    fn push_clip(&mut self, rect: (f64, f64, f64, f64)) {
+        self.display_list.push_clip(rect);
+    }
+
+    fn push_clip_shape(&mut self, transform: &Transform2D, points: &[(f64, f64)]) {
+        self.display_list.push_clip_shape(transform, points);
+    }
+
+    fn pop_clip(&mut self) {
+        self.display_list.pop_clip();
+    }
+
+    fn target_size(&self) -> (f64, f64) {
+        (self.display_list.width, self.display_list.height)
+    }
+
+    fn dpi(&self) -> f64 {
+        self.display_list.dpi
+    }
+
+    fn time(&self) -> f64 {
+        self.display_list.time
+    }
+
+    fn dt(&self) -> f64 {
+        self.display_list.dt
+    }
+
+    fn safe_area(&self) -> Insets {
+        self.display_list.safe_area
+    }
+
+    fn begin_element(&mut self, element: &Element, transform: &Transform2D, opacity: f32) {
+        let start = self.display_list.commands.len();
+        let size = element.get_size();
+        let (w, h) = (size.0 as f64, size.1 as f64);
+        let sized_by_target = match element.element {
+            Prim::Responsive(_) | Prim::Fill(_) => Some(element.clone()),
+            _ => None,
+        };
+        self.stack.push(ResolvedNode {
+            id: element.props.id.clone(),
+            size: size,
+            transform: transform.clone(),
+            rect: Rect::from_xywh(0.0, 0.0, w, h).transform(transform),
+            commands: start..start,
+            children: Vec::new(),
+            visible: !element.is_hidden(),
+            opacity: opacity,
+            sized_by_target: sized_by_target,
+        });
+    }
+
+    fn end_element(&mut self) {
+        let mut node = self.stack.pop().expect("every end_element has a matching begin_element");
+        node.commands.end = self.display_list.commands.len();
+        match self.stack.last_mut() {
+            Some(parent) => parent.children.push(node),
+            None => self.root = Some(node),
+        }
+    }
+
+}
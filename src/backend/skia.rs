@@ -0,0 +1,337 @@
+//!
+//! A CPU `Backend` that renders to a [tiny-skia](https://github.com/RazrFalcon/tiny-skia)
+//! `Pixmap`.
+//!
+//! Unlike the piston renderer, this backend produces anti-aliased fills and strokes with proper
+//! joins, caps and dashing, along with linear and radial gradients and textured fills. As it
+//! requires no window or GPU, it is also used as the reference renderer for the `testing` module.
+//!
+//! Text is not yet drawn by this backend as it has no means of loading glyphs.
+//!
+
+use backend::{self, Backend, RenderOptions};
+use color::{Color, Gradient};
+use element::{Element, ImageStyle};
+use form::{FillStyle, LineCap, LineJoin, LineStyle};
+use length::Insets;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use text::Text;
+use tiny_skia;
+use tiny_skia::{FillRule, GradientStop, LinearGradient, Mask, Paint, PathBuilder, Pattern,
+                Pixmap, Point, RadialGradient, Rect, Shader, SpreadMode, Stroke, StrokeDash,
+                FilterQuality, Transform};
+use transform_2d::Transform2D;
+use utils::clampf32;
+
+
+/// A `Backend` that draws to a tiny-skia `Pixmap`.
+pub struct PixmapBackend<'a> {
+    pixmap: &'a mut Pixmap,
+    /// The stack of clipping masks, each the intersection of itself and the mask before it.
+    clips: Vec<Mask>,
+    /// Textures loaded from disk, or `None` if the texture at the path could not be loaded.
+    textures: HashMap<PathBuf, Option<Pixmap>>,
+    options: RenderOptions,
+}
+
+
+impl<'a> PixmapBackend<'a> {
+
+    /// Construct a backend that draws to the given `Pixmap`.
+    pub fn new(pixmap: &'a mut Pixmap) -> PixmapBackend<'a> {
+        PixmapBackend {
+            pixmap: pixmap,
+            clips: Vec::new(),
+            textures: HashMap::new(),
+            options: RenderOptions::default(),
+        }
+    }
+
+    /// Builder method for drawing with the given `RenderOptions`.
+    pub fn options(self, options: RenderOptions) -> PixmapBackend<'a> {
+        PixmapBackend { options: options, ..self }
+    }
+
+    /// The path along the given points, snapped to pixels if the options ask for it.
+    fn path(&self, transform: &Transform2D, points: &[(f64, f64)], closed: bool)
+        -> Option<tiny_skia::Path>
+    {
+        if self.options.pixel_snap {
+            let size = (self.pixmap.width() as f64, self.pixmap.height() as f64);
+            polygon_path(&backend::snap_to_pixels(transform, points, size), closed)
+        } else {
+            polygon_path(points, closed)
+        }
+    }
+
+    /// The transform mapping the given local transform all the way to pixel coordinates.
+    fn pixel_transform(&self, transform: &Transform2D) -> Transform {
+        let (w, h) = (self.pixmap.width() as f64, self.pixmap.height() as f64);
+        let Transform2D(m) = backend::view_to_target(w, h).multiply(transform.clone());
+        Transform::from_row(m[0][0] as f32, m[1][0] as f32, m[0][1] as f32,
+                            m[1][1] as f32, m[0][2] as f32, m[1][2] as f32)
+    }
+
+    /// Push a clip that is the intersection of the current clip and the given path.
+    fn push_clip_path(&mut self, path: Option<&tiny_skia::Path>, transform: Transform) {
+        let mut mask = match self.clips.last() {
+            Some(mask) => mask.clone(),
+            None => {
+                let mut mask = Mask::new(self.pixmap.width(), self.pixmap.height())
+                    .expect("pixmap dimensions are non-zero");
+                mask.invert();
+                mask
+            },
+        };
+        match path {
+            Some(path) => {
+                let anti_alias = self.options.anti_alias;
+                mask.intersect_path(path, FillRule::Winding, anti_alias, transform)
+            },
+            // An empty clip path clips everything.
+            None => mask.clear(),
+        }
+        self.clips.push(mask);
+    }
+
+    /// Load the texture at the given path if it has not yet been loaded.
+    fn load_texture(&mut self, path: &Path) {
+        if !self.textures.contains_key(path) {
+            let texture = Pixmap::load_png(path).ok();
+            self.textures.insert(path.to_path_buf(), texture);
+        }
+    }
+
+}
+
+
+impl<'a> Backend for PixmapBackend<'a> {
+
+    fn clear(&mut self, color: Color) {
+        self.pixmap.fill(convert_color(color, 1.0));
+    }
+
+    fn fill_polygon(&mut self, transform: &Transform2D, points: &[(f64, f64)],
+                    style: &FillStyle, alpha: f32) {
+        let path = match self.path(transform, points, true) {
+            Some(path) => path,
+            None => return,
+        };
+        if let FillStyle::Texture(ref texture_path) = *style {
+            self.load_texture(texture_path);
+        }
+        let shader = match *style {
+            FillStyle::Solid(color) => Some(Shader::SolidColor(convert_color(color, alpha))),
+            FillStyle::Grad(ref gradient) if self.options.linear_blending =>
+                gradient_shader(&gradient.clone().in_linear_rgb(), alpha),
+            FillStyle::Grad(ref gradient) => gradient_shader(gradient, alpha),
+            FillStyle::Texture(ref texture_path) =>
+                self.textures[texture_path].as_ref().map(|texture| {
+                    Pattern::new(texture.as_ref(), SpreadMode::Repeat, FilterQuality::Bilinear,
+                                 alpha, Transform::from_scale(1.0, -1.0))
+                }),
+        };
+        if let Some(shader) = shader {
+            let paint = Paint { shader: shader, anti_alias: self.options.anti_alias,
+                                ..Paint::default() };
+            let transform = self.pixel_transform(transform);
+            self.pixmap.fill_path(&path, &paint, FillRule::Winding, transform, self.clips.last());
+        }
+    }
+
+    fn stroke_path(&mut self, transform: &Transform2D, points: &[(f64, f64)], closed: bool,
+                   style: &LineStyle, alpha: f32) {
+        let path = match self.path(transform, points, closed) {
+            Some(path) => path,
+            None => return,
+        };
+        let mut paint = Paint::default();
+        paint.set_color(convert_color(style.color, alpha));
+        paint.anti_alias = self.options.anti_alias;
+        let transform = self.pixel_transform(transform);
+        self.pixmap.stroke_path(&path, &paint, &convert_stroke(style), transform, self.clips.last());
+    }
+
+    fn draw_text(&mut self, _transform: &Transform2D, _text: &Text, _outline: Option<&LineStyle>,
+                 _alpha: f32) {
+        // TODO: Requires some means of loading glyphs.
+    }
+
+    fn draw_image(&mut self, transform: &Transform2D, style: ImageStyle, (w, h): (i32, i32),
+                  path: &Path, alpha: f32) {
+        self.load_texture(path);
+        let texture = match self.textures[path] {
+            Some(ref texture) => texture,
+            None => return,
+        };
+        let rect = match Rect::from_xywh(0.0, 0.0, w as f32, h as f32) {
+            Some(rect) => rect,
+            None => return,
+        };
+
+        // Map the image's top-left origin, y-down coordinates to the element's centered space.
+        let (tex_w, tex_h) = (texture.width() as f32, texture.height() as f32);
+        let (src_x, src_y, scale_x, scale_y, spread) = match style {
+            ImageStyle::Plain => (0.0, 0.0, w as f32 / tex_w, h as f32 / tex_h, SpreadMode::Pad),
+            ImageStyle::Fitted => {
+                let scale = (w as f32 / tex_w).max(h as f32 / tex_h);
+                let (x, y) = ((tex_w * scale - w as f32) / 2.0, (tex_h * scale - h as f32) / 2.0);
+                (x, y, scale, scale, SpreadMode::Pad)
+            },
+            ImageStyle::Cropped(x, y) => (x as f32, y as f32, 1.0, 1.0, SpreadMode::Pad),
+            ImageStyle::Tiled => (0.0, 0.0, 1.0, 1.0, SpreadMode::Repeat),
+        };
+        let pattern_transform = Transform::from_row(scale_x, 0.0, 0.0, scale_y, -src_x, -src_y);
+        let shader = Pattern::new(texture.as_ref(), spread, FilterQuality::Bilinear, alpha,
+                                  pattern_transform);
+        let paint = Paint { shader: shader, anti_alias: self.options.anti_alias,
+                            ..Paint::default() };
+        let image_to_local = Transform::from_row(1.0, 0.0, 0.0, -1.0,
+                                                 -w as f32 / 2.0, h as f32 / 2.0);
+        let transform = self.pixel_transform(transform).pre_concat(image_to_local);
+        self.pixmap.fill_rect(rect, &paint, transform, self.clips.last());
+    }
+
+    fn push_clip(&mut self, (x, y, w, h): (f64, f64, f64, f64)) {
+        let (vw, vh) = (self.pixmap.width() as f64, self.pixmap.height() as f64);
+        let left = vw / 2.0 + x - w / 2.0;
+        let top = vh / 2.0 - y - h / 2.0;
+        let rect = Rect::from_xywh(left as f32, top as f32, w.max(0.0) as f32, h.max(0.0) as f32);
+        let path = rect.map(PathBuilder::from_rect);
+        self.push_clip_path(path.as_ref(), Transform::identity());
+    }
+
+    fn push_clip_shape(&mut self, transform: &Transform2D, points: &[(f64, f64)]) {
+        let path = self.path(transform, points, true);
+        let transform = self.pixel_transform(transform);
+        self.push_clip_path(path.as_ref(), transform);
+    }
+
+    fn pop_clip(&mut self) {
+        self.clips.pop();
+    }
+
+    fn target_size(&self) -> (f64, f64) {
+        (self.pixmap.width() as f64, self.pixmap.height() as f64)
+    }
+
+    fn dpi(&self) -> f64 {
+        self.options.dpi
+    }
+
+    fn time(&self) -> f64 {
+        self.options.time
+    }
+
+    fn dt(&self) -> f64 {
+        self.options.dt
+    }
+
+    fn safe_area(&self) -> Insets {
+        self.options.safe_area
+    }
+
+}
+
+
+/// Render the given `Element` to a new `Pixmap` of the given size.
+///
+/// Returns `None` if either dimension is zero.
+pub fn render(element: &Element, width: u32, height: u32) -> Option<Pixmap> {
+    render_with_options(element, width, height, RenderOptions::default())
+}
+
+
+/// Render the given `Element` to a new `Pixmap` of the given size, drawing with the given
+/// `RenderOptions`.
+///
+/// Returns `None` if either dimension is zero.
+pub fn render_with_options(element: &Element, width: u32, height: u32, options: RenderOptions)
+    -> Option<Pixmap>
+{
+    Pixmap::new(width, height).map(|mut pixmap| {
+        {
+            let mut backend = PixmapBackend::new(&mut pixmap).options(options);
+            backend::draw_element(element, 1.0, ::transform_2d::identity(), &mut backend,
+                                  &mut None);
+        }
+        pixmap
+    })
+}
+
+
+/// Render the given `Element` to the given `Pixmap`.
+pub fn render_to(element: &Element, pixmap: &mut Pixmap) {
+    let mut backend = PixmapBackend::new(pixmap);
+    backend::draw_element(element, 1.0, ::transform_2d::identity(), &mut backend, &mut None);
+}
+
+
+/// Convert an elmesque color to a tiny-skia color.
+fn convert_color(color: Color, alpha: f32) -> tiny_skia::Color {
+    let [r, g, b, a] = color.to_fsa();
+    tiny_skia::Color::from_rgba(clampf32(r), clampf32(g), clampf32(b), clampf32(a * alpha))
+        .unwrap_or(tiny_skia::Color::TRANSPARENT)
+}
+
+
+/// Convert an elmesque line style to a tiny-skia stroke.
+fn convert_stroke(style: &LineStyle) -> Stroke {
+    let LineStyle { width, cap, join, ref dashing, dash_offset, .. } = *style;
+    let (line_join, miter_limit) = match join {
+        LineJoin::Smooth => (tiny_skia::LineJoin::Round, 4.0),
+        LineJoin::Sharp(limit) => (tiny_skia::LineJoin::Miter, limit as f32),
+        LineJoin::Clipped => (tiny_skia::LineJoin::Bevel, 4.0),
+    };
+    let dash = if dashing.is_empty() {
+        None
+    } else {
+        StrokeDash::new(dashing.iter().map(|&d| d as f32).collect(), dash_offset as f32)
+    };
+    Stroke {
+        width: width as f32,
+        miter_limit: miter_limit,
+        line_cap: match cap {
+            LineCap::Flat => tiny_skia::LineCap::Butt,
+            LineCap::Round => tiny_skia::LineCap::Round,
+            LineCap::Padded => tiny_skia::LineCap::Square,
+        },
+        line_join: line_join,
+        dash: dash,
+    }
+}
+
+
+/// Convert an elmesque gradient to a tiny-skia shader.
+///
+/// tiny-skia's radial gradients always start with a radius of zero, so the start radius of a
+/// `Gradient::Radial` is ignored.
+fn gradient_shader(gradient: &Gradient, alpha: f32) -> Option<Shader<'static>> {
+    let stops = |colors: &[(f64, Color)]| -> Vec<GradientStop> {
+        colors.iter().map(|&(t, color)| GradientStop::new(t as f32, convert_color(color, alpha)))
+            .collect()
+    };
+    let point = |(x, y): (f64, f64)| Point::from_xy(x as f32, y as f32);
+    match *gradient {
+        Gradient::Linear(start, end, ref colors) =>
+            LinearGradient::new(point(start), point(end), stops(colors), SpreadMode::Pad,
+                                Transform::identity()),
+        Gradient::Radial(start, _, end, end_r, ref colors) =>
+            RadialGradient::new(point(start), point(end), end_r as f32, stops(colors),
+                                SpreadMode::Pad, Transform::identity()),
+    }
+}
+
+
+/// Build a path along the given points.
+fn polygon_path(points: &[(f64, f64)], closed: bool) -> Option<tiny_skia::Path> {
+    let mut builder = PathBuilder::new();
+    for (i, &(x, y)) in points.iter().enumerate() {
+        if i == 0 { builder.move_to(x as f32, y as f32) } else { builder.line_to(x as f32, y as f32) }
+    }
+    if closed {
+        builder.close();
+    }
+    builder.finish()
+}
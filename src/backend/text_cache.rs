@@ -0,0 +1,122 @@
+//!
+//! Caching the layout of text across frames.
+//!
+//! The piston `Renderer` measures each run of text through its `CharacterCache` every time the
+//! text is drawn, which adds up for text-heavy scenes such as dashboards. A `TextCache` wraps some
+//! `CharacterCache`, remembering the position of each glyph within every run of text that it has
+//! measured so that the same run is only ever measured once.
+//!
+
+use graphics::character::{Character, CharacterCache, FontSize};
+use std::collections::HashMap;
+
+
+/// A `CharacterCache` that remembers the glyph positions of each run of text it measures.
+///
+/// Give it to the `Renderer` in place of the `CharacterCache` it wraps and keep it between
+/// frames. Runs are keyed by their string and font size. Call `trim` once per frame to forget
+/// the runs that weren't used since the last call, so that the cache doesn't grow without bound
+/// while text changes.
+pub struct TextCache<C> {
+    character_cache: C,
+    runs: HashMap<FontSize, HashMap<String, Run>>,
+}
+
+
+/// The layout of a single run of text.
+#[derive(Clone, Debug)]
+struct Run {
+    /// The position of each glyph's origin along the baseline, relative to the start of the run.
+    positions: Vec<f64>,
+    /// The total advance width of the run.
+    width: f64,
+    /// Whether or not the run has been used since the last call to `trim`.
+    used: bool,
+}
+
+
+impl<C: CharacterCache> TextCache<C> {
+
+    /// Wrap the given `CharacterCache`.
+    pub fn new(character_cache: C) -> TextCache<C> {
+        TextCache {
+            character_cache: character_cache,
+            runs: HashMap::new(),
+        }
+    }
+
+    /// The position of each glyph's origin along the baseline, relative to the start of the run
+    /// of text, measuring the run if it hasn't been measured before.
+    pub fn positions(&mut self, size: FontSize, text: &str) -> &[f64] {
+        &self.run(size, text).positions
+    }
+
+    /// The number of runs of text within the cache.
+    pub fn len(&self) -> usize {
+        self.runs.values().map(|runs| runs.len()).sum()
+    }
+
+    /// Whether or not the cache holds no runs of text.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Forget every run of text that hasn't been used since the last call to `trim`.
+    pub fn trim(&mut self) {
+        for runs in self.runs.values_mut() {
+            runs.retain(|_, run| run.used);
+            for run in runs.values_mut() {
+                run.used = false;
+            }
+        }
+        self.runs.retain(|_, runs| !runs.is_empty());
+    }
+
+    /// Forget every run of text.
+    pub fn clear(&mut self) {
+        self.runs.clear();
+    }
+
+    /// A reference to the wrapped `CharacterCache`.
+    pub fn character_cache(&self) -> &C {
+        &self.character_cache
+    }
+
+    /// Unwrap the `CharacterCache`.
+    pub fn into_inner(self) -> C {
+        self.character_cache
+    }
+
+    /// The layout of the given run of text, measuring it if it hasn't been measured before.
+    fn run(&mut self, size: FontSize, text: &str) -> &Run {
+        let TextCache { ref mut character_cache, ref mut runs } = *self;
+        let runs = runs.entry(size).or_insert_with(HashMap::new);
+        if !runs.contains_key(text) {
+            let mut positions = Vec::with_capacity(text.len());
+            let mut x = 0.0;
+            for ch in text.chars() {
+                positions.push(x);
+                x += character_cache.character(size, ch).width();
+            }
+            let run = Run { positions: positions, width: x, used: false };
+            runs.insert(text.to_string(), run);
+        }
+        let run = runs.get_mut(text).expect("the run was measured above");
+        run.used = true;
+        run
+    }
+
+}
+
+
+impl<C: CharacterCache> CharacterCache for TextCache<C> {
+    type Texture = C::Texture;
+
+    fn character<'a>(&'a mut self, size: FontSize, ch: char) -> Character<'a, C::Texture> {
+        self.character_cache.character(size, ch)
+    }
+
+    fn width(&mut self, size: FontSize, text: &str) -> f64 {
+        self.run(size, text).width
+    }
+}
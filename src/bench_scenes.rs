@@ -0,0 +1,108 @@
+//!
+//! Procedurally generated scenes for measuring the performance of the draw path.
+//!
+//! Each scene stresses a different part of drawing: `particles` the number of forms, `deep_flow`
+//! the depth of the `Element` tree and `long_text` the amount of text. The scenes are generated
+//! deterministically, so the same arguments always produce the same scene and measurements may be
+//! compared between runs and across backends.
+//!
+//! This module is only available with the `bench-scenes` feature.
+//!
+
+use color::{hsl, Color};
+use element::{self, Element};
+use form::{self, Form};
+use std::f32::consts::PI;
+use text::Text;
+
+
+/// The number of particles in the standard `particles` scene.
+pub const PARTICLES: usize = 10_000;
+
+/// The depth of the standard `deep_flow` scene.
+pub const FLOW_DEPTH: usize = 200;
+
+/// The number of lines in the standard `long_text` scene.
+pub const TEXT_LINES: usize = 500;
+
+
+/// Every standard scene for a render target of the given size, along with its name.
+pub fn scenes(width: i32, height: i32) -> Vec<(&'static str, Element)> {
+    vec![
+        ("particles", particles(PARTICLES, width, height)),
+        ("deep_flow", deep_flow(FLOW_DEPTH)),
+        ("long_text", long_text(TEXT_LINES, width, height)),
+    ]
+}
+
+
+/// A collage of the given size scattered with the given number of small, translucent circles of
+/// varying color and size.
+pub fn particles(count: usize, width: i32, height: i32) -> Element {
+    let (w, h) = (width as f64, height as f64);
+    let forms = (0..count).map(|i| {
+        let (x, y) = scatter(i);
+        let radius = 1.0 + 4.0 * fract(i as f64 * 0.381966);
+        form::circle(radius)
+            .filled(hue(i).with_alpha(0.75))
+            .shift((x - 0.5) * w, (y - 0.5) * h)
+    }).collect();
+    form::collage(width, height, forms)
+}
+
+
+/// An `Element` tree of the given depth, where each level is a flow alternating between down and
+/// right, holding a colored spacer and the next level within a padded, colored container.
+pub fn deep_flow(depth: usize) -> Element {
+    (0..depth).rev().fold(element::spacer(8, 8).color(hue(depth)), |inner, level| {
+        let direction = if level % 2 == 0 { element::down() } else { element::right() };
+        let (w, h) = inner.get_size();
+        let padded = inner.container(w + 4, h + 4, element::middle()).color(hue(level));
+        element::flow(direction, vec![element::spacer(4, 4).color(hue(level + 1)), padded])
+    })
+}
+
+
+/// A collage of the given size filled with the given number of lines of text, each of a
+/// different length and color.
+pub fn long_text(lines: usize, width: i32, height: i32) -> Element {
+    let (w, h) = (width as f64, height as f64);
+    let line_height = 14.0;
+    let forms: Vec<Form> = (0..lines).map(|i| {
+        let words = 4 + i % 12;
+        let string = (0..words).map(|word| WORDS[(i + word * 7) % WORDS.len()])
+            .collect::<Vec<_>>()
+            .join(" ");
+        let text = Text::from_string(string).height(line_height).color(hue(i));
+        let y = h / 2.0 - line_height * (i as f64 + 1.0) % h;
+        form::text(text).shift(-w / 4.0, y)
+    }).collect();
+    form::collage(width, height, forms)
+}
+
+
+/// The words from which the lines of `long_text` are built.
+const WORDS: &[&str] = &[
+    "lorem", "ipsum", "dolor", "sit", "amet", "consectetur", "adipiscing", "elit", "sed", "do",
+    "eiusmod", "tempor", "incididunt", "ut", "labore", "et", "dolore", "magna", "aliqua",
+];
+
+
+/// The position, within the unit square, of the `i`th point of a low-discrepancy sequence, which
+/// spreads points evenly without a random number generator.
+fn scatter(i: usize) -> (f64, f64) {
+    // The reciprocals of the plastic number and its square.
+    (fract(0.5 + i as f64 * 0.7548776662466927), fract(0.5 + i as f64 * 0.5698402909980532))
+}
+
+
+/// A vivid color whose hue varies with `i`.
+fn hue(i: usize) -> Color {
+    hsl(2.0 * PI * fract(i as f64 * 0.618034) as f32, 0.7, 0.5)
+}
+
+
+/// The fractional part of `f`.
+fn fract(f: f64) -> f64 {
+    f - f.floor()
+}
@@ -0,0 +1,537 @@
+//!
+//! The [Material Design color palette](https://m2.material.io/design/color/the-color-system.html)
+//! from 2014, with the full range of shades of each hue.
+//!
+//! Shades range from `50` (the lightest) through `500` (the primary shade) to `900` (the
+//! darkest). Most hues also have the accent shades `A100` to `A700`, e.g. `blue_a200`.
+//!
+
+use super::{rgb_bytes, Color};
+
+
+/// Red 50 - #FFEBEE
+pub fn red_50() -> Color { rgb_bytes(255, 235, 238) }
+/// Red 100 - #FFCDD2
+pub fn red_100() -> Color { rgb_bytes(255, 205, 210) }
+/// Red 200 - #EF9A9A
+pub fn red_200() -> Color { rgb_bytes(239, 154, 154) }
+/// Red 300 - #E57373
+pub fn red_300() -> Color { rgb_bytes(229, 115, 115) }
+/// Red 400 - #EF5350
+pub fn red_400() -> Color { rgb_bytes(239, 83, 80) }
+/// Red 500 - #F44336
+pub fn red_500() -> Color { rgb_bytes(244, 67, 54) }
+/// Red 600 - #E53935
+pub fn red_600() -> Color { rgb_bytes(229, 57, 53) }
+/// Red 700 - #D32F2F
+pub fn red_700() -> Color { rgb_bytes(211, 47, 47) }
+/// Red 800 - #C62828
+pub fn red_800() -> Color { rgb_bytes(198, 40, 40) }
+/// Red 900 - #B71C1C
+pub fn red_900() -> Color { rgb_bytes(183, 28, 28) }
+/// Red A100 - #FF8A80
+pub fn red_a100() -> Color { rgb_bytes(255, 138, 128) }
+/// Red A200 - #FF5252
+pub fn red_a200() -> Color { rgb_bytes(255, 82, 82) }
+/// Red A400 - #FF1744
+pub fn red_a400() -> Color { rgb_bytes(255, 23, 68) }
+/// Red A700 - #D50000
+pub fn red_a700() -> Color { rgb_bytes(213, 0, 0) }
+
+/// Pink 50 - #FCE4EC
+pub fn pink_50() -> Color { rgb_bytes(252, 228, 236) }
+/// Pink 100 - #F8BBD0
+pub fn pink_100() -> Color { rgb_bytes(248, 187, 208) }
+/// Pink 200 - #F48FB1
+pub fn pink_200() -> Color { rgb_bytes(244, 143, 177) }
+/// Pink 300 - #F06292
+pub fn pink_300() -> Color { rgb_bytes(240, 98, 146) }
+/// Pink 400 - #EC407A
+pub fn pink_400() -> Color { rgb_bytes(236, 64, 122) }
+/// Pink 500 - #E91E63
+pub fn pink_500() -> Color { rgb_bytes(233, 30, 99) }
+/// Pink 600 - #D81B60
+pub fn pink_600() -> Color { rgb_bytes(216, 27, 96) }
+/// Pink 700 - #C2185B
+pub fn pink_700() -> Color { rgb_bytes(194, 24, 91) }
+/// Pink 800 - #AD1457
+pub fn pink_800() -> Color { rgb_bytes(173, 20, 87) }
+/// Pink 900 - #880E4F
+pub fn pink_900() -> Color { rgb_bytes(136, 14, 79) }
+/// Pink A100 - #FF80AB
+pub fn pink_a100() -> Color { rgb_bytes(255, 128, 171) }
+/// Pink A200 - #FF4081
+pub fn pink_a200() -> Color { rgb_bytes(255, 64, 129) }
+/// Pink A400 - #F50057
+pub fn pink_a400() -> Color { rgb_bytes(245, 0, 87) }
+/// Pink A700 - #C51162
+pub fn pink_a700() -> Color { rgb_bytes(197, 17, 98) }
+
+/// Purple 50 - #F3E5F5
+pub fn purple_50() -> Color { rgb_bytes(243, 229, 245) }
+/// Purple 100 - #E1BEE7
+pub fn purple_100() -> Color { rgb_bytes(225, 190, 231) }
+/// Purple 200 - #CE93D8
+pub fn purple_200() -> Color { rgb_bytes(206, 147, 216) }
+/// Purple 300 - #BA68C8
+pub fn purple_300() -> Color { rgb_bytes(186, 104, 200) }
+/// Purple 400 - #AB47BC
+pub fn purple_400() -> Color { rgb_bytes(171, 71, 188) }
+/// Purple 500 - #9C27B0
+pub fn purple_500() -> Color { rgb_bytes(156, 39, 176) }
+/// Purple 600 - #8E24AA
+pub fn purple_600() -> Color { rgb_bytes(142, 36, 170) }
+/// Purple 700 - #7B1FA2
+pub fn purple_700() -> Color { rgb_bytes(123, 31, 162) }
+/// Purple 800 - #6A1B9A
+pub fn purple_800() -> Color { rgb_bytes(106, 27, 154) }
+/// Purple 900 - #4A148C
+pub fn purple_900() -> Color { rgb_bytes(74, 20, 140) }
+/// Purple A100 - #EA80FC
+pub fn purple_a100() -> Color { rgb_bytes(234, 128, 252) }
+/// Purple A200 - #E040FB
+pub fn purple_a200() -> Color { rgb_bytes(224, 64, 251) }
+/// Purple A400 - #D500F9
+pub fn purple_a400() -> Color { rgb_bytes(213, 0, 249) }
+/// Purple A700 - #AA00FF
+pub fn purple_a700() -> Color { rgb_bytes(170, 0, 255) }
+
+/// Deep Purple 50 - #EDE7F6
+pub fn deep_purple_50() -> Color { rgb_bytes(237, 231, 246) }
+/// Deep Purple 100 - #D1C4E9
+pub fn deep_purple_100() -> Color { rgb_bytes(209, 196, 233) }
+/// Deep Purple 200 - #B39DDB
+pub fn deep_purple_200() -> Color { rgb_bytes(179, 157, 219) }
+/// Deep Purple 300 - #9575CD
+pub fn deep_purple_300() -> Color { rgb_bytes(149, 117, 205) }
+/// Deep Purple 400 - #7E57C2
+pub fn deep_purple_400() -> Color { rgb_bytes(126, 87, 194) }
+/// Deep Purple 500 - #673AB7
+pub fn deep_purple_500() -> Color { rgb_bytes(103, 58, 183) }
+/// Deep Purple 600 - #5E35B1
+pub fn deep_purple_600() -> Color { rgb_bytes(94, 53, 177) }
+/// Deep Purple 700 - #512DA8
+pub fn deep_purple_700() -> Color { rgb_bytes(81, 45, 168) }
+/// Deep Purple 800 - #4527A0
+pub fn deep_purple_800() -> Color { rgb_bytes(69, 39, 160) }
+/// Deep Purple 900 - #311B92
+pub fn deep_purple_900() -> Color { rgb_bytes(49, 27, 146) }
+/// Deep Purple A100 - #B388FF
+pub fn deep_purple_a100() -> Color { rgb_bytes(179, 136, 255) }
+/// Deep Purple A200 - #7C4DFF
+pub fn deep_purple_a200() -> Color { rgb_bytes(124, 77, 255) }
+/// Deep Purple A400 - #651FFF
+pub fn deep_purple_a400() -> Color { rgb_bytes(101, 31, 255) }
+/// Deep Purple A700 - #6200EA
+pub fn deep_purple_a700() -> Color { rgb_bytes(98, 0, 234) }
+
+/// Indigo 50 - #E8EAF6
+pub fn indigo_50() -> Color { rgb_bytes(232, 234, 246) }
+/// Indigo 100 - #C5CAE9
+pub fn indigo_100() -> Color { rgb_bytes(197, 202, 233) }
+/// Indigo 200 - #9FA8DA
+pub fn indigo_200() -> Color { rgb_bytes(159, 168, 218) }
+/// Indigo 300 - #7986CB
+pub fn indigo_300() -> Color { rgb_bytes(121, 134, 203) }
+/// Indigo 400 - #5C6BC0
+pub fn indigo_400() -> Color { rgb_bytes(92, 107, 192) }
+/// Indigo 500 - #3F51B5
+pub fn indigo_500() -> Color { rgb_bytes(63, 81, 181) }
+/// Indigo 600 - #3949AB
+pub fn indigo_600() -> Color { rgb_bytes(57, 73, 171) }
+/// Indigo 700 - #303F9F
+pub fn indigo_700() -> Color { rgb_bytes(48, 63, 159) }
+/// Indigo 800 - #283593
+pub fn indigo_800() -> Color { rgb_bytes(40, 53, 147) }
+/// Indigo 900 - #1A237E
+pub fn indigo_900() -> Color { rgb_bytes(26, 35, 126) }
+/// Indigo A100 - #8C9EFF
+pub fn indigo_a100() -> Color { rgb_bytes(140, 158, 255) }
+/// Indigo A200 - #536DFE
+pub fn indigo_a200() -> Color { rgb_bytes(83, 109, 254) }
+/// Indigo A400 - #3D5AFE
+pub fn indigo_a400() -> Color { rgb_bytes(61, 90, 254) }
+/// Indigo A700 - #304FFE
+pub fn indigo_a700() -> Color { rgb_bytes(48, 79, 254) }
+
+/// Blue 50 - #E3F2FD
+pub fn blue_50() -> Color { rgb_bytes(227, 242, 253) }
+/// Blue 100 - #BBDEFB
+pub fn blue_100() -> Color { rgb_bytes(187, 222, 251) }
+/// Blue 200 - #90CAF9
+pub fn blue_200() -> Color { rgb_bytes(144, 202, 249) }
+/// Blue 300 - #64B5F6
+pub fn blue_300() -> Color { rgb_bytes(100, 181, 246) }
+/// Blue 400 - #42A5F5
+pub fn blue_400() -> Color { rgb_bytes(66, 165, 245) }
+/// Blue 500 - #2196F3
+pub fn blue_500() -> Color { rgb_bytes(33, 150, 243) }
+/// Blue 600 - #1E88E5
+pub fn blue_600() -> Color { rgb_bytes(30, 136, 229) }
+/// Blue 700 - #1976D2
+pub fn blue_700() -> Color { rgb_bytes(25, 118, 210) }
+/// Blue 800 - #1565C0
+pub fn blue_800() -> Color { rgb_bytes(21, 101, 192) }
+/// Blue 900 - #0D47A1
+pub fn blue_900() -> Color { rgb_bytes(13, 71, 161) }
+/// Blue A100 - #82B1FF
+pub fn blue_a100() -> Color { rgb_bytes(130, 177, 255) }
+/// Blue A200 - #448AFF
+pub fn blue_a200() -> Color { rgb_bytes(68, 138, 255) }
+/// Blue A400 - #2979FF
+pub fn blue_a400() -> Color { rgb_bytes(41, 121, 255) }
+/// Blue A700 - #2962FF
+pub fn blue_a700() -> Color { rgb_bytes(41, 98, 255) }
+
+/// Light Blue 50 - #E1F5FE
+pub fn light_blue_50() -> Color { rgb_bytes(225, 245, 254) }
+/// Light Blue 100 - #B3E5FC
+pub fn light_blue_100() -> Color { rgb_bytes(179, 229, 252) }
+/// Light Blue 200 - #81D4FA
+pub fn light_blue_200() -> Color { rgb_bytes(129, 212, 250) }
+/// Light Blue 300 - #4FC3F7
+pub fn light_blue_300() -> Color { rgb_bytes(79, 195, 247) }
+/// Light Blue 400 - #29B6F6
+pub fn light_blue_400() -> Color { rgb_bytes(41, 182, 246) }
+/// Light Blue 500 - #03A9F4
+pub fn light_blue_500() -> Color { rgb_bytes(3, 169, 244) }
+/// Light Blue 600 - #039BE5
+pub fn light_blue_600() -> Color { rgb_bytes(3, 155, 229) }
+/// Light Blue 700 - #0288D1
+pub fn light_blue_700() -> Color { rgb_bytes(2, 136, 209) }
+/// Light Blue 800 - #0277BD
+pub fn light_blue_800() -> Color { rgb_bytes(2, 119, 189) }
+/// Light Blue 900 - #01579B
+pub fn light_blue_900() -> Color { rgb_bytes(1, 87, 155) }
+/// Light Blue A100 - #80D8FF
+pub fn light_blue_a100() -> Color { rgb_bytes(128, 216, 255) }
+/// Light Blue A200 - #40C4FF
+pub fn light_blue_a200() -> Color { rgb_bytes(64, 196, 255) }
+/// Light Blue A400 - #00B0FF
+pub fn light_blue_a400() -> Color { rgb_bytes(0, 176, 255) }
+/// Light Blue A700 - #0091EA
+pub fn light_blue_a700() -> Color { rgb_bytes(0, 145, 234) }
+
+/// Cyan 50 - #E0F7FA
+pub fn cyan_50() -> Color { rgb_bytes(224, 247, 250) }
+/// Cyan 100 - #B2EBF2
+pub fn cyan_100() -> Color { rgb_bytes(178, 235, 242) }
+/// Cyan 200 - #80DEEA
+pub fn cyan_200() -> Color { rgb_bytes(128, 222, 234) }
+/// Cyan 300 - #4DD0E1
+pub fn cyan_300() -> Color { rgb_bytes(77, 208, 225) }
+/// Cyan 400 - #26C6DA
+pub fn cyan_400() -> Color { rgb_bytes(38, 198, 218) }
+/// Cyan 500 - #00BCD4
+pub fn cyan_500() -> Color { rgb_bytes(0, 188, 212) }
+/// Cyan 600 - #00ACC1
+pub fn cyan_600() -> Color { rgb_bytes(0, 172, 193) }
+/// Cyan 700 - #0097A7
+pub fn cyan_700() -> Color { rgb_bytes(0, 151, 167) }
+/// Cyan 800 - #00838F
+pub fn cyan_800() -> Color { rgb_bytes(0, 131, 143) }
+/// Cyan 900 - #006064
+pub fn cyan_900() -> Color { rgb_bytes(0, 96, 100) }
+/// Cyan A100 - #84FFFF
+pub fn cyan_a100() -> Color { rgb_bytes(132, 255, 255) }
+/// Cyan A200 - #18FFFF
+pub fn cyan_a200() -> Color { rgb_bytes(24, 255, 255) }
+/// Cyan A400 - #00E5FF
+pub fn cyan_a400() -> Color { rgb_bytes(0, 229, 255) }
+/// Cyan A700 - #00B8D4
+pub fn cyan_a700() -> Color { rgb_bytes(0, 184, 212) }
+
+/// Teal 50 - #E0F2F1
+pub fn teal_50() -> Color { rgb_bytes(224, 242, 241) }
+/// Teal 100 - #B2DFDB
+pub fn teal_100() -> Color { rgb_bytes(178, 223, 219) }
+/// Teal 200 - #80CBC4
+pub fn teal_200() -> Color { rgb_bytes(128, 203, 196) }
+/// Teal 300 - #4DB6AC
+pub fn teal_300() -> Color { rgb_bytes(77, 182, 172) }
+/// Teal 400 - #26A69A
+pub fn teal_400() -> Color { rgb_bytes(38, 166, 154) }
+/// Teal 500 - #009688
+pub fn teal_500() -> Color { rgb_bytes(0, 150, 136) }
+/// Teal 600 - #00897B
+pub fn teal_600() -> Color { rgb_bytes(0, 137, 123) }
+/// Teal 700 - #00796B
+pub fn teal_700() -> Color { rgb_bytes(0, 121, 107) }
+/// Teal 800 - #00695C
+pub fn teal_800() -> Color { rgb_bytes(0, 105, 92) }
+/// Teal 900 - #004D40
+pub fn teal_900() -> Color { rgb_bytes(0, 77, 64) }
+/// Teal A100 - #A7FFEB
+pub fn teal_a100() -> Color { rgb_bytes(167, 255, 235) }
+/// Teal A200 - #64FFDA
+pub fn teal_a200() -> Color { rgb_bytes(100, 255, 218) }
+/// Teal A400 - #1DE9B6
+pub fn teal_a400() -> Color { rgb_bytes(29, 233, 182) }
+/// Teal A700 - #00BFA5
+pub fn teal_a700() -> Color { rgb_bytes(0, 191, 165) }
+
+/// Green 50 - #E8F5E9
+pub fn green_50() -> Color { rgb_bytes(232, 245, 233) }
+/// Green 100 - #C8E6C9
+pub fn green_100() -> Color { rgb_bytes(200, 230, 201) }
+/// Green 200 - #A5D6A7
+pub fn green_200() -> Color { rgb_bytes(165, 214, 167) }
+/// Green 300 - #81C784
+pub fn green_300() -> Color { rgb_bytes(129, 199, 132) }
+/// Green 400 - #66BB6A
+pub fn green_400() -> Color { rgb_bytes(102, 187, 106) }
+/// Green 500 - #4CAF50
+pub fn green_500() -> Color { rgb_bytes(76, 175, 80) }
+/// Green 600 - #43A047
+pub fn green_600() -> Color { rgb_bytes(67, 160, 71) }
+/// Green 700 - #388E3C
+pub fn green_700() -> Color { rgb_bytes(56, 142, 60) }
+/// Green 800 - #2E7D32
+pub fn green_800() -> Color { rgb_bytes(46, 125, 50) }
+/// Green 900 - #1B5E20
+pub fn green_900() -> Color { rgb_bytes(27, 94, 32) }
+/// Green A100 - #B9F6CA
+pub fn green_a100() -> Color { rgb_bytes(185, 246, 202) }
+/// Green A200 - #69F0AE
+pub fn green_a200() -> Color { rgb_bytes(105, 240, 174) }
+/// Green A400 - #00E676
+pub fn green_a400() -> Color { rgb_bytes(0, 230, 118) }
+/// Green A700 - #00C853
+pub fn green_a700() -> Color { rgb_bytes(0, 200, 83) }
+
+/// Light Green 50 - #F1F8E9
+pub fn light_green_50() -> Color { rgb_bytes(241, 248, 233) }
+/// Light Green 100 - #DCEDC8
+pub fn light_green_100() -> Color { rgb_bytes(220, 237, 200) }
+/// Light Green 200 - #C5E1A5
+pub fn light_green_200() -> Color { rgb_bytes(197, 225, 165) }
+/// Light Green 300 - #AED581
+pub fn light_green_300() -> Color { rgb_bytes(174, 213, 129) }
+/// Light Green 400 - #9CCC65
+pub fn light_green_400() -> Color { rgb_bytes(156, 204, 101) }
+/// Light Green 500 - #8BC34A
+pub fn light_green_500() -> Color { rgb_bytes(139, 195, 74) }
+/// Light Green 600 - #7CB342
+pub fn light_green_600() -> Color { rgb_bytes(124, 179, 66) }
+/// Light Green 700 - #689F38
+pub fn light_green_700() -> Color { rgb_bytes(104, 159, 56) }
+/// Light Green 800 - #558B2F
+pub fn light_green_800() -> Color { rgb_bytes(85, 139, 47) }
+/// Light Green 900 - #33691E
+pub fn light_green_900() -> Color { rgb_bytes(51, 105, 30) }
+/// Light Green A100 - #CCFF90
+pub fn light_green_a100() -> Color { rgb_bytes(204, 255, 144) }
+/// Light Green A200 - #B2FF59
+pub fn light_green_a200() -> Color { rgb_bytes(178, 255, 89) }
+/// Light Green A400 - #76FF03
+pub fn light_green_a400() -> Color { rgb_bytes(118, 255, 3) }
+/// Light Green A700 - #64DD17
+pub fn light_green_a700() -> Color { rgb_bytes(100, 221, 23) }
+
+/// Lime 50 - #F9FBE7
+pub fn lime_50() -> Color { rgb_bytes(249, 251, 231) }
+/// Lime 100 - #F0F4C3
+pub fn lime_100() -> Color { rgb_bytes(240, 244, 195) }
+/// Lime 200 - #E6EE9C
+pub fn lime_200() -> Color { rgb_bytes(230, 238, 156) }
+/// Lime 300 - #DCE775
+pub fn lime_300() -> Color { rgb_bytes(220, 231, 117) }
+/// Lime 400 - #D4E157
+pub fn lime_400() -> Color { rgb_bytes(212, 225, 87) }
+/// Lime 500 - #CDDC39
+pub fn lime_500() -> Color { rgb_bytes(205, 220, 57) }
+/// Lime 600 - #C0CA33
+pub fn lime_600() -> Color { rgb_bytes(192, 202, 51) }
+/// Lime 700 - #AFB42B
+pub fn lime_700() -> Color { rgb_bytes(175, 180, 43) }
+/// Lime 800 - #9E9D24
+pub fn lime_800() -> Color { rgb_bytes(158, 157, 36) }
+/// Lime 900 - #827717
+pub fn lime_900() -> Color { rgb_bytes(130, 119, 23) }
+/// Lime A100 - #F4FF81
+pub fn lime_a100() -> Color { rgb_bytes(244, 255, 129) }
+/// Lime A200 - #EEFF41
+pub fn lime_a200() -> Color { rgb_bytes(238, 255, 65) }
+/// Lime A400 - #C6FF00
+pub fn lime_a400() -> Color { rgb_bytes(198, 255, 0) }
+/// Lime A700 - #AEEA00
+pub fn lime_a700() -> Color { rgb_bytes(174, 234, 0) }
+
+/// Yellow 50 - #FFFDE7
+pub fn yellow_50() -> Color { rgb_bytes(255, 253, 231) }
+/// Yellow 100 - #FFF9C4
+pub fn yellow_100() -> Color { rgb_bytes(255, 249, 196) }
+/// Yellow 200 - #FFF59D
+pub fn yellow_200() -> Color { rgb_bytes(255, 245, 157) }
+/// Yellow 300 - #FFF176
+pub fn yellow_300() -> Color { rgb_bytes(255, 241, 118) }
+/// Yellow 400 - #FFEE58
+pub fn yellow_400() -> Color { rgb_bytes(255, 238, 88) }
+/// Yellow 500 - #FFEB3B
+pub fn yellow_500() -> Color { rgb_bytes(255, 235, 59) }
+/// Yellow 600 - #FDD835
+pub fn yellow_600() -> Color { rgb_bytes(253, 216, 53) }
+/// Yellow 700 - #FBC02D
+pub fn yellow_700() -> Color { rgb_bytes(251, 192, 45) }
+/// Yellow 800 - #F9A825
+pub fn yellow_800() -> Color { rgb_bytes(249, 168, 37) }
+/// Yellow 900 - #F57F17
+pub fn yellow_900() -> Color { rgb_bytes(245, 127, 23) }
+/// Yellow A100 - #FFFF8D
+pub fn yellow_a100() -> Color { rgb_bytes(255, 255, 141) }
+/// Yellow A200 - #FFFF00
+pub fn yellow_a200() -> Color { rgb_bytes(255, 255, 0) }
+/// Yellow A400 - #FFEA00
+pub fn yellow_a400() -> Color { rgb_bytes(255, 234, 0) }
+/// Yellow A700 - #FFD600
+pub fn yellow_a700() -> Color { rgb_bytes(255, 214, 0) }
+
+/// Amber 50 - #FFF8E1
+pub fn amber_50() -> Color { rgb_bytes(255, 248, 225) }
+/// Amber 100 - #FFECB3
+pub fn amber_100() -> Color { rgb_bytes(255, 236, 179) }
+/// Amber 200 - #FFE082
+pub fn amber_200() -> Color { rgb_bytes(255, 224, 130) }
+/// Amber 300 - #FFD54F
+pub fn amber_300() -> Color { rgb_bytes(255, 213, 79) }
+/// Amber 400 - #FFCA28
+pub fn amber_400() -> Color { rgb_bytes(255, 202, 40) }
+/// Amber 500 - #FFC107
+pub fn amber_500() -> Color { rgb_bytes(255, 193, 7) }
+/// Amber 600 - #FFB300
+pub fn amber_600() -> Color { rgb_bytes(255, 179, 0) }
+/// Amber 700 - #FFA000
+pub fn amber_700() -> Color { rgb_bytes(255, 160, 0) }
+/// Amber 800 - #FF8F00
+pub fn amber_800() -> Color { rgb_bytes(255, 143, 0) }
+/// Amber 900 - #FF6F00
+pub fn amber_900() -> Color { rgb_bytes(255, 111, 0) }
+/// Amber A100 - #FFE57F
+pub fn amber_a100() -> Color { rgb_bytes(255, 229, 127) }
+/// Amber A200 - #FFD740
+pub fn amber_a200() -> Color { rgb_bytes(255, 215, 64) }
+/// Amber A400 - #FFC400
+pub fn amber_a400() -> Color { rgb_bytes(255, 196, 0) }
+/// Amber A700 - #FFAB00
+pub fn amber_a700() -> Color { rgb_bytes(255, 171, 0) }
+
+/// Orange 50 - #FFF3E0
+pub fn orange_50() -> Color { rgb_bytes(255, 243, 224) }
+/// Orange 100 - #FFE0B2
+pub fn orange_100() -> Color { rgb_bytes(255, 224, 178) }
+/// Orange 200 - #FFCC80
+pub fn orange_200() -> Color { rgb_bytes(255, 204, 128) }
+/// Orange 300 - #FFB74D
+pub fn orange_300() -> Color { rgb_bytes(255, 183, 77) }
+/// Orange 400 - #FFA726
+pub fn orange_400() -> Color { rgb_bytes(255, 167, 38) }
+/// Orange 500 - #FF9800
+pub fn orange_500() -> Color { rgb_bytes(255, 152, 0) }
+/// Orange 600 - #FB8C00
+pub fn orange_600() -> Color { rgb_bytes(251, 140, 0) }
+/// Orange 700 - #F57C00
+pub fn orange_700() -> Color { rgb_bytes(245, 124, 0) }
+/// Orange 800 - #EF6C00
+pub fn orange_800() -> Color { rgb_bytes(239, 108, 0) }
+/// Orange 900 - #E65100
+pub fn orange_900() -> Color { rgb_bytes(230, 81, 0) }
+/// Orange A100 - #FFD180
+pub fn orange_a100() -> Color { rgb_bytes(255, 209, 128) }
+/// Orange A200 - #FFAB40
+pub fn orange_a200() -> Color { rgb_bytes(255, 171, 64) }
+/// Orange A400 - #FF9100
+pub fn orange_a400() -> Color { rgb_bytes(255, 145, 0) }
+/// Orange A700 - #FF6D00
+pub fn orange_a700() -> Color { rgb_bytes(255, 109, 0) }
+
+/// Deep Orange 50 - #FBE9E7
+pub fn deep_orange_50() -> Color { rgb_bytes(251, 233, 231) }
+/// Deep Orange 100 - #FFCCBC
+pub fn deep_orange_100() -> Color { rgb_bytes(255, 204, 188) }
+/// Deep Orange 200 - #FFAB91
+pub fn deep_orange_200() -> Color { rgb_bytes(255, 171, 145) }
+/// Deep Orange 300 - #FF8A65
+pub fn deep_orange_300() -> Color { rgb_bytes(255, 138, 101) }
+/// Deep Orange 400 - #FF7043
+pub fn deep_orange_400() -> Color { rgb_bytes(255, 112, 67) }
+/// Deep Orange 500 - #FF5722
+pub fn deep_orange_500() -> Color { rgb_bytes(255, 87, 34) }
+/// Deep Orange 600 - #F4511E
+pub fn deep_orange_600() -> Color { rgb_bytes(244, 81, 30) }
+/// Deep Orange 700 - #E64A19
+pub fn deep_orange_700() -> Color { rgb_bytes(230, 74, 25) }
+/// Deep Orange 800 - #D84315
+pub fn deep_orange_800() -> Color { rgb_bytes(216, 67, 21) }
+/// Deep Orange 900 - #BF360C
+pub fn deep_orange_900() -> Color { rgb_bytes(191, 54, 12) }
+/// Deep Orange A100 - #FF9E80
+pub fn deep_orange_a100() -> Color { rgb_bytes(255, 158, 128) }
+/// Deep Orange A200 - #FF6E40
+pub fn deep_orange_a200() -> Color { rgb_bytes(255, 110, 64) }
+/// Deep Orange A400 - #FF3D00
+pub fn deep_orange_a400() -> Color { rgb_bytes(255, 61, 0) }
+/// Deep Orange A700 - #DD2C00
+pub fn deep_orange_a700() -> Color { rgb_bytes(221, 44, 0) }
+
+/// Brown 50 - #EFEBE9
+pub fn brown_50() -> Color { rgb_bytes(239, 235, 233) }
+/// Brown 100 - #D7CCC8
+pub fn brown_100() -> Color { rgb_bytes(215, 204, 200) }
+/// Brown 200 - #BCAAA4
+pub fn brown_200() -> Color { rgb_bytes(188, 170, 164) }
+/// Brown 300 - #A1887F
+pub fn brown_300() -> Color { rgb_bytes(161, 136, 127) }
+/// Brown 400 - #8D6E63
+pub fn brown_400() -> Color { rgb_bytes(141, 110, 99) }
+/// Brown 500 - #795548
+pub fn brown_500() -> Color { rgb_bytes(121, 85, 72) }
+/// Brown 600 - #6D4C41
+pub fn brown_600() -> Color { rgb_bytes(109, 76, 65) }
+/// Brown 700 - #5D4037
+pub fn brown_700() -> Color { rgb_bytes(93, 64, 55) }
+/// Brown 800 - #4E342E
+pub fn brown_800() -> Color { rgb_bytes(78, 52, 46) }
+/// Brown 900 - #3E2723
+pub fn brown_900() -> Color { rgb_bytes(62, 39, 35) }
+
+/// Grey 50 - #FAFAFA
+pub fn grey_50() -> Color { rgb_bytes(250, 250, 250) }
+/// Grey 100 - #F5F5F5
+pub fn grey_100() -> Color { rgb_bytes(245, 245, 245) }
+/// Grey 200 - #EEEEEE
+pub fn grey_200() -> Color { rgb_bytes(238, 238, 238) }
+/// Grey 300 - #E0E0E0
+pub fn grey_300() -> Color { rgb_bytes(224, 224, 224) }
+/// Grey 400 - #BDBDBD
+pub fn grey_400() -> Color { rgb_bytes(189, 189, 189) }
+/// Grey 500 - #9E9E9E
+pub fn grey_500() -> Color { rgb_bytes(158, 158, 158) }
+/// Grey 600 - #757575
+pub fn grey_600() -> Color { rgb_bytes(117, 117, 117) }
+/// Grey 700 - #616161
+pub fn grey_700() -> Color { rgb_bytes(97, 97, 97) }
+/// Grey 800 - #424242
+pub fn grey_800() -> Color { rgb_bytes(66, 66, 66) }
+/// Grey 900 - #212121
+pub fn grey_900() -> Color { rgb_bytes(33, 33, 33) }
+
+/// Blue Grey 50 - #ECEFF1
+pub fn blue_grey_50() -> Color { rgb_bytes(236, 239, 241) }
+/// Blue Grey 100 - #CFD8DC
+pub fn blue_grey_100() -> Color { rgb_bytes(207, 216, 220) }
+/// Blue Grey 200 - #B0BEC5
+pub fn blue_grey_200() -> Color { rgb_bytes(176, 190, 197) }
+/// Blue Grey 300 - #90A4AE
+pub fn blue_grey_300() -> Color { rgb_bytes(144, 164, 174) }
+/// Blue Grey 400 - #78909C
+pub fn blue_grey_400() -> Color { rgb_bytes(120, 144, 156) }
+/// Blue Grey 500 - #607D8B
+pub fn blue_grey_500() -> Color { rgb_bytes(96, 125, 139) }
+/// Blue Grey 600 - #546E7A
+pub fn blue_grey_600() -> Color { rgb_bytes(84, 110, 122) }
+/// Blue Grey 700 - #455A64
+pub fn blue_grey_700() -> Color { rgb_bytes(69, 90, 100) }
+/// Blue Grey 800 - #37474F
+pub fn blue_grey_800() -> Color { rgb_bytes(55, 71, 79) }
+/// Blue Grey 900 - #263238
+pub fn blue_grey_900() -> Color { rgb_bytes(38, 50, 56) }
@@ -0,0 +1,908 @@
+//! 
+//! A library providing simple `Color` and `Gradient` types along with useful transformations and
+//! presets.
+//!
+//!
+//! Inspiration taken from [elm-lang's color module]
+//! (https://github.com/elm-lang/core/blob/62b22218c42fb8ccc996c86bea450a14991ab815/src/Color.elm)
+//!
+//!
+//! Module for working with colors. Includes [RGB](https://en.wikipedia.org/wiki/RGB_color_model)
+//! and [HSL](http://en.wikipedia.org/wiki/HSL_and_HSV) creation, gradients and built-in names.
+//!
+//! The built-in names come from the Tango palette. The `material` and `tailwind` modules provide
+//! larger palettes with the full range of shades of each hue, e.g. `material::blue_500()`.
+//!
+//! Colors are formatted in CSS syntax by `Display` and may be parsed from it with `str::parse`,
+//! e.g. `"#ff8000".parse::<Color>()`, so that they may be round-tripped through config files.
+//!
+
+use angle::Angle;
+use rand::Rng;
+use std::error;
+use std::f32::consts::PI;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+use utils::{clampf32, degrees, fmod, hash_f32, hash_f64, min, max, turns, wrap_angle};
+
+pub mod material;
+pub mod tailwind;
+
+
+/// Color supporting RGB and HSL variants.
+#[derive(PartialEq, Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Color {
+    /// Red, Green, Blue, Alpha - All values' scales represented between 0.0 and 1.0.
+    Rgba(f32, f32, f32, f32),
+    /// Hue, Saturation, Lightness, Alpha - all valuess scales represented between 0.0 and 1.0.
+    Hsla(f32, f32, f32, f32),
+}
+
+/// Regional spelling alias.
+pub type Colour = Color;
+
+
+/// Colors are compared by value and hashed by the bit patterns of their components, so `Eq` only
+/// holds for colors without `NaN` components.
+impl Eq for Color {}
+
+impl Hash for Color {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let (variant, [a, b, c, d]) = match *self {
+            Color::Rgba(r, g, b, a) => (0u8, [r, g, b, a]),
+            Color::Hsla(h, s, l, a) => (1u8, [h, s, l, a]),
+        };
+        variant.hash(state);
+        for &f in [a, b, c, d].iter() {
+            hash_f32(f, state);
+        }
+    }
+}
+
+
+/// Create RGB colors with an alpha component for transparency.
+/// The alpha component is specified with numbers between 0 and 1.
+#[inline]
+pub fn rgba(r: f32, g: f32, b: f32, a: f32) -> Color {
+    Color::Rgba(r, g, b, a)
+}
+
+
+/// Create RGB colors from numbers between 0.0 and 1.0.
+#[inline]
+pub fn rgb(r: f32, g: f32, b: f32) -> Color {
+    Color::Rgba(r, g, b, 1.0)
+}
+
+
+/// Create RGB colors from numbers between 0 and 255 inclusive.
+/// The alpha component is specified with numbers between 0 and 1.
+#[inline]
+pub fn rgba_bytes(r: u8, g: u8, b: u8, a: f32) -> Color {
+    Color::Rgba(r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0, a)
+}
+
+
+/// Create RGB colors from numbers between 0 and 255 inclusive.
+#[inline]
+pub fn rgb_bytes(r: u8, g: u8, b: u8) -> Color {
+    rgba_bytes(r, g, b, 1.0)
+}
+
+
+/// Create [HSL colors](http://en.wikipedia.org/wiki/HSL_and_HSV) with an alpha component for
+/// transparency.
+#[inline]
+pub fn hsla<A: Into<Angle>>(hue: A, saturation: f32, lightness: f32, alpha: f32) -> Color {
+    Color::Hsla(wrap_angle(hue.into().radians() as f32), saturation, lightness, alpha)
+}
+
+
+/// Create [HSL colors](http://en.wikipedia.org/wiki/HSL_and_HSV). This gives you access to colors
+/// more like a color wheel, where all hues are arranged in a circle that you specify with an
+/// `Angle` (or radians).
+/// 
+///   red        = hsl(Angle::deg(0.0)   , 1.0 , 0.5)
+///   green      = hsl(Angle::deg(120.0) , 1.0 , 0.5)
+///   blue       = hsl(Angle::deg(240.0) , 1.0 , 0.5)
+///   pastel_red = hsl(Angle::deg(0.0)   , 0.7 , 0.7)
+///
+/// To cycle through all colors, just cycle through degrees. The saturation level is how vibrant
+/// the color is, like a dial between grey and bright colors. The lightness level is a dial between
+/// white and black.
+#[inline]
+pub fn hsl<A: Into<Angle>>(hue: A, saturation: f32, lightness: f32) -> Color {
+    hsla(hue, saturation, lightness, 1.0)
+}
+
+
+/// Produce a gray based on the input. 0.0 is white, 1.0 is black.
+pub fn grayscale(p: f32) -> Color {
+    Color::Hsla(0.0, 0.0, 1.0-p, 1.0)
+}
+/// Produce a gray based on the input. 0.0 is white, 1.0 is black.
+pub fn greyscale(p: f32) -> Color {
+    Color::Hsla(0.0, 0.0, 1.0-p, 1.0)
+}
+
+
+/// Construct a random color.
+///
+/// See `random_with` for colors that are reproducible between runs.
+pub fn random() -> Color {
+    random_with(&mut ::rand::thread_rng())
+}
+
+
+/// Construct a random color from the given random number generator, e.g. one from
+/// `utils::seeded_rng` so that the same colors are produced on every run.
+pub fn random_with<R: Rng>(rng: &mut R) -> Color {
+    rgb(rng.gen(), rng.gen(), rng.gen())
+}
+
+
+impl Color {
+
+    /// Produce a complementary color. The two colors will accent each other. This is the same as
+    /// rotating the hue by 180 degrees.
+    pub fn complement(self) -> Color {
+        match self {
+            Color::Hsla(h, s, l, a) => hsla(h + degrees(180.0), s, l, a),
+            Color::Rgba(r, g, b, a) => {
+                let (h, s, l) = rgb_to_hsl(r, g, b);
+                hsla(h + degrees(180.0), s, l, a)
+            },
+        }
+    }
+
+    /// Calculate and return the luminance of the Color.
+    pub fn luminance(&self) -> f32 {
+        match *self {
+            Color::Rgba(r, g, b, _) => (r + g + b) / 3.0,
+            Color::Hsla(_, _, l, _) => l,
+        }
+    }
+
+    /// Return either black or white, depending which contrasts the Color the most. This will be
+    /// useful for determining a readable color for text on any given background Color.
+    pub fn plain_contrast(self) -> Color {
+        if self.luminance() > 0.5 { black() } else { white() }
+    }
+
+    /// Mix the color with another by the given amount within HSL, travelling around the color
+    /// wheel in the given direction. An amount of `0.0` gives this color and `1.0` the other.
+    ///
+    /// Grays have no hue, so when mixing a gray with some other color only the other color's hue
+    /// is used.
+    pub fn mix_hsl(self, other: Color, amount: f32, direction: HueDirection) -> Color {
+        let (Hsla(h, s, l, a), Hsla(other_h, other_s, other_l, other_a)) =
+            (self.to_hsl(), other.to_hsl());
+        let h = if s == 0.0 { other_h } else { h };
+        let hue = h + self.hue_delta(other, direction) * amount;
+        let mix = |a: f32, b: f32| a + (b - a) * amount;
+        hsla(hue, mix(s, other_s), mix(l, other_l), mix(a, other_a))
+    }
+
+    /// The change in hue from this color to another when travelling around the color wheel in the
+    /// given direction.
+    fn hue_delta(self, other: Color, direction: HueDirection) -> f32 {
+        let (Hsla(h, s, _, _), Hsla(other_h, other_s, _, _)) = (self.to_hsl(), other.to_hsl());
+        if s == 0.0 || other_s == 0.0 {
+            return 0.0;
+        }
+        let delta = other_h - h;
+        let turn = turns(1.0);
+        match direction {
+            HueDirection::Shorter if delta > PI => delta - turn,
+            HueDirection::Shorter if delta < -PI => delta + turn,
+            HueDirection::Longer if delta > 0.0 && delta < PI => delta - turn,
+            HueDirection::Longer if delta <= 0.0 && delta > -PI => delta + turn,
+            HueDirection::Clockwise if delta < 0.0 => delta + turn,
+            HueDirection::CounterClockwise if delta > 0.0 => delta - turn,
+            _ => delta,
+        }
+    }
+
+    /// Extract the components of a color in the HSL format.
+    pub fn to_hsl(self) -> Hsla {
+        match self {
+            Color::Hsla(h, s, l, a) => Hsla(h, s, l, a),
+            Color::Rgba(r, g, b, a) => {
+                let (h, s, l) = rgb_to_hsl(r, g, b);
+                Hsla(h, s, l, a)
+            },
+        }
+    }
+
+    /// Extract the components of a color in the RGB format.
+    pub fn to_rgb(self) -> Rgba {
+        match self {
+            Color::Rgba(r, g, b, a) => Rgba(r, g, b, a),
+            Color::Hsla(h, s, l, a) => {
+                let (r, g, b) = hsl_to_rgb(h, s, l);
+                Rgba(r, g, b, a)
+            },
+        }
+    }
+
+    /// Extract the components of a color in the RGB format within a fixed-size array.
+    pub fn to_fsa(self) -> [f32; 4] {
+        let Rgba(r, g, b, a) = self.to_rgb();
+        [r, g, b, a]
+    }
+
+    /// Same as `to_fsa`, except r, g, b and a are represented in byte form.
+    pub fn to_byte_fsa(self) -> [u8; 4] {
+        let Rgba(r, g, b, a) = self.to_rgb();
+        [f32_to_byte(r), f32_to_byte(g), f32_to_byte(b), f32_to_byte(a)]
+    }
+
+    /// Return the hex representation of this color in the format #RRGGBBAA
+    /// e.g. `Color(1.0, 0.0, 5.0, 1.0) == "#FF0080FF"`
+    pub fn to_hex(self) -> String {
+        let [r, g, b, a] = self.to_byte_fsa();
+        format!("#{:02X}{:02X}{:02X}{:02X}", r, g, b, a)
+    }
+
+    /// Return the same color but with the given luminance.
+    pub fn with_luminance(self, l: f32) -> Color {
+        let Hsla(h, s, _, a) = self.to_hsl();
+        Color::Hsla(h, s, l, a)
+    }
+
+    /// Return the same color but with the alpha multiplied by the given alpha.
+    pub fn alpha(self, alpha: f32) -> Color {
+        match self {
+            Color::Rgba(r, g, b, a) => Color::Rgba(r, g, b, a * alpha),
+            Color::Hsla(h, s, l, a) => Color::Hsla(h, s, l, a * alpha),
+        }
+    }
+
+    /// Return the same color but with the given alpha.
+    pub fn with_alpha(self, a: f32) -> Color {
+        match self {
+            Color::Rgba(r, g, b, _) => Color::Rgba(r, g, b, a),
+            Color::Hsla(h, s, l, _) => Color::Hsla(h, s, l, a),
+        }
+    }
+
+    /// Return a highlighted version of the current Color.
+    pub fn highlighted(self) -> Color {
+        let luminance = self.luminance();
+        let Rgba(r, g, b, a) = self.to_rgb();
+        let (r, g, b) = {
+            if      luminance > 0.8 { (r - 0.2, g - 0.2, b - 0.2) }
+            else if luminance < 0.2 { (r + 0.2, g + 0.2, b + 0.2) }
+            else {
+                (clampf32((1.0 - r) * 0.5 * r + r),
+                 clampf32((1.0 - g) * 0.1 * g + g),
+                 clampf32((1.0 - b) * 0.1 * b + b))
+            }
+        };
+        let a = clampf32((1.0 - a) * 0.5 + a);
+        rgba(r, g, b, a)
+    }
+
+    /// Return a clicked version of the current Color.
+    pub fn clicked(&self) -> Color {
+        let luminance = self.luminance();
+        let Rgba(r, g, b, a) = self.to_rgb();
+        let (r, g, b) = {
+            if      luminance > 0.8 { (r      , g - 0.2, b - 0.2) }
+            else if luminance < 0.2 { (r + 0.4, g + 0.2, b + 0.2) }
+            else {
+                (clampf32((1.0 - r) * 0.75 + r),
+                 clampf32((1.0 - g) * 0.25 + g),
+                 clampf32((1.0 - b) * 0.25 + b))
+            }
+        };
+        let a = clampf32((1.0 - a) * 0.75 + a);
+        rgba(r, g, b, a)
+    }
+
+    /// Mix the color with white by the given amount between `0.0` (the color itself) and `1.0`
+    /// (white), keeping its alpha. Useful for deriving lighter states such as hover from a base
+    /// color.
+    pub fn tint(self, amount: f32) -> Color {
+        let amount = clampf32(amount);
+        let Rgba(r, g, b, a) = self.to_rgb();
+        let tint = |c: f32| c + (1.0 - c) * amount;
+        rgba(tint(r), tint(g), tint(b), a)
+    }
+
+    /// Mix the color with black by the given amount between `0.0` (the color itself) and `1.0`
+    /// (black), keeping its alpha. Useful for deriving darker states such as pressed from a base
+    /// color.
+    pub fn shade(self, amount: f32) -> Color {
+        let amount = clampf32(amount);
+        let Rgba(r, g, b, a) = self.to_rgb();
+        let shade = |c: f32| c * (1.0 - amount);
+        rgba(shade(r), shade(g), shade(b), a)
+    }
+
+    /// Shift the color towards a warmer temperature by the given amount between `0.0` (the color
+    /// itself) and `1.0`, raising its red towards full and lowering its blue towards none.
+    pub fn warmer(self, amount: f32) -> Color {
+        let amount = clampf32(amount);
+        let Rgba(r, g, b, a) = self.to_rgb();
+        rgba(r + (1.0 - r) * amount, g, b * (1.0 - amount), a)
+    }
+
+    /// Shift the color towards a cooler temperature by the given amount between `0.0` (the color
+    /// itself) and `1.0`, raising its blue towards full and lowering its red towards none.
+    pub fn cooler(self, amount: f32) -> Color {
+        let amount = clampf32(amount);
+        let Rgba(r, g, b, a) = self.to_rgb();
+        rgba(r * (1.0 - amount), g, b + (1.0 - b) * amount, a)
+    }
+
+    /// Composite the color over the given background color with the "source-over" operator,
+    /// returning the color of the result as it would be drawn.
+    ///
+    /// This may be used to precompute the flattened color of a stack of translucent colors, e.g.
+    /// `top.over(middle.over(bottom))`. The result is fully transparent only if both colors are.
+    pub fn over(self, background: Color) -> Color {
+        let Rgba(r, g, b, a) = self.to_rgb();
+        let Rgba(bg_r, bg_g, bg_b, bg_a) = background.to_rgb();
+        let out_a = a + bg_a * (1.0 - a);
+        if out_a <= 0.0 {
+            return rgba(0.0, 0.0, 0.0, 0.0);
+        }
+        let over = |c: f32, bg_c: f32| (c * a + bg_c * bg_a * (1.0 - a)) / out_a;
+        rgba(over(r, bg_r), over(g, bg_g), over(b, bg_b), out_a)
+    }
+
+    /// Composite the color over the given background color as `over` does, but blending within
+    /// linear RGB rather than sRGB.
+    ///
+    /// Blending within sRGB darkens the overlap of translucent bright colors, as sRGB values
+    /// aren't proportional to the light they describe. Blending within linear RGB does not.
+    pub fn over_linear(self, background: Color) -> Color {
+        self.to_linear().over(background.to_linear()).to_srgb()
+    }
+
+    /// Mix the color with another by the given amount within linear RGB. An amount of `0.0`
+    /// gives this color and `1.0` the other.
+    ///
+    /// The midpoint between two bright colors is brighter than when mixed within sRGB, avoiding
+    /// the dark band through the middle of gradients such as red to green.
+    pub fn mix_linear(self, other: Color, amount: f32) -> Color {
+        let Rgba(r, g, b, a) = self.to_linear().to_rgb();
+        let Rgba(other_r, other_g, other_b, other_a) = other.to_linear().to_rgb();
+        let mix = |a: f32, b: f32| a + (b - a) * amount;
+        rgba(mix(r, other_r), mix(g, other_g), mix(b, other_b), mix(a, other_a)).to_srgb()
+    }
+
+    /// Convert the color's sRGB encoded channels to linear RGB. The alpha is unchanged.
+    pub fn to_linear(self) -> Color {
+        let Rgba(r, g, b, a) = self.to_rgb();
+        rgba(srgb_to_linear(r), srgb_to_linear(g), srgb_to_linear(b), a)
+    }
+
+    /// Convert the color's linear RGB channels to the sRGB encoding. The alpha is unchanged.
+    pub fn to_srgb(self) -> Color {
+        let Rgba(r, g, b, a) = self.to_rgb();
+        rgba(linear_to_srgb(r), linear_to_srgb(g), linear_to_srgb(b), a)
+    }
+
+    /// Return the Color's invert.
+    pub fn invert(self) -> Color {
+        let Rgba(r, g, b, a) = self.to_rgb();
+        rgba((r - 1.0).abs(), (g - 1.0).abs(), (b - 1.0).abs(), a)
+    }
+
+    /// Return the red value.
+    pub fn red(&self) -> f32 {
+        let Rgba(r, _, _, _) = self.to_rgb();
+        r
+    }
+
+    /// Return the green value.
+    pub fn green(&self) -> f32 {
+        let Rgba(_, g, _, _) = self.to_rgb();
+        g
+    }
+
+    /// Return the blue value.
+    pub fn blue(&self) -> f32 {
+        let Rgba(_, _, b, _) = self.to_rgb();
+        b
+    }
+
+    /// Set the red value.
+    pub fn set_red(&mut self, r: f32) {
+        let Rgba(_, g, b, a) = self.to_rgb();
+        *self = rgba(r, g, b, a);
+    }
+
+    /// Set the green value.
+    pub fn set_green(&mut self, g: f32) {
+        let Rgba(r, _, b, a) = self.to_rgb();
+        *self = rgba(r, g, b, a);
+    }
+
+    /// Set the blue value.
+    pub fn set_blue(&mut self, b: f32) {
+        let Rgba(r, g, _, a) = self.to_rgb();
+        *self = rgba(r, g, b, a);
+    }
+
+}
+
+
+/// Formats the color in CSS syntax.
+///
+/// `Rgba` colors are written as `#rrggbb` when opaque and as `rgba(r, g, b, a)` otherwise, while
+/// `Hsla` colors are written as `hsl(h, s%, l%)` or `hsla(h, s%, l%, a)`. The result may be
+/// parsed back into a `Color` with `str::parse`.
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let byte = |c: f32| (clampf32(c) * 255.0).round() as u8;
+        match *self {
+            Color::Rgba(r, g, b, a) if a >= 1.0 =>
+                write!(f, "#{:02x}{:02x}{:02x}", byte(r), byte(g), byte(b)),
+            Color::Rgba(r, g, b, a) =>
+                write!(f, "rgba({}, {}, {}, {})", byte(r), byte(g), byte(b), a),
+            Color::Hsla(h, s, l, a) if a >= 1.0 =>
+                write!(f, "hsl({}, {}%, {}%)", h.to_degrees(), s * 100.0, l * 100.0),
+            Color::Hsla(h, s, l, a) =>
+                write!(f, "hsla({}, {}%, {}%, {})", h.to_degrees(), s * 100.0, l * 100.0, a),
+        }
+    }
+}
+
+
+/// Parses a color from CSS syntax.
+///
+/// Accepts `#rgb`, `#rgba`, `#rrggbb` and `#rrggbbaa` hex colors along with the `rgb()`,
+/// `rgba()`, `hsl()` and `hsla()` functions. Arguments may be separated by commas or by
+/// whitespace with the alpha following a `/`, e.g. `rgb(255 128 0 / 50%)`. Hues are in degrees
+/// unless given in `rad` or `turn`.
+impl FromStr for Color {
+    type Err = ParseColorError;
+    fn from_str(s: &str) -> Result<Color, ParseColorError> {
+        let s = s.trim();
+        if let Some(digits) = s.strip_prefix('#') {
+            return parse_hex(digits);
+        }
+        let open = s.find('(').ok_or(ParseColorError::UnknownSyntax)?;
+        if !s.ends_with(')') {
+            return Err(ParseColorError::UnknownSyntax);
+        }
+        let name = s[..open].trim().to_lowercase();
+        let args = s[open + 1..s.len() - 1].replace([',', '/'], " ");
+        let args: Vec<&str> = args.split_whitespace().collect();
+        if args.len() != 3 && args.len() != 4 {
+            return Err(ParseColorError::InvalidArgument);
+        }
+        let alpha = match args.get(3) {
+            Some(arg) => parse_fraction(arg, 1.0)?,
+            None => 1.0,
+        };
+        match &name[..] {
+            "rgb" | "rgba" => Ok(rgba(parse_fraction(args[0], 255.0)?,
+                                      parse_fraction(args[1], 255.0)?,
+                                      parse_fraction(args[2], 255.0)?,
+                                      alpha)),
+            "hsl" | "hsla" => Ok(hsla(parse_hue(args[0])?,
+                                      parse_fraction(args[1], 100.0)?,
+                                      parse_fraction(args[2], 100.0)?,
+                                      alpha)),
+            _ => Err(ParseColorError::UnknownSyntax),
+        }
+    }
+}
+
+
+/// Parse the digits of a hex color following the `#`.
+fn parse_hex(digits: &str) -> Result<Color, ParseColorError> {
+    let nibbles = digits.chars()
+        .map(|c| c.to_digit(16).map(|d| d as u8))
+        .collect::<Option<Vec<u8>>>()
+        .ok_or(ParseColorError::InvalidHex)?;
+    let bytes: Vec<u8> = match nibbles.len() {
+        3 | 4 => nibbles.iter().map(|&n| n * 17).collect(),
+        6 | 8 => nibbles.chunks(2).map(|pair| pair[0] * 16 + pair[1]).collect(),
+        _ => return Err(ParseColorError::InvalidHex),
+    };
+    let alpha = bytes.get(3).map(|&a| a as f32 / 255.0).unwrap_or(1.0);
+    Ok(rgba_bytes(bytes[0], bytes[1], bytes[2], alpha))
+}
+
+
+/// Parse a color function argument as a fraction, where a plain number is out of the given
+/// maximum and a percentage is out of `100%`.
+fn parse_fraction(arg: &str, max: f32) -> Result<f32, ParseColorError> {
+    let (number, max) = match arg.strip_suffix('%') {
+        Some(number) => (number, 100.0),
+        None => (arg, max),
+    };
+    number.parse::<f32>().map(|n| clampf32(n / max)).map_err(|_| ParseColorError::InvalidArgument)
+}
+
+
+/// Parse a hue in degrees, `rad` or `turn`, returning it in radians.
+fn parse_hue(arg: &str) -> Result<f32, ParseColorError> {
+    let parse = |number: &str| number.parse::<f32>().map_err(|_| ParseColorError::InvalidArgument);
+    if let Some(number) = arg.strip_suffix("deg") {
+        parse(number).map(degrees)
+    } else if let Some(number) = arg.strip_suffix("rad") {
+        parse(number)
+    } else if let Some(number) = arg.strip_suffix("turn") {
+        parse(number).map(turns)
+    } else {
+        parse(arg).map(degrees)
+    }
+}
+
+
+/// The reasons for which a `Color` may fail to parse.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ParseColorError {
+    /// A `#` color didn't consist of 3, 4, 6 or 8 hex digits.
+    InvalidHex,
+    /// The color was neither a `#` color nor one of the `rgb()`, `rgba()`, `hsl()` or `hsla()`
+    /// functions.
+    UnknownSyntax,
+    /// A color function had the wrong number of arguments or an argument that wasn't a number.
+    InvalidArgument,
+}
+
+impl fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseColorError::InvalidHex => write!(f, "a hex color must have 3, 4, 6 or 8 digits"),
+            ParseColorError::UnknownSyntax =>
+                write!(f, "expected a hex color or an rgb(), rgba(), hsl() or hsla() function"),
+            ParseColorError::InvalidArgument =>
+                write!(f, "a color function has too few, too many or malformed arguments"),
+        }
+    }
+}
+
+impl error::Error for ParseColorError {
+    fn description(&self) -> &str {
+        match *self {
+            ParseColorError::InvalidHex => "invalid hex color",
+            ParseColorError::UnknownSyntax => "unknown color syntax",
+            ParseColorError::InvalidArgument => "invalid color function argument",
+        }
+    }
+}
+
+
+/// The parts of HSL along with an alpha for transparency.
+#[derive(Copy, Clone, Debug)]
+pub struct Hsla(pub f32, pub f32, pub f32, pub f32);
+
+
+/// The parts of RGB along with an alpha for transparency.
+#[derive(Copy, Clone, Debug)]
+pub struct Rgba(pub f32, pub f32, pub f32, pub f32);
+
+
+/// Convert an f32 color to a byte.
+#[inline]
+pub fn f32_to_byte(c: f32) -> u8 { (c * 255.0) as u8 }
+
+
+/// Pure function for converting rgb to hsl.
+pub fn rgb_to_hsl(r: f32, g: f32, b: f32) -> (f32, f32, f32) {
+    let c_max = max(max(r, g), b);
+    let c_min = min(min(r, g), b);
+    let c = c_max - c_min;
+
+    // If there's no difference in the channels we have grayscale, so the hue is undefined and
+    // the saturation is zero (rather than the zero by zero division it would be for black and
+    // white).
+    if c == 0.0 {
+        return (0.0, 0.0, c_max);
+    }
+
+    let sector = if      c_max == r { fmod((g - b) / c, 6.0) }
+                 else if c_max == g { ((b - r) / c) + 2.0 }
+                 else               { ((r - g) / c) + 4.0 };
+    let hue = degrees(60.0) * sector;
+
+    let lightness = (c_max + c_min) / 2.0;
+    let saturation = c / (1.0 - (2.0 * lightness - 1.0).abs());
+    (hue, saturation, lightness)
+}
+
+
+/// Pure function for converting hsl to rgb.
+pub fn hsl_to_rgb(hue: f32, saturation: f32, lightness: f32) -> (f32, f32, f32) {
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    // Hues outside of a single turn (including a whole turn, i.e. 360 degrees) wrap around.
+    let hue = wrap_angle(hue) / degrees(60.0);
+    let x = chroma * (1.0 - (fmod(hue, 2.0) - 1.0).abs());
+    let (r, g, b) = match hue {
+        hue if hue < 1.0 => (chroma, x, 0.0),
+        hue if hue < 2.0 => (x, chroma, 0.0),
+        hue if hue < 3.0 => (0.0, chroma, x),
+        hue if hue < 4.0 => (0.0, x, chroma),
+        hue if hue < 5.0 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = lightness - chroma / 2.0;
+    (r + m, g + m, b + m)
+}
+
+
+/// Pure function for converting an sRGB encoded channel to linear RGB.
+pub fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+}
+
+
+/// Pure function for converting a linear RGB channel to the sRGB encoding.
+pub fn linear_to_srgb(c: f32) -> f32 {
+    if c <= 0.0031308 { c * 12.92 } else { 1.055 * c.powf(1.0 / 2.4) - 0.055 }
+}
+
+
+/// Linear or Radial Gradient.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Gradient {
+    /// Takes a start and end point and then a series of color stops that indicate how to
+    /// interpolate between the start and end points.
+    Linear((f64, f64), (f64, f64), Vec<(f64, Color)>),
+    /// First takes a start point and inner radius. Then takes an end point and outer radius.
+    /// It then takes a series of color stops that indicate how to interpolate between the
+    /// inner and outer circles.
+    Radial((f64, f64), f64, (f64, f64), f64, Vec<(f64, Color)>),
+}
+
+impl Eq for Gradient {}
+
+impl Hash for Gradient {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let (floats, colors) = match *self {
+            Gradient::Linear(start, end, ref colors) => {
+                0u8.hash(state);
+                (vec![start.0, start.1, end.0, end.1], colors)
+            },
+            Gradient::Radial(start, start_r, end, end_r, ref colors) => {
+                1u8.hash(state);
+                (vec![start.0, start.1, start_r, end.0, end.1, end_r], colors)
+            },
+        };
+        for &f in floats.iter() {
+            hash_f64(f, state);
+        }
+        colors.len().hash(state);
+        for &(t, color) in colors.iter() {
+            hash_f64(t, state);
+            color.hash(state);
+        }
+    }
+}
+
+
+impl Gradient {
+
+    /// The gradient's color stops.
+    pub fn stops(&self) -> &[(f64, Color)] {
+        match *self {
+            Gradient::Linear(_, _, ref colors) | Gradient::Radial(_, _, _, _, ref colors) => colors,
+        }
+    }
+
+    /// Interpolate between the gradient's color stops in HSL rather than RGB, travelling around
+    /// the color wheel in the given direction.
+    ///
+    /// Renderers only interpolate in RGB, so this is achieved by adding stops between each pair
+    /// of stops at least every `HUE_STEP` radians of hue. Interpolating between red and blue in
+    /// RGB passes through a dull purple, whereas in HSL it sweeps smoothly through magenta (or
+    /// through green, in the other direction).
+    pub fn in_hsl(self, direction: HueDirection) -> Gradient {
+        self.subdivided(|prev, next| {
+            let hue_delta = prev.hue_delta(next, direction);
+            ((hue_delta.abs() / HUE_STEP).ceil() as usize).max(1)
+        }, |prev, next, amount| prev.mix_hsl(next, amount, direction))
+    }
+
+    /// Interpolate between the gradient's color stops in linear RGB rather than sRGB.
+    ///
+    /// Renderers interpolate sRGB values directly, so this is achieved by adding `LINEAR_STEPS`
+    /// stops between each pair of stops. Interpolating between two bright colors in sRGB passes
+    /// through a darker band, whereas in linear RGB the light blends evenly.
+    pub fn in_linear_rgb(self) -> Gradient {
+        self.subdivided(|_, _| LINEAR_STEPS, |prev, next, amount| prev.mix_linear(next, amount))
+    }
+
+    /// Add stops between each pair of the gradient's stops. `steps` gives the number of
+    /// segments between a pair of colors and `mix` the color at some amount between them.
+    fn subdivided<S, M>(self, steps: S, mix: M) -> Gradient
+        where
+            S: Fn(Color, Color) -> usize,
+            M: Fn(Color, Color, f32) -> Color,
+    {
+        let subdivide = |colors: Vec<(f64, Color)>| {
+            let mut stops = Vec::with_capacity(colors.len());
+            for (i, &(t, color)) in colors.iter().enumerate() {
+                if let Some(&(prev_t, prev)) = i.checked_sub(1).and_then(|i| colors.get(i)) {
+                    let steps = steps(prev, color);
+                    for step in 1..steps {
+                        let amount = step as f32 / steps as f32;
+                        let stop_t = prev_t + (t - prev_t) * amount as f64;
+                        stops.push((stop_t, mix(prev, color, amount)));
+                    }
+                }
+                stops.push((t, color));
+            }
+            stops
+        };
+        match self {
+            Gradient::Linear(start, end, colors) => Gradient::Linear(start, end, subdivide(colors)),
+            Gradient::Radial(start, start_r, end, end_r, colors) =>
+                Gradient::Radial(start, start_r, end, end_r, subdivide(colors)),
+        }
+    }
+
+}
+
+
+/// The greatest difference in hue between consecutive stops of a gradient interpolated in HSL.
+pub const HUE_STEP: f32 = PI / 18.0;
+
+
+/// The number of segments between each pair of stops of a gradient interpolated in linear RGB.
+pub const LINEAR_STEPS: usize = 8;
+
+
+/// The way around the color wheel in which hues are interpolated. See `Gradient::in_hsl`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum HueDirection {
+    /// Whichever way around the wheel is shorter.
+    Shorter,
+    /// Whichever way around the wheel is longer.
+    Longer,
+    /// Towards increasing hues, i.e. from red through yellow to green.
+    Clockwise,
+    /// Towards decreasing hues, i.e. from red through magenta to blue.
+    CounterClockwise,
+}
+
+
+/// Create a linear gradient.
+pub fn linear(start: (f64, f64), end: (f64, f64), colors: Vec<(f64, Color)>) -> Gradient {
+    Gradient::Linear(start, end, colors)
+}
+
+
+/// Create a linear gradient of the given length running through the origin in the direction of
+/// the given `Angle` (or radians), e.g. `linear_angled(Angle::deg(90.0), 100.0, stops)` for a
+/// gradient running up a 100 unit tall shape.
+pub fn linear_angled<A: Into<Angle>>(angle: A, length: f64, colors: Vec<(f64, Color)>)
+    -> Gradient
+{
+    let angle = angle.into().radians();
+    let (dx, dy) = (angle.cos() * length / 2.0, angle.sin() * length / 2.0);
+    Gradient::Linear((-dx, -dy), (dx, dy), colors)
+}
+
+
+/// Create a radial gradient. 
+pub fn radial(start: (f64, f64), start_r: f64,
+              end: (f64, f64), end_r: f64,
+              colors: Vec<(f64, Color)>) -> Gradient {
+    Gradient::Radial(start, start_r, end, end_r, colors)
+}
+
+
+/// Built-in colors.
+///
+/// These colors come from the
+/// [Tango palette](http://tango.freedesktop.org/Tango_Icon_Theme_Guidelines) which provides
+/// aesthetically reasonable defaults for colors. Each color also comes with a light and dark
+/// version.
+///
+/// Scarlet Red - Light - #EF2929
+pub fn light_red()      -> Color { rgb_bytes(239 , 41  , 41 ) }
+/// Scarlet Red - Regular - #CC0000
+pub fn red()            -> Color { rgb_bytes(204 , 0   , 0  ) }
+/// Scarlet Red - Dark - #A30000
+pub fn dark_red()       -> Color { rgb_bytes(164 , 0   , 0  ) }
+
+/// Orange - Light - #FCAF3E
+pub fn light_orange()   -> Color { rgb_bytes(252 , 175 , 62 ) }
+/// Orange - Regular - #F57900
+pub fn orange()         -> Color { rgb_bytes(245 , 121 , 0  ) }
+/// Orange - Dark - #CE5C00
+pub fn dark_orange()    -> Color { rgb_bytes(206 , 92  , 0  ) }
+
+/// Butter - Light - #FCE94F
+pub fn light_yellow()   -> Color { rgb_bytes(255 , 233 , 79 ) }
+/// Butter - Regular - #EDD400
+pub fn yellow()         -> Color { rgb_bytes(237 , 212 , 0  ) }
+/// Butter - Dark - #C4A000
+pub fn dark_yellow()    -> Color { rgb_bytes(196 , 160 , 0  ) }
+
+/// Chameleon - Light - #8AE234
+pub fn light_green()    -> Color { rgb_bytes(138 , 226 , 52 ) }
+/// Chameleon - Regular - #73D216
+pub fn green()          -> Color { rgb_bytes(115 , 210 , 22 ) }
+/// Chameleon - Dark - #4E9A06
+pub fn dark_green()     -> Color { rgb_bytes(78  , 154 , 6  ) }
+
+/// Sky Blue - Light - #729FCF
+pub fn light_blue()     -> Color { rgb_bytes(114 , 159 , 207) }
+/// Sky Blue - Regular - #3465A4
+pub fn blue()           -> Color { rgb_bytes(52  , 101 , 164) }
+/// Sky Blue - Dark - #204A87
+pub fn dark_blue()      -> Color { rgb_bytes(32  , 74  , 135) }
+
+/// Plum - Light - #AD7FA8
+pub fn light_purple()   -> Color { rgb_bytes(173 , 127 , 168) }
+/// Plum - Regular - #75507B
+pub fn purple()         -> Color { rgb_bytes(117 , 80  , 123) }
+/// Plum - Dark - #5C3566
+pub fn dark_purple()    -> Color { rgb_bytes(92  , 53  , 102) }
+
+/// Chocolate - Light - #E9B96E
+pub fn light_brown()    -> Color { rgb_bytes(233 , 185 , 110) }
+/// Chocolate - Regular - #C17D11
+pub fn brown()          -> Color { rgb_bytes(193 , 125 , 17 ) }
+/// Chocolate - Dark - #8F5902
+pub fn dark_brown()     -> Color { rgb_bytes(143 , 89  , 2  ) }
+
+/// Straight Black.
+pub fn black()          -> Color { rgb_bytes(0   , 0   , 0  ) }
+/// Straight White.
+pub fn white()          -> Color { rgb_bytes(255 , 255 , 255) }
+
+/// Alluminium - Light
+pub fn light_gray()     -> Color { rgb_bytes(238 , 238 , 236) }
+/// Alluminium - Regular
+pub fn gray()           -> Color { rgb_bytes(211 , 215 , 207) }
+/// Alluminium - Dark
+pub fn dark_gray()      -> Color { rgb_bytes(186 , 189 , 182) }
+
+/// Aluminium - Light - #EEEEEC
+pub fn light_grey()     -> Color { rgb_bytes(238 , 238 , 236) }
+/// Aluminium - Regular - #D3D7CF
+pub fn grey()           -> Color { rgb_bytes(211 , 215 , 207) }
+/// Aluminium - Dark - #BABDB6
+pub fn dark_grey()      -> Color { rgb_bytes(186 , 189 , 182) }
+
+/// Charcoal - Light - #888A85
+pub fn light_charcoal() -> Color { rgb_bytes(136 , 138 , 133) }
+/// Charcoal - Regular - #555753
+pub fn charcoal()       -> Color { rgb_bytes(85  , 87  , 83 ) }
+/// Charcoal - Dark - #2E3436
+pub fn dark_charcoal()  -> Color { rgb_bytes(46  , 52  , 54 ) }
+
+
+
+/// Types that can be colored.
+pub trait Colorable: Sized {
+
+    /// Set the color of the widget.
+    fn color(self, color: Color) -> Self;
+
+    /// Set the color of the widget from rgba values.
+    fn rgba(self, r: f32, g: f32, b: f32, a: f32) -> Self {
+        self.color(rgba(r, g, b, a))
+    }
+
+    /// Set the color of the widget from rgb values.
+    fn rgb(self, r: f32, g: f32, b: f32) -> Self {
+        self.color(rgb(r, g, b))
+    }
+
+    /// Set the color of the widget from hsla values.
+    fn hsla<A: Into<Angle>>(self, h: A, s: f32, l: f32, a: f32) -> Self {
+        self.color(hsla(h, s, l, a))
+    }
+
+    /// Set the color of the widget from hsl values.
+    fn hsl<A: Into<Angle>>(self, h: A, s: f32, l: f32) -> Self {
+        self.color(hsl(h, s, l))
+    }
+
+}
+
@@ -0,0 +1,515 @@
+//!
+//! The default color palette of [Tailwind CSS](https://tailwindcss.com/docs/customizing-colors)
+//! (version 3), with the full range of shades of each hue.
+//!
+//! Shades range from `50` (the lightest) to `950` (the darkest), e.g. `sky_500`.
+//!
+
+use super::{rgb_bytes, Color};
+
+
+/// Slate 50 - #F8FAFC
+pub fn slate_50() -> Color { rgb_bytes(248, 250, 252) }
+/// Slate 100 - #F1F5F9
+pub fn slate_100() -> Color { rgb_bytes(241, 245, 249) }
+/// Slate 200 - #E2E8F0
+pub fn slate_200() -> Color { rgb_bytes(226, 232, 240) }
+/// Slate 300 - #CBD5E1
+pub fn slate_300() -> Color { rgb_bytes(203, 213, 225) }
+/// Slate 400 - #94A3B8
+pub fn slate_400() -> Color { rgb_bytes(148, 163, 184) }
+/// Slate 500 - #64748B
+pub fn slate_500() -> Color { rgb_bytes(100, 116, 139) }
+/// Slate 600 - #475569
+pub fn slate_600() -> Color { rgb_bytes(71, 85, 105) }
+/// Slate 700 - #334155
+pub fn slate_700() -> Color { rgb_bytes(51, 65, 85) }
+/// Slate 800 - #1E293B
+pub fn slate_800() -> Color { rgb_bytes(30, 41, 59) }
+/// Slate 900 - #0F172A
+pub fn slate_900() -> Color { rgb_bytes(15, 23, 42) }
+/// Slate 950 - #020617
+pub fn slate_950() -> Color { rgb_bytes(2, 6, 23) }
+
+/// Gray 50 - #F9FAFB
+pub fn gray_50() -> Color { rgb_bytes(249, 250, 251) }
+/// Gray 100 - #F3F4F6
+pub fn gray_100() -> Color { rgb_bytes(243, 244, 246) }
+/// Gray 200 - #E5E7EB
+pub fn gray_200() -> Color { rgb_bytes(229, 231, 235) }
+/// Gray 300 - #D1D5DB
+pub fn gray_300() -> Color { rgb_bytes(209, 213, 219) }
+/// Gray 400 - #9CA3AF
+pub fn gray_400() -> Color { rgb_bytes(156, 163, 175) }
+/// Gray 500 - #6B7280
+pub fn gray_500() -> Color { rgb_bytes(107, 114, 128) }
+/// Gray 600 - #4B5563
+pub fn gray_600() -> Color { rgb_bytes(75, 85, 99) }
+/// Gray 700 - #374151
+pub fn gray_700() -> Color { rgb_bytes(55, 65, 81) }
+/// Gray 800 - #1F2937
+pub fn gray_800() -> Color { rgb_bytes(31, 41, 55) }
+/// Gray 900 - #111827
+pub fn gray_900() -> Color { rgb_bytes(17, 24, 39) }
+/// Gray 950 - #030712
+pub fn gray_950() -> Color { rgb_bytes(3, 7, 18) }
+
+/// Zinc 50 - #FAFAFA
+pub fn zinc_50() -> Color { rgb_bytes(250, 250, 250) }
+/// Zinc 100 - #F4F4F5
+pub fn zinc_100() -> Color { rgb_bytes(244, 244, 245) }
+/// Zinc 200 - #E4E4E7
+pub fn zinc_200() -> Color { rgb_bytes(228, 228, 231) }
+/// Zinc 300 - #D4D4D8
+pub fn zinc_300() -> Color { rgb_bytes(212, 212, 216) }
+/// Zinc 400 - #A1A1AA
+pub fn zinc_400() -> Color { rgb_bytes(161, 161, 170) }
+/// Zinc 500 - #71717A
+pub fn zinc_500() -> Color { rgb_bytes(113, 113, 122) }
+/// Zinc 600 - #52525B
+pub fn zinc_600() -> Color { rgb_bytes(82, 82, 91) }
+/// Zinc 700 - #3F3F46
+pub fn zinc_700() -> Color { rgb_bytes(63, 63, 70) }
+/// Zinc 800 - #27272A
+pub fn zinc_800() -> Color { rgb_bytes(39, 39, 42) }
+/// Zinc 900 - #18181B
+pub fn zinc_900() -> Color { rgb_bytes(24, 24, 27) }
+/// Zinc 950 - #09090B
+pub fn zinc_950() -> Color { rgb_bytes(9, 9, 11) }
+
+/// Neutral 50 - #FAFAFA
+pub fn neutral_50() -> Color { rgb_bytes(250, 250, 250) }
+/// Neutral 100 - #F5F5F5
+pub fn neutral_100() -> Color { rgb_bytes(245, 245, 245) }
+/// Neutral 200 - #E5E5E5
+pub fn neutral_200() -> Color { rgb_bytes(229, 229, 229) }
+/// Neutral 300 - #D4D4D4
+pub fn neutral_300() -> Color { rgb_bytes(212, 212, 212) }
+/// Neutral 400 - #A3A3A3
+pub fn neutral_400() -> Color { rgb_bytes(163, 163, 163) }
+/// Neutral 500 - #737373
+pub fn neutral_500() -> Color { rgb_bytes(115, 115, 115) }
+/// Neutral 600 - #525252
+pub fn neutral_600() -> Color { rgb_bytes(82, 82, 82) }
+/// Neutral 700 - #404040
+pub fn neutral_700() -> Color { rgb_bytes(64, 64, 64) }
+/// Neutral 800 - #262626
+pub fn neutral_800() -> Color { rgb_bytes(38, 38, 38) }
+/// Neutral 900 - #171717
+pub fn neutral_900() -> Color { rgb_bytes(23, 23, 23) }
+/// Neutral 950 - #0A0A0A
+pub fn neutral_950() -> Color { rgb_bytes(10, 10, 10) }
+
+/// Stone 50 - #FAFAF9
+pub fn stone_50() -> Color { rgb_bytes(250, 250, 249) }
+/// Stone 100 - #F5F5F4
+pub fn stone_100() -> Color { rgb_bytes(245, 245, 244) }
+/// Stone 200 - #E7E5E4
+pub fn stone_200() -> Color { rgb_bytes(231, 229, 228) }
+/// Stone 300 - #D6D3D1
+pub fn stone_300() -> Color { rgb_bytes(214, 211, 209) }
+/// Stone 400 - #A8A29E
+pub fn stone_400() -> Color { rgb_bytes(168, 162, 158) }
+/// Stone 500 - #78716C
+pub fn stone_500() -> Color { rgb_bytes(120, 113, 108) }
+/// Stone 600 - #57534E
+pub fn stone_600() -> Color { rgb_bytes(87, 83, 78) }
+/// Stone 700 - #44403C
+pub fn stone_700() -> Color { rgb_bytes(68, 64, 60) }
+/// Stone 800 - #292524
+pub fn stone_800() -> Color { rgb_bytes(41, 37, 36) }
+/// Stone 900 - #1C1917
+pub fn stone_900() -> Color { rgb_bytes(28, 25, 23) }
+/// Stone 950 - #0C0A09
+pub fn stone_950() -> Color { rgb_bytes(12, 10, 9) }
+
+/// Red 50 - #FEF2F2
+pub fn red_50() -> Color { rgb_bytes(254, 242, 242) }
+/// Red 100 - #FEE2E2
+pub fn red_100() -> Color { rgb_bytes(254, 226, 226) }
+/// Red 200 - #FECACA
+pub fn red_200() -> Color { rgb_bytes(254, 202, 202) }
+/// Red 300 - #FCA5A5
+pub fn red_300() -> Color { rgb_bytes(252, 165, 165) }
+/// Red 400 - #F87171
+pub fn red_400() -> Color { rgb_bytes(248, 113, 113) }
+/// Red 500 - #EF4444
+pub fn red_500() -> Color { rgb_bytes(239, 68, 68) }
+/// Red 600 - #DC2626
+pub fn red_600() -> Color { rgb_bytes(220, 38, 38) }
+/// Red 700 - #B91C1C
+pub fn red_700() -> Color { rgb_bytes(185, 28, 28) }
+/// Red 800 - #991B1B
+pub fn red_800() -> Color { rgb_bytes(153, 27, 27) }
+/// Red 900 - #7F1D1D
+pub fn red_900() -> Color { rgb_bytes(127, 29, 29) }
+/// Red 950 - #450A0A
+pub fn red_950() -> Color { rgb_bytes(69, 10, 10) }
+
+/// Orange 50 - #FFF7ED
+pub fn orange_50() -> Color { rgb_bytes(255, 247, 237) }
+/// Orange 100 - #FFEDD5
+pub fn orange_100() -> Color { rgb_bytes(255, 237, 213) }
+/// Orange 200 - #FED7AA
+pub fn orange_200() -> Color { rgb_bytes(254, 215, 170) }
+/// Orange 300 - #FDBA74
+pub fn orange_300() -> Color { rgb_bytes(253, 186, 116) }
+/// Orange 400 - #FB923C
+pub fn orange_400() -> Color { rgb_bytes(251, 146, 60) }
+/// Orange 500 - #F97316
+pub fn orange_500() -> Color { rgb_bytes(249, 115, 22) }
+/// Orange 600 - #EA580C
+pub fn orange_600() -> Color { rgb_bytes(234, 88, 12) }
+/// Orange 700 - #C2410C
+pub fn orange_700() -> Color { rgb_bytes(194, 65, 12) }
+/// Orange 800 - #9A3412
+pub fn orange_800() -> Color { rgb_bytes(154, 52, 18) }
+/// Orange 900 - #7C2D12
+pub fn orange_900() -> Color { rgb_bytes(124, 45, 18) }
+/// Orange 950 - #431407
+pub fn orange_950() -> Color { rgb_bytes(67, 20, 7) }
+
+/// Amber 50 - #FFFBEB
+pub fn amber_50() -> Color { rgb_bytes(255, 251, 235) }
+/// Amber 100 - #FEF3C7
+pub fn amber_100() -> Color { rgb_bytes(254, 243, 199) }
+/// Amber 200 - #FDE68A
+pub fn amber_200() -> Color { rgb_bytes(253, 230, 138) }
+/// Amber 300 - #FCD34D
+pub fn amber_300() -> Color { rgb_bytes(252, 211, 77) }
+/// Amber 400 - #FBBF24
+pub fn amber_400() -> Color { rgb_bytes(251, 191, 36) }
+/// Amber 500 - #F59E0B
+pub fn amber_500() -> Color { rgb_bytes(245, 158, 11) }
+/// Amber 600 - #D97706
+pub fn amber_600() -> Color { rgb_bytes(217, 119, 6) }
+/// Amber 700 - #B45309
+pub fn amber_700() -> Color { rgb_bytes(180, 83, 9) }
+/// Amber 800 - #92400E
+pub fn amber_800() -> Color { rgb_bytes(146, 64, 14) }
+/// Amber 900 - #78350F
+pub fn amber_900() -> Color { rgb_bytes(120, 53, 15) }
+/// Amber 950 - #451A03
+pub fn amber_950() -> Color { rgb_bytes(69, 26, 3) }
+
+/// Yellow 50 - #FEFCE8
+pub fn yellow_50() -> Color { rgb_bytes(254, 252, 232) }
+/// Yellow 100 - #FEF9C3
+pub fn yellow_100() -> Color { rgb_bytes(254, 249, 195) }
+/// Yellow 200 - #FEF08A
+pub fn yellow_200() -> Color { rgb_bytes(254, 240, 138) }
+/// Yellow 300 - #FDE047
+pub fn yellow_300() -> Color { rgb_bytes(253, 224, 71) }
+/// Yellow 400 - #FACC15
+pub fn yellow_400() -> Color { rgb_bytes(250, 204, 21) }
+/// Yellow 500 - #EAB308
+pub fn yellow_500() -> Color { rgb_bytes(234, 179, 8) }
+/// Yellow 600 - #CA8A04
+pub fn yellow_600() -> Color { rgb_bytes(202, 138, 4) }
+/// Yellow 700 - #A16207
+pub fn yellow_700() -> Color { rgb_bytes(161, 98, 7) }
+/// Yellow 800 - #854D0E
+pub fn yellow_800() -> Color { rgb_bytes(133, 77, 14) }
+/// Yellow 900 - #713F12
+pub fn yellow_900() -> Color { rgb_bytes(113, 63, 18) }
+/// Yellow 950 - #422006
+pub fn yellow_950() -> Color { rgb_bytes(66, 32, 6) }
+
+/// Lime 50 - #F7FEE7
+pub fn lime_50() -> Color { rgb_bytes(247, 254, 231) }
+/// Lime 100 - #ECFCCB
+pub fn lime_100() -> Color { rgb_bytes(236, 252, 203) }
+/// Lime 200 - #D9F99D
+pub fn lime_200() -> Color { rgb_bytes(217, 249, 157) }
+/// Lime 300 - #BEF264
+pub fn lime_300() -> Color { rgb_bytes(190, 242, 100) }
+/// Lime 400 - #A3E635
+pub fn lime_400() -> Color { rgb_bytes(163, 230, 53) }
+/// Lime 500 - #84CC16
+pub fn lime_500() -> Color { rgb_bytes(132, 204, 22) }
+/// Lime 600 - #65A30D
+pub fn lime_600() -> Color { rgb_bytes(101, 163, 13) }
+/// Lime 700 - #4D7C0F
+pub fn lime_700() -> Color { rgb_bytes(77, 124, 15) }
+/// Lime 800 - #3F6212
+pub fn lime_800() -> Color { rgb_bytes(63, 98, 18) }
+/// Lime 900 - #365314
+pub fn lime_900() -> Color { rgb_bytes(54, 83, 20) }
+/// Lime 950 - #1A2E05
+pub fn lime_950() -> Color { rgb_bytes(26, 46, 5) }
+
+/// Green 50 - #F0FDF4
+pub fn green_50() -> Color { rgb_bytes(240, 253, 244) }
+/// Green 100 - #DCFCE7
+pub fn green_100() -> Color { rgb_bytes(220, 252, 231) }
+/// Green 200 - #BBF7D0
+pub fn green_200() -> Color { rgb_bytes(187, 247, 208) }
+/// Green 300 - #86EFAC
+pub fn green_300() -> Color { rgb_bytes(134, 239, 172) }
+/// Green 400 - #4ADE80
+pub fn green_400() -> Color { rgb_bytes(74, 222, 128) }
+/// Green 500 - #22C55E
+pub fn green_500() -> Color { rgb_bytes(34, 197, 94) }
+/// Green 600 - #16A34A
+pub fn green_600() -> Color { rgb_bytes(22, 163, 74) }
+/// Green 700 - #15803D
+pub fn green_700() -> Color { rgb_bytes(21, 128, 61) }
+/// Green 800 - #166534
+pub fn green_800() -> Color { rgb_bytes(22, 101, 52) }
+/// Green 900 - #14532D
+pub fn green_900() -> Color { rgb_bytes(20, 83, 45) }
+/// Green 950 - #052E16
+pub fn green_950() -> Color { rgb_bytes(5, 46, 22) }
+
+/// Emerald 50 - #ECFDF5
+pub fn emerald_50() -> Color { rgb_bytes(236, 253, 245) }
+/// Emerald 100 - #D1FAE5
+pub fn emerald_100() -> Color { rgb_bytes(209, 250, 229) }
+/// Emerald 200 - #A7F3D0
+pub fn emerald_200() -> Color { rgb_bytes(167, 243, 208) }
+/// Emerald 300 - #6EE7B7
+pub fn emerald_300() -> Color { rgb_bytes(110, 231, 183) }
+/// Emerald 400 - #34D399
+pub fn emerald_400() -> Color { rgb_bytes(52, 211, 153) }
+/// Emerald 500 - #10B981
+pub fn emerald_500() -> Color { rgb_bytes(16, 185, 129) }
+/// Emerald 600 - #059669
+pub fn emerald_600() -> Color { rgb_bytes(5, 150, 105) }
+/// Emerald 700 - #047857
+pub fn emerald_700() -> Color { rgb_bytes(4, 120, 87) }
+/// Emerald 800 - #065F46
+pub fn emerald_800() -> Color { rgb_bytes(6, 95, 70) }
+/// Emerald 900 - #064E3B
+pub fn emerald_900() -> Color { rgb_bytes(6, 78, 59) }
+/// Emerald 950 - #022C22
+pub fn emerald_950() -> Color { rgb_bytes(2, 44, 34) }
+
+/// Teal 50 - #F0FDFA
+pub fn teal_50() -> Color { rgb_bytes(240, 253, 250) }
+/// Teal 100 - #CCFBF1
+pub fn teal_100() -> Color { rgb_bytes(204, 251, 241) }
+/// Teal 200 - #99F6E4
+pub fn teal_200() -> Color { rgb_bytes(153, 246, 228) }
+/// Teal 300 - #5EEAD4
+pub fn teal_300() -> Color { rgb_bytes(94, 234, 212) }
+/// Teal 400 - #2DD4BF
+pub fn teal_400() -> Color { rgb_bytes(45, 212, 191) }
+/// Teal 500 - #14B8A6
+pub fn teal_500() -> Color { rgb_bytes(20, 184, 166) }
+/// Teal 600 - #0D9488
+pub fn teal_600() -> Color { rgb_bytes(13, 148, 136) }
+/// Teal 700 - #0F766E
+pub fn teal_700() -> Color { rgb_bytes(15, 118, 110) }
+/// Teal 800 - #115E59
+pub fn teal_800() -> Color { rgb_bytes(17, 94, 89) }
+/// Teal 900 - #134E4A
+pub fn teal_900() -> Color { rgb_bytes(19, 78, 74) }
+/// Teal 950 - #042F2E
+pub fn teal_950() -> Color { rgb_bytes(4, 47, 46) }
+
+/// Cyan 50 - #ECFEFF
+pub fn cyan_50() -> Color { rgb_bytes(236, 254, 255) }
+/// Cyan 100 - #CFFAFE
+pub fn cyan_100() -> Color { rgb_bytes(207, 250, 254) }
+/// Cyan 200 - #A5F3FC
+pub fn cyan_200() -> Color { rgb_bytes(165, 243, 252) }
+/// Cyan 300 - #67E8F9
+pub fn cyan_300() -> Color { rgb_bytes(103, 232, 249) }
+/// Cyan 400 - #22D3EE
+pub fn cyan_400() -> Color { rgb_bytes(34, 211, 238) }
+/// Cyan 500 - #06B6D4
+pub fn cyan_500() -> Color { rgb_bytes(6, 182, 212) }
+/// Cyan 600 - #0891B2
+pub fn cyan_600() -> Color { rgb_bytes(8, 145, 178) }
+/// Cyan 700 - #0E7490
+pub fn cyan_700() -> Color { rgb_bytes(14, 116, 144) }
+/// Cyan 800 - #155E75
+pub fn cyan_800() -> Color { rgb_bytes(21, 94, 117) }
+/// Cyan 900 - #164E63
+pub fn cyan_900() -> Color { rgb_bytes(22, 78, 99) }
+/// Cyan 950 - #083344
+pub fn cyan_950() -> Color { rgb_bytes(8, 51, 68) }
+
+/// Sky 50 - #F0F9FF
+pub fn sky_50() -> Color { rgb_bytes(240, 249, 255) }
+/// Sky 100 - #E0F2FE
+pub fn sky_100() -> Color { rgb_bytes(224, 242, 254) }
+/// Sky 200 - #BAE6FD
+pub fn sky_200() -> Color { rgb_bytes(186, 230, 253) }
+/// Sky 300 - #7DD3FC
+pub fn sky_300() -> Color { rgb_bytes(125, 211, 252) }
+/// Sky 400 - #38BDF8
+pub fn sky_400() -> Color { rgb_bytes(56, 189, 248) }
+/// Sky 500 - #0EA5E9
+pub fn sky_500() -> Color { rgb_bytes(14, 165, 233) }
+/// Sky 600 - #0284C7
+pub fn sky_600() -> Color { rgb_bytes(2, 132, 199) }
+/// Sky 700 - #0369A1
+pub fn sky_700() -> Color { rgb_bytes(3, 105, 161) }
+/// Sky 800 - #075985
+pub fn sky_800() -> Color { rgb_bytes(7, 89, 133) }
+/// Sky 900 - #0C4A6E
+pub fn sky_900() -> Color { rgb_bytes(12, 74, 110) }
+/// Sky 950 - #082F49
+pub fn sky_950() -> Color { rgb_bytes(8, 47, 73) }
+
+/// Blue 50 - #EFF6FF
+pub fn blue_50() -> Color { rgb_bytes(239, 246, 255) }
+/// Blue 100 - #DBEAFE
+pub fn blue_100() -> Color { rgb_bytes(219, 234, 254) }
+/// Blue 200 - #BFDBFE
+pub fn blue_200() -> Color { rgb_bytes(191, 219, 254) }
+/// Blue 300 - #93C5FD
+pub fn blue_300() -> Color { rgb_bytes(147, 197, 253) }
+/// Blue 400 - #60A5FA
+pub fn blue_400() -> Color { rgb_bytes(96, 165, 250) }
+/// Blue 500 - #3B82F6
+pub fn blue_500() -> Color { rgb_bytes(59, 130, 246) }
+/// Blue 600 - #2563EB
+pub fn blue_600() -> Color { rgb_bytes(37, 99, 235) }
+/// Blue 700 - #1D4ED8
+pub fn blue_700() -> Color { rgb_bytes(29, 78, 216) }
+/// Blue 800 - #1E40AF
+pub fn blue_800() -> Color { rgb_bytes(30, 64, 175) }
+/// Blue 900 - #1E3A8A
+pub fn blue_900() -> Color { rgb_bytes(30, 58, 138) }
+/// Blue 950 - #172554
+pub fn blue_950() -> Color { rgb_bytes(23, 37, 84) }
+
+/// Indigo 50 - #EEF2FF
+pub fn indigo_50() -> Color { rgb_bytes(238, 242, 255) }
+/// Indigo 100 - #E0E7FF
+pub fn indigo_100() -> Color { rgb_bytes(224, 231, 255) }
+/// Indigo 200 - #C7D2FE
+pub fn indigo_200() -> Color { rgb_bytes(199, 210, 254) }
+/// Indigo 300 - #A5B4FC
+pub fn indigo_300() -> Color { rgb_bytes(165, 180, 252) }
+/// Indigo 400 - #818CF8
+pub fn indigo_400() -> Color { rgb_bytes(129, 140, 248) }
+/// Indigo 500 - #6366F1
+pub fn indigo_500() -> Color { rgb_bytes(99, 102, 241) }
+/// Indigo 600 - #4F46E5
+pub fn indigo_600() -> Color { rgb_bytes(79, 70, 229) }
+/// Indigo 700 - #4338CA
+pub fn indigo_700() -> Color { rgb_bytes(67, 56, 202) }
+/// Indigo 800 - #3730A3
+pub fn indigo_800() -> Color { rgb_bytes(55, 48, 163) }
+/// Indigo 900 - #312E81
+pub fn indigo_900() -> Color { rgb_bytes(49, 46, 129) }
+/// Indigo 950 - #1E1B4B
+pub fn indigo_950() -> Color { rgb_bytes(30, 27, 75) }
+
+/// Violet 50 - #F5F3FF
+pub fn violet_50() -> Color { rgb_bytes(245, 243, 255) }
+/// Violet 100 - #EDE9FE
+pub fn violet_100() -> Color { rgb_bytes(237, 233, 254) }
+/// Violet 200 - #DDD6FE
+pub fn violet_200() -> Color { rgb_bytes(221, 214, 254) }
+/// Violet 300 - #C4B5FD
+pub fn violet_300() -> Color { rgb_bytes(196, 181, 253) }
+/// Violet 400 - #A78BFA
+pub fn violet_400() -> Color { rgb_bytes(167, 139, 250) }
+/// Violet 500 - #8B5CF6
+pub fn violet_500() -> Color { rgb_bytes(139, 92, 246) }
+/// Violet 600 - #7C3AED
+pub fn violet_600() -> Color { rgb_bytes(124, 58, 237) }
+/// Violet 700 - #6D28D9
+pub fn violet_700() -> Color { rgb_bytes(109, 40, 217) }
+/// Violet 800 - #5B21B6
+pub fn violet_800() -> Color { rgb_bytes(91, 33, 182) }
+/// Violet 900 - #4C1D95
+pub fn violet_900() -> Color { rgb_bytes(76, 29, 149) }
+/// Violet 950 - #2E1065
+pub fn violet_950() -> Color { rgb_bytes(46, 16, 101) }
+
+/// Purple 50 - #FAF5FF
+pub fn purple_50() -> Color { rgb_bytes(250, 245, 255) }
+/// Purple 100 - #F3E8FF
+pub fn purple_100() -> Color { rgb_bytes(243, 232, 255) }
+/// Purple 200 - #E9D5FF
+pub fn purple_200() -> Color { rgb_bytes(233, 213, 255) }
+/// Purple 300 - #D8B4FE
+pub fn purple_300() -> Color { rgb_bytes(216, 180, 254) }
+/// Purple 400 - #C084FC
+pub fn purple_400() -> Color { rgb_bytes(192, 132, 252) }
+/// Purple 500 - #A855F7
+pub fn purple_500() -> Color { rgb_bytes(168, 85, 247) }
+/// Purple 600 - #9333EA
+pub fn purple_600() -> Color { rgb_bytes(147, 51, 234) }
+/// Purple 700 - #7E22CE
+pub fn purple_700() -> Color { rgb_bytes(126, 34, 206) }
+/// Purple 800 - #6B21A8
+pub fn purple_800() -> Color { rgb_bytes(107, 33, 168) }
+/// Purple 900 - #581C87
+pub fn purple_900() -> Color { rgb_bytes(88, 28, 135) }
+/// Purple 950 - #3B0764
+pub fn purple_950() -> Color { rgb_bytes(59, 7, 100) }
+
+/// Fuchsia 50 - #FDF4FF
+pub fn fuchsia_50() -> Color { rgb_bytes(253, 244, 255) }
+/// Fuchsia 100 - #FAE8FF
+pub fn fuchsia_100() -> Color { rgb_bytes(250, 232, 255) }
+/// Fuchsia 200 - #F5D0FE
+pub fn fuchsia_200() -> Color { rgb_bytes(245, 208, 254) }
+/// Fuchsia 300 - #F0ABFC
+pub fn fuchsia_300() -> Color { rgb_bytes(240, 171, 252) }
+/// Fuchsia 400 - #E879F9
+pub fn fuchsia_400() -> Color { rgb_bytes(232, 121, 249) }
+/// Fuchsia 500 - #D946EF
+pub fn fuchsia_500() -> Color { rgb_bytes(217, 70, 239) }
+/// Fuchsia 600 - #C026D3
+pub fn fuchsia_600() -> Color { rgb_bytes(192, 38, 211) }
+/// Fuchsia 700 - #A21CAF
+pub fn fuchsia_700() -> Color { rgb_bytes(162, 28, 175) }
+/// Fuchsia 800 - #86198F
+pub fn fuchsia_800() -> Color { rgb_bytes(134, 25, 143) }
+/// Fuchsia 900 - #701A75
+pub fn fuchsia_900() -> Color { rgb_bytes(112, 26, 117) }
+/// Fuchsia 950 - #4A044E
+pub fn fuchsia_950() -> Color { rgb_bytes(74, 4, 78) }
+
+/// Pink 50 - #FDF2F8
+pub fn pink_50() -> Color { rgb_bytes(253, 242, 248) }
+/// Pink 100 - #FCE7F3
+pub fn pink_100() -> Color { rgb_bytes(252, 231, 243) }
+/// Pink 200 - #FBCFE8
+pub fn pink_200() -> Color { rgb_bytes(251, 207, 232) }
+/// Pink 300 - #F9A8D4
+pub fn pink_300() -> Color { rgb_bytes(249, 168, 212) }
+/// Pink 400 - #F472B6
+pub fn pink_400() -> Color { rgb_bytes(244, 114, 182) }
+/// Pink 500 - #EC4899
+pub fn pink_500() -> Color { rgb_bytes(236, 72, 153) }
+/// Pink 600 - #DB2777
+pub fn pink_600() -> Color { rgb_bytes(219, 39, 119) }
+/// Pink 700 - #BE185D
+pub fn pink_700() -> Color { rgb_bytes(190, 24, 93) }
+/// Pink 800 - #9D174D
+pub fn pink_800() -> Color { rgb_bytes(157, 23, 77) }
+/// Pink 900 - #831843
+pub fn pink_900() -> Color { rgb_bytes(131, 24, 67) }
+/// Pink 950 - #500724
+pub fn pink_950() -> Color { rgb_bytes(80, 7, 36) }
+
+/// Rose 50 - #FFF1F2
+pub fn rose_50() -> Color { rgb_bytes(255, 241, 242) }
+/// Rose 100 - #FFE4E6
+pub fn rose_100() -> Color { rgb_bytes(255, 228, 230) }
+/// Rose 200 - #FECDD3
+pub fn rose_200() -> Color { rgb_bytes(254, 205, 211) }
+/// Rose 300 - #FDA4AF
+pub fn rose_300() -> Color { rgb_bytes(253, 164, 175) }
+/// Rose 400 - #FB7185
+pub fn rose_400() -> Color { rgb_bytes(251, 113, 133) }
+/// Rose 500 - #F43F5E
+pub fn rose_500() -> Color { rgb_bytes(244, 63, 94) }
+/// Rose 600 - #E11D48
+pub fn rose_600() -> Color { rgb_bytes(225, 29, 72) }
+/// Rose 700 - #BE123C
+pub fn rose_700() -> Color { rgb_bytes(190, 18, 60) }
+/// Rose 800 - #9F1239
+pub fn rose_800() -> Color { rgb_bytes(159, 18, 57) }
+/// Rose 900 - #881337
+pub fn rose_900() -> Color { rgb_bytes(136, 19, 55) }
+/// Rose 950 - #4C0519
+pub fn rose_950() -> Color { rgb_bytes(76, 5, 25) }
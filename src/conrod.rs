@@ -0,0 +1,199 @@
+//!
+//! Interop with the [conrod](https://github.com/PistonDevelopers/conrod) GUI library.
+//!
+//! This module is only available with the `conrod` feature. It works in both directions:
+//!
+//! - `primitives_to_form` converts the primitives that conrod renders each frame into a `Form`,
+//!   so that a GUI may be drawn within a collage, or by any of this crate's backends.
+//! - `ElementWidget` embeds an `Element` within a conrod GUI as a widget. Conrod knows nothing of
+//!   how to draw an `Element`, so the widget is rendered as a `PrimitiveKind::Other` primitive;
+//!   with the `piston-backend` feature, `draw_element_widget` draws these primitives.
+//!
+//! Both conrod and collages place the origin at the center of the window with the y-axis pointing
+//! up, so coordinates pass between the two unchanged.
+//!
+
+use color::{self, Color};
+use conrod_core::{self, widget, Ui, Widget};
+use conrod_core::position::Dimension;
+use conrod_core::render::{PrimitiveKind, Primitives};
+use element::Element;
+use form::{self, Form};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+#[cfg(feature = "piston-backend")]
+use conrod_core::render::Primitive;
+#[cfg(feature = "piston-backend")]
+use element::Renderer;
+#[cfg(feature = "piston-backend")]
+use error::Error;
+#[cfg(feature = "piston-backend")]
+use geometry::Rect;
+#[cfg(feature = "piston-backend")]
+use graphics::Graphics;
+#[cfg(feature = "piston-backend")]
+use graphics::character::CharacterCache;
+
+
+/// Convert the primitives of a conrod GUI into a single `Form`, in the order they are drawn.
+///
+/// Rectangles and triangles are converted to filled polygons. Triangles with a color per vertex
+/// are filled with the average of their vertices' colors, as forms have no per-vertex coloring.
+/// Text, image and `Other` primitives are skipped, as are the scissors that clip primitives to
+/// their parents - render the GUI through conrod's own backends where these matter.
+pub fn primitives_to_form(mut primitives: Primitives) -> Form {
+    let mut forms = Vec::new();
+    while let Some(primitive) = primitives.next() {
+        match primitive.kind {
+            PrimitiveKind::Rectangle { color } => {
+                let (x, y, w, h) = primitive.rect.x_y_w_h();
+                forms.push(form::rect(w, h).filled(color_from_conrod(color)).shift(x, y));
+            },
+            PrimitiveKind::TrianglesSingleColor { color, triangles } => {
+                let color = color_from_rgba(color);
+                forms.extend(triangles.iter().map(|triangle| {
+                    let points = triangle.0.iter().map(|p| (p[0], p[1])).collect();
+                    form::polygon(points).filled(color)
+                }));
+            },
+            PrimitiveKind::TrianglesMultiColor { triangles } => {
+                forms.extend(triangles.iter().map(|triangle| {
+                    let points = triangle.0.iter().map(|&(p, _)| (p[0], p[1])).collect();
+                    let mut sum = [0.0; 4];
+                    for &(_, conrod_core::color::Rgba(r, g, b, a)) in triangle.0.iter() {
+                        sum = [sum[0] + r, sum[1] + g, sum[2] + b, sum[3] + a];
+                    }
+                    let color = color::rgba(sum[0] / 3.0, sum[1] / 3.0, sum[2] / 3.0, sum[3] / 3.0);
+                    form::polygon(points).filled(color)
+                }));
+            },
+            PrimitiveKind::Image { .. } |
+            PrimitiveKind::Text { .. } |
+            PrimitiveKind::Other(_) => (),
+        }
+    }
+    form::group(forms)
+}
+
+
+/// Convert a conrod color to a `Color`.
+pub fn color_from_conrod(color: conrod_core::Color) -> Color {
+    let [r, g, b, a] = color.to_fsa();
+    color::rgba(r, g, b, a)
+}
+
+
+fn color_from_rgba(conrod_core::color::Rgba(r, g, b, a): conrod_core::color::Rgba) -> Color {
+    color::rgba(r, g, b, a)
+}
+
+
+/// A conrod widget that displays an `Element`.
+///
+/// Unless given a size with conrod's `Sizeable` methods, the widget takes the size of its
+/// `Element`. The `Element` is centered within the widget's rectangle and clipped to it.
+pub struct ElementWidget {
+    common: widget::CommonBuilder,
+    element: Element,
+}
+
+
+/// The state of an `ElementWidget`, holding the `Element` it last displayed.
+pub struct State {
+    pub element: Element,
+    hash: u64,
+}
+
+
+impl ElementWidget {
+
+    /// Construct a widget displaying the given `Element`.
+    pub fn new(element: Element) -> ElementWidget {
+        ElementWidget {
+            common: widget::CommonBuilder::default(),
+            element: element,
+        }
+    }
+
+}
+
+
+impl widget::Common for ElementWidget {
+    fn common(&self) -> &widget::CommonBuilder {
+        &self.common
+    }
+    fn common_mut(&mut self) -> &mut widget::CommonBuilder {
+        &mut self.common
+    }
+}
+
+
+impl Widget for ElementWidget {
+    type State = State;
+    type Style = ();
+    type Event = ();
+
+    fn init_state(&self, _: widget::id::Generator) -> State {
+        State { element: self.element.clone(), hash: hash_element(&self.element) }
+    }
+
+    fn style(&self) -> () {
+        ()
+    }
+
+    fn default_x_dimension(&self, _ui: &Ui) -> Dimension {
+        Dimension::Absolute(self.element.get_width() as f64)
+    }
+
+    fn default_y_dimension(&self, _ui: &Ui) -> Dimension {
+        Dimension::Absolute(self.element.get_height() as f64)
+    }
+
+    /// Only replaces the stored `Element` when it has changed, so that conrod does not redraw an
+    /// unchanged widget.
+    fn update(self, args: widget::UpdateArgs<Self>) {
+        let widget::UpdateArgs { state, .. } = args;
+        let hash = hash_element(&self.element);
+        if state.hash != hash {
+            let element = self.element;
+            state.update(|state| {
+                state.element = element;
+                state.hash = hash;
+            });
+        }
+    }
+}
+
+
+fn hash_element(element: &Element) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    element.hash(&mut hasher);
+    hasher.finish()
+}
+
+
+/// Draw the given primitive if it belongs to an `ElementWidget`, returning whether it did.
+///
+/// Call this for each `PrimitiveKind::Other` primitive while rendering a conrod GUI with piston
+/// graphics. The `Element` is drawn within the primitive's rectangle and clipped to it, although
+/// not to the primitive's scissor.
+#[cfg(feature = "piston-backend")]
+pub fn draw_element_widget<'a, C, G>(primitive: &Primitive, renderer: &mut Renderer<'a, C, G>)
+    -> Result<bool, Error>
+    where
+        C: CharacterCache,
+        G: Graphics<Texture=C::Texture>,
+{
+    let container = match primitive.kind {
+        PrimitiveKind::Other(container) => container,
+        _ => return Ok(false),
+    };
+    let unique = match container.unique_widget_state::<ElementWidget>() {
+        Some(unique) => unique,
+        None => return Ok(false),
+    };
+    let (x, y, w, h) = primitive.rect.x_y_w_h();
+    unique.state.element.draw_in_rect(Rect::from_xywh(x, y, w, h), renderer)?;
+    Ok(true)
+}
@@ -11,32 +11,75 @@
 //!
 //! # Images
 //!
-//!   image, fitted_image, cropped_image, tiled_image
+//!   image, fitted_image, cropped_image, tiled_image, animated_image, animated_gif
 //!
 //!
 //! # Styling
 //!
-//!   width, height, size, color, opacity
+//!   width, height, size, set_width_exact, set_height_exact, color, background, corner_radius,
+//!   opacity, visible, collapsed
+//!
+//! ## Sizing in other units
+//!
+//! Sizes may also be given as a `Length` in viewport or physical units. These are resolved when
+//! the `Element` is drawn, so like `responsive` the resulting `Element`s take up no space within
+//! other layouts:
+//!
+//!   width_in, height_in, size_in, spacer_in, with_viewport
+//!
+//! ## Opacity
+//!
+//! An `Element`'s opacity multiplies the opacity of everything it contains, as does the `alpha`
+//! of a `Form`, so the opacity with which anything is drawn is the product of its own opacity and
+//! that of all of its ancestors. This applies alike to backgrounds, `clear` colors, images, text
+//! and the forms of collages. Each primitive is faded individually, so overlapping children of a
+//! translucent `Element` show through one another.
 //!
 //!
 //! # Inspection
 //!
-//!   width_of, height_of, size_of
+//!   width_of, height_of, size_of, is_empty, is_hidden, content_size, content_hash, pick_top,
+//!   pick_top_with_min_alpha
+//!
+//! Elements may be given an `ElementId` with `id`, which is kept within the layout resolved by
+//! `Element::resolve` for tooling and tests. The tree may be searched without matching upon
+//! `Prim` variants with:
+//!
+//!   children, descendants, find
 //!
 //!
 //! # Layout
 //!
-//!   flow, up, down, left, right, inward, outward
+//!   flow, flow_justified, flow_flex, up, down, left, right, inward, outward
 //!
 //! ## Layout Aliases
 //!
 //! There are some convenience functions for working with `flow` in specific cases:
 //!
-//!   layers, above, below, beside
+//!   layers, stack, above, below, beside, above_aligned, below_aligned, beside_aligned
+//!
+//! Empty `Element`s (see `Element::is_empty`) take up no space within a flow. Use `try_flow` and
+//! `try_layers` to have degenerate layouts reported as a `FlowError` instead.
+//!
+//! ## Tables
+//!
+//! Rows of cells may be laid out in columns with `table`, whose columns fit their widest cell, take
+//! a fixed width or share the width left over within the table (see `ColumnWidth`):
+//!
+//!   table(rows).columns(vec![ColumnWidth::Fit, ColumnWidth::Weighted(1.0)]).width(600)
+//!       .padding(4).grid_lines(solid(grey())).into_element()
 //!
 //!
 //! # Positioning
-//!   empty, spacer, container
+//!   empty, spacer, background, container, container_many, inset_by, responsive
+//!
+//! ## Safe Areas
+//!
+//! Displays with notches, rounded corners or overscan may obscure the edges of a full-screen
+//! `Element`. The renderer is given the obscured `Insets` via `RenderOptions::safe_area`, and
+//! `with_safe_area` lays out content within whatever remains:
+//!
+//!   with_safe_area(|w, h| hud(w, h))
 //!
 //! ## Specific Positions
 //!
@@ -52,13 +95,32 @@
 //!   top_left_at, top_right_at, bottom_left_at, bottom_right_at
 //!
 
-use color::Color;
-use form::{self, Form};
-use graphics::character::CharacterCache;
-use graphics::{Context, Graphics, Transformed};
+use color::{Color, Gradient};
+use form::{self, BasicForm, Form, LineStyle, Tag};
+use geometry::{self, Rect};
+use length::{Insets, Length, Viewport};
 use self::Three::{P, Z, N};
+use std::error;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::iter;
 use std::path::PathBuf;
-use transform_2d;
+use std::sync::Arc;
+use utils::{hash_f32, hash_f64, FnvHasher};
+
+#[cfg(feature = "gif")]
+use gif;
+#[cfg(feature = "gif")]
+use png;
+#[cfg(feature = "gif")]
+use std::fs::{self, File};
+#[cfg(feature = "gif")]
+use std::io::{self, BufWriter};
+#[cfg(feature = "gif")]
+use std::path::Path;
+
+#[cfg(feature = "piston-backend")]
+pub use backend::piston::{draw_element, Renderer};
 
 
 /// An Element's Properties.
@@ -68,7 +130,165 @@ pub struct Properties {
     pub height: i32,
     pub opacity: f32,
     pub crop: Option<(f64, f64, f64, f64)>,
-    pub color: Option<Color>,
+    /// A crop in the Element's own coordinates. See `Element::crop_local`.
+    pub local_crop: Option<(f64, f64, f64, f64)>,
+    pub background: Option<Background>,
+    /// The radius with which the background's corners are rounded and the content is clipped.
+    pub corner_radius: f64,
+    /// Identifies the Element to tooling and tests. See `Element::id`.
+    pub id: Option<ElementId>,
+    /// Whether the Element is drawn. See `Element::visible`.
+    pub visible: bool,
+    /// Whether the Element is hidden and takes up no space within flows. See
+    /// `Element::collapsed`.
+    pub collapsed: bool,
+}
+
+/// The ID of an Element doesn't affect how it is drawn, so it isn't hashed.
+impl Hash for Properties {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let Properties {
+            width, height, opacity, crop, local_crop, ref background, corner_radius, id: _,
+            visible, collapsed,
+        } = *self;
+        (width, height).hash(state);
+        hash_f32(opacity, state);
+        hash_crop(crop, state);
+        hash_crop(local_crop, state);
+        background.hash(state);
+        hash_f64(corner_radius, state);
+        visible.hash(state);
+        collapsed.hash(state);
+    }
+}
+
+/// Feed an optional crop to the given hasher by the bit patterns of its floats.
+fn hash_crop<H: Hasher>(crop: Option<(f64, f64, f64, f64)>, state: &mut H) {
+    match crop {
+        Some((x, y, w, h)) => {
+            1u8.hash(state);
+            for &f in [x, y, w, h].iter() {
+                hash_f64(f, state);
+            }
+        },
+        None => 0u8.hash(state),
+    }
+}
+
+impl Properties {
+
+    /// The color of a solid background, if any.
+    ///
+    /// This replaces the `color` field, which became `background` when backgrounds gained
+    /// gradients and images.
+    #[deprecated(since = "0.13.0", note = "match upon the `background` field instead")]
+    pub fn color(&self) -> Option<Color> {
+        match self.background {
+            Some(Background::Solid(color)) => Some(color),
+            Some(Background::Grad(_)) | Some(Background::Image(_)) | None => None,
+        }
+    }
+
+}
+
+
+/// An iterator over the Elements nested directly within some Element. See `Element::children`.
+pub struct Children<'a> {
+    children: ::std::vec::IntoIter<&'a Element>,
+}
+
+impl<'a> Iterator for Children<'a> {
+    type Item = &'a Element;
+    fn next(&mut self) -> Option<&'a Element> {
+        self.children.next()
+    }
+}
+
+
+/// An iterator over every Element nested within some Element. See `Element::descendants`.
+pub struct Descendants<'a> {
+    stack: Vec<&'a Element>,
+}
+
+impl<'a> Iterator for Descendants<'a> {
+    type Item = &'a Element;
+    fn next(&mut self) -> Option<&'a Element> {
+        let element = self.stack.pop()?;
+        let start = self.stack.len();
+        self.stack.extend(element.children());
+        self.stack[start..].reverse();
+        Some(element)
+    }
+}
+
+
+/// Push the Elements placed within the given forms, searching within groups.
+fn push_form_elements<'a>(forms: &'a [Form], elements: &mut Vec<&'a Element>) {
+    for form in forms.iter() {
+        match form.form {
+            BasicForm::Element(ref element) => elements.push(element),
+            BasicForm::Group(_, ref forms) => push_form_elements(forms, elements),
+            BasicForm::Positioned(ref forms) => {
+                for &(_, ref form) in forms.iter() {
+                    push_form_elements(::std::slice::from_ref(form), elements);
+                }
+            },
+            BasicForm::Instanced(ref form, _) =>
+                push_form_elements(::std::slice::from_ref(&**form), elements),
+            BasicForm::Lod(ref levels) => {
+                if let Some(&(_, ref form)) = levels.first() {
+                    push_form_elements(::std::slice::from_ref(form), elements);
+                }
+            },
+            _ => (),
+        }
+    }
+}
+
+
+/// An identifier given to an `Element` so that it may be found within the tree and recognised
+/// within layout and hit testing results, e.g. by `ResolvedElement::id_at`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ElementId {
+    Name(String),
+    Index(u64),
+}
+
+impl<'a> From<&'a str> for ElementId {
+    fn from(name: &'a str) -> ElementId {
+        ElementId::Name(name.to_string())
+    }
+}
+
+impl From<String> for ElementId {
+    fn from(name: String) -> ElementId {
+        ElementId::Name(name)
+    }
+}
+
+impl From<u64> for ElementId {
+    fn from(index: u64) -> ElementId {
+        ElementId::Index(index)
+    }
+}
+
+impl fmt::Display for ElementId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ElementId::Name(ref name) => write!(f, "{}", name),
+            ElementId::Index(index) => write!(f, "#{}", index),
+        }
+    }
+}
+
+
+/// What is drawn behind an `Element`'s content, filling the `Element`'s area.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Background {
+    Solid(Color),
+    Grad(Gradient),
+    /// The image at the given path, fitted to the `Element` as with `fitted_image`.
+    Image(PathBuf),
 }
 
 
@@ -76,7 +296,7 @@ pub struct Properties {
 ///
 /// Each element is a rectangle with a known width and height, making them easy to combine and
 /// position.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Hash)]
 pub struct Element {
     pub props: Properties,
     pub element: Prim,
@@ -85,45 +305,93 @@ pub struct Element {
 
 impl Element {
 
-    /// Create an `Element` with a given width.
+    /// Create an `Element` with a given width, scaling its height to preserve its aspect ratio.
+    ///
+    /// The aspect ratio is that of the `Element`'s content (see `content_size`), whose rendering
+    /// is scaled to fill the new size. Use `set_width_exact` to leave the height as it is.
     #[inline]
     pub fn width(self, new_width: i32) -> Element {
-        let Element { props, element } = self;
-        let new_props = match element {
-            Prim::Image(_, w, h, _) | Prim::Collage(w, h, _) => {
-                Properties {
-                    height: (h as f32 / w as f32 * new_width as f32).round() as i32,
-                    ..props
-                }
-            },
-            _ => props,
+        let (w, h) = self.content_size();
+        let new_height = if w > 0 {
+            (h as f32 / w as f32 * new_width as f32).round() as i32
+        } else {
+            self.props.height
         };
-        Element { props: new_props, element: element }
+        self.set_width_exact(new_width).set_height_exact(new_height)
     }
 
-    /// Create an `Element` with a given height.
+    /// Create an `Element` with a given height, scaling its width to preserve its aspect ratio.
+    ///
+    /// See `width`. Use `set_height_exact` to leave the width as it is.
     #[inline]
     pub fn height(self, new_height: i32) -> Element {
-        let Element { props, element } = self;
-        let new_props = match element {
-            Prim::Image(_, w, h, _) | Prim::Collage(w, h, _) => {
-                Properties {
-                    width: (w as f32 / h as f32 * new_height as f32).round() as i32,
-                    ..props
-                }
-            },
-            _ => props,
+        let (w, h) = self.content_size();
+        let new_width = if h > 0 {
+            (w as f32 / h as f32 * new_height as f32).round() as i32
+        } else {
+            self.props.width
         };
-        Element { props: new_props, element: element }
+        self.set_width_exact(new_width).set_height_exact(new_height)
     }
 
-    /// Create an `Element` with a given size.
+    /// Create an `Element` with a given size, scaling its content to fill it.
     #[inline]
     pub fn size(self, new_w: i32, new_h: i32) -> Element {
-        self.height(new_h).width(new_w)
+        self.set_width_exact(new_w).set_height_exact(new_h)
     }
 
-    /// Create an `Element` with a given opacity.
+    /// Create an `Element` with a given width, leaving its height unchanged.
+    #[inline]
+    pub fn set_width_exact(mut self, new_width: i32) -> Element {
+        self.props.width = new_width;
+        self
+    }
+
+    /// Create an `Element` with a given height, leaving its width unchanged.
+    #[inline]
+    pub fn set_height_exact(mut self, new_height: i32) -> Element {
+        self.props.height = new_height;
+        self
+    }
+
+    /// Create an `Element` with a width resolved at draw time, scaling its height to preserve its
+    /// aspect ratio as with `width`.
+    ///
+    /// As with `responsive`, the size isn't known until drawn, so the resulting `Element` takes
+    /// up no space within other layouts.
+    pub fn width_in<L: Into<Length>>(self, new_width: L) -> Element {
+        let new_width = new_width.into();
+        with_viewport(move |viewport| self.clone().width(viewport.px(new_width)))
+    }
+
+    /// Create an `Element` with a height resolved at draw time, scaling its width to preserve its
+    /// aspect ratio as with `height`. See `width_in`.
+    pub fn height_in<L: Into<Length>>(self, new_height: L) -> Element {
+        let new_height = new_height.into();
+        with_viewport(move |viewport| self.clone().height(viewport.px(new_height)))
+    }
+
+    /// Create an `Element` with a size resolved at draw time, scaling its content to fill it.
+    /// See `width_in`.
+    pub fn size_in<W, H>(self, new_w: W, new_h: H) -> Element
+        where W: Into<Length>,
+              H: Into<Length>,
+    {
+        let (new_w, new_h) = (new_w.into(), new_h.into());
+        with_viewport(move |viewport| self.clone().size(viewport.px(new_w), viewport.px(new_h)))
+    }
+
+    /// Create an `Element` with the given ID, e.g. `element.id("sidebar")`.
+    ///
+    /// IDs don't affect layout or drawing. They are kept within the layout resolved by
+    /// `Element::resolve`, so that tooling and tests may refer to parts of the tree robustly.
+    #[inline]
+    pub fn id<I: Into<ElementId>>(mut self, id: I) -> Element {
+        self.props.id = Some(id.into());
+        self
+    }
+
+    /// Create an `Element` with a given opacity, which multiplies the opacity of its content.
     #[inline]
     pub fn opacity(mut self, opacity: f32) -> Element {
         self.props.opacity = opacity;
@@ -131,9 +399,29 @@ impl Element {
     }
 
     /// Create an `Element with a given background color.
+    ///
+    /// The background fills the `Element`'s area behind its content and is faded by the
+    /// `Element`'s opacity along with the content.
+    #[inline]
+    pub fn color(self, color: Color) -> Element {
+        self.background(Background::Solid(color))
+    }
+
+    /// Create an `Element` with a given `Background`, drawn in the same manner as `color`.
     #[inline]
-    pub fn color(mut self, color: Color) -> Element {
-        self.props.color = Some(color);
+    pub fn background(mut self, background: Background) -> Element {
+        self.props.background = Some(background);
+        self
+    }
+
+    /// Round the corners of the `Element`'s background with the given radius, clipping the
+    /// `Element`'s content to the rounded rectangle.
+    ///
+    /// The piston renderer clips the content via the stencil buffer, so its corners are only
+    /// clipped when drawing to a render target with one.
+    #[inline]
+    pub fn corner_radius(mut self, radius: f64) -> Element {
+        self.props.corner_radius = radius;
         self
     }
 
@@ -145,6 +433,86 @@ impl Element {
         Element { props: new_props, element: element }
     }
 
+    /// Crops an `Element` with the given rectangle in the `Element`'s own coordinates, where the
+    /// origin is the `Element`'s center and the y-axis points up.
+    ///
+    /// Unlike `crop`, the rectangle moves along with the `Element` wherever it is placed.
+    #[inline]
+    pub fn crop_local(mut self, x: f64, y: f64, w: f64, h: f64) -> Element {
+        self.props.local_crop = Some((x, y, w, h));
+        self
+    }
+
+    /// Crops an `Element` with a rectangle given as fractions of the `Element`'s size.
+    ///
+    /// `x_frac` and `y_frac` locate the rectangle's center relative to the `Element`'s center, so
+    /// `crop_frac(0.0, 0.0, 0.5, 0.5)` keeps the middle quarter of the `Element`.
+    #[inline]
+    pub fn crop_frac(self, x_frac: f64, y_frac: f64, w_frac: f64, h_frac: f64) -> Element {
+        let (w, h) = (self.props.width as f64, self.props.height as f64);
+        self.crop_local(x_frac * w, y_frac * h, w_frac * w, h_frac * h)
+    }
+
+    /// Crops an `Element` with a rectangle whose top left corner is `x` to the right of and `y`
+    /// below the `Element`'s top left corner.
+    #[inline]
+    pub fn crop_from_top_left(self, x: f64, y: f64, w: f64, h: f64) -> Element {
+        self.crop_from_corner(N, P, x, y, w, h)
+    }
+
+    /// Crops an `Element` with a rectangle whose top right corner is `x` to the left of and `y`
+    /// below the `Element`'s top right corner.
+    #[inline]
+    pub fn crop_from_top_right(self, x: f64, y: f64, w: f64, h: f64) -> Element {
+        self.crop_from_corner(P, P, x, y, w, h)
+    }
+
+    /// Crops an `Element` with a rectangle whose bottom left corner is `x` to the right of and
+    /// `y` above the `Element`'s bottom left corner.
+    #[inline]
+    pub fn crop_from_bottom_left(self, x: f64, y: f64, w: f64, h: f64) -> Element {
+        self.crop_from_corner(N, N, x, y, w, h)
+    }
+
+    /// Crops an `Element` with a rectangle whose bottom right corner is `x` to the left of and
+    /// `y` above the `Element`'s bottom right corner.
+    #[inline]
+    pub fn crop_from_bottom_right(self, x: f64, y: f64, w: f64, h: f64) -> Element {
+        self.crop_from_corner(P, N, x, y, w, h)
+    }
+
+    /// Crop with a rectangle inset from the given corner by `x` and `y`.
+    fn crop_from_corner(self, horizontal: Three, vertical: Three, x: f64, y: f64, w: f64, h: f64)
+        -> Element
+    {
+        let (e_w, e_h) = (self.props.width as f64, self.props.height as f64);
+        let sign = |three: Three| match three { P => 1.0, Z => 0.0, N => -1.0 };
+        let center_x = sign(horizontal) * ((e_w - w) / 2.0 - x);
+        let center_y = sign(vertical) * ((e_h - h) / 2.0 - y);
+        self.crop_local(center_x, center_y, w, h)
+    }
+
+    /// Show or hide the Element. Hidden Elements (and everything within them) are neither drawn nor
+    /// picked, but keep their size within the layout, so they may be shown again without
+    /// rebuilding the layout around them. The default is visible.
+    #[inline]
+    pub fn visible(mut self, visible: bool) -> Element {
+        self.props.visible = visible;
+        self
+    }
+
+    /// Collapse the Element, hiding it and having it take up no space within flows. The default
+    /// is not collapsed.
+    ///
+    /// A collapsed Element is empty (see `is_empty`), so it is skipped by flows as though it
+    /// weren't there, while keeping its place within the tree. Its own width and height are left
+    /// untouched, so it takes up its space again once it is no longer collapsed.
+    #[inline]
+    pub fn collapsed(mut self, collapsed: bool) -> Element {
+        self.props.collapsed = collapsed;
+        self
+    }
+
     /// Put an element in a container. This lets you position the element really easily, and there are
     /// tons of ways to set the `Position`.
     #[inline]
@@ -152,8 +520,24 @@ impl Element {
         new_element(w, h, Prim::Container(pos, Box::new(self)))
     }
 
+    /// Surround an element with the given insets, producing an element larger by the insets with
+    /// the original placed inset from each edge. Insets are rounded to whole pixels.
+    pub fn inset_by(self, insets: Insets) -> Element {
+        let Insets { top, right, bottom, left } = insets;
+        let (top, right) = (top.round() as i32, right.round() as i32);
+        let (bottom, left) = (bottom.round() as i32, left.round() as i32);
+        let w = self.get_width() + left + right;
+        let h = self.get_height() + top + bottom;
+        self.container(w, h, top_left_at(absolute(left), absolute(top)))
+    }
+
     /// Put an element in a cleared wrapper. The color provided will be the color that clears the
     /// screen before rendering the contained element.
+    ///
+    /// The clear color is faded by the opacity of the `Element` and its ancestors. Only the root
+    /// `Element` of a frame may clear the screen, as clearing would wipe everything drawn before
+    /// it. Cleared `Element`s elsewhere only fill their own area with the color - use
+    /// `background` to fill the whole screen behind some layers.
     #[inline]
     pub fn clear(self, color: Color) -> Element {
         new_element(self.get_width(), self.get_height(),
@@ -163,9 +547,7 @@ impl Element {
     /// Stack elements vertically. To put `a` above `b` you would say: `a.above(b)`
     #[inline]
     pub fn above(self, other: Element) -> Element {
-        new_element(::std::cmp::max(self.get_width(), other.get_width()),
-                    self.get_height() + other.get_height(),
-                    Prim::Flow(down(), vec![self, other]))
+        flow(down(), vec![self, other])
     }
 
     /// Stack elements vertically. To put `a` below `b` you would say: `a.below(b)`
@@ -178,9 +560,45 @@ impl Element {
     ///   `a.beside(b)`
     #[inline]
     pub fn beside(self, other: Element) -> Element {
-        new_element(self.get_width() + other.get_width(),
-                    ::std::cmp::max(self.get_height(), other.get_height()),
-                    Prim::Flow(right(), vec![self, other]))
+        flow(right(), vec![self, other])
+    }
+
+    /// Stack elements vertically, aligning them along the given edge rather than their centers.
+    /// To give `a` and `b` a shared left edge you would say: `a.above_aligned(Alignment::Left, b)`
+    ///
+    /// Only `Left`, `Center` and `Right` apply to vertical stacks - `Top` and `Bottom` center
+    /// the elements.
+    pub fn above_aligned(self, alignment: Alignment, other: Element) -> Element {
+        let w = ::std::cmp::max(self.get_width(), other.get_width());
+        let position = match alignment {
+            Alignment::Left => mid_left(),
+            Alignment::Right => mid_right(),
+            Alignment::Top | Alignment::Center | Alignment::Bottom => middle(),
+        };
+        let align = |e: Element| { let h = e.get_height(); e.container(w, h, position) };
+        align(self).above(align(other))
+    }
+
+    /// Stack elements vertically, aligning them along the given edge. See `above_aligned`.
+    #[inline]
+    pub fn below_aligned(self, alignment: Alignment, other: Element) -> Element {
+        other.above_aligned(alignment, self)
+    }
+
+    /// Put elements beside each other horizontally, aligning them along the given edge rather
+    /// than their centers.
+    ///
+    /// Only `Top`, `Center` and `Bottom` apply to horizontal rows - `Left` and `Right` center the
+    /// elements.
+    pub fn beside_aligned(self, alignment: Alignment, other: Element) -> Element {
+        let h = ::std::cmp::max(self.get_height(), other.get_height());
+        let position = match alignment {
+            Alignment::Top => mid_top(),
+            Alignment::Bottom => mid_bottom(),
+            Alignment::Left | Alignment::Center | Alignment::Right => middle(),
+        };
+        let align = |e: Element| { let w = e.get_width(); e.container(w, h, position) };
+        align(self).beside(align(other))
     }
 
     /// Return the width of the Element.
@@ -192,26 +610,123 @@ impl Element {
     /// Return the size of the Element's bounding rectangle.
     pub fn get_size(&self) -> (i32, i32) { (self.props.width, self.props.height) }
 
-    /// Draw the form with some given graphics backend.
-    #[inline]
-    pub fn draw<'a, C, G>(&self, renderer: &mut Renderer<'a, C, G>)
-        where
-            C: CharacterCache,
-            G: Graphics<Texture=C::Texture>,
-    {
-        let Renderer {
-            context,
-            ref mut backend,
-            ref mut maybe_character_cache,
-        } = *renderer;
-        let view_size = context.get_view_size();
-        let context = context.trans(view_size[0] / 2.0, view_size[1] / 2.0).scale(1.0, -1.0);
-        draw_element(self, 1.0, *backend, maybe_character_cache, context);
+    /// The natural size of the Element's content, i.e. its size before being resized with
+    /// `width`, `height` or `size`.
+    ///
+    /// The content is scaled from this size to fill the Element when drawn, so that the forms of
+    /// a resized collage are scaled along with it. Containers and spacers lay out their content
+    /// within whatever size they are given, so for those this is the Element's size.
+    pub fn content_size(&self) -> (i32, i32) {
+        match self.element {
+            Prim::Image(_, w, h, _) | Prim::Collage(w, h, _) => (w, h),
+            Prim::Flow(dir, ref elements) => flow_size(dir, elements),
+            Prim::Cleared(_, ref element) => element.get_size(),
+            Prim::Container(..) | Prim::Fill(_) | Prim::Responsive(_) | Prim::Spacer =>
+                self.get_size(),
+        }
+    }
+
+    /// The amount by which the Element's content is scaled along each axis to fill the Element.
+    pub fn content_scale(&self) -> (f64, f64) {
+        let (w, h) = self.content_size();
+        let scale = |dim: i32, content: i32| {
+            if content > 0 { dim as f64 / content as f64 } else { 1.0 }
+        };
+        (scale(self.props.width, w), scale(self.props.height, h))
+    }
+
+    /// A hash of everything that determines how the Element is drawn: its layout, geometry,
+    /// styles and text.
+    ///
+    /// The hash is deterministic, so it may be compared between frames to skip rendering when
+    /// nothing has changed, or used to key caches that outlive the process. Floats are hashed by
+    /// their bit patterns, and `responsive` Elements by the identity of their function.
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = FnvHasher::default();
+        self.hash(&mut hasher);
+        hasher.finish()
     }
 
-    /// Return whether or not a point is over the element.
+    /// Whether or not the Element takes up no space, i.e. it is collapsed or neither its width nor
+    /// its height is greater than zero.
+    ///
+    /// `empty()`, `layers(vec![])`, collapsed Elements and flows of only empty Elements are all
+    /// empty. Empty Elements are skipped when sizing a flow, so they may be used for things that
+    /// appear conditionally.
+    pub fn is_empty(&self) -> bool {
+        self.props.collapsed || (self.props.width <= 0 && self.props.height <= 0)
+    }
+
+    /// Whether the Element is neither drawn nor picked, i.e. whether it is hidden (see `visible`)
+    /// or collapsed.
+    pub fn is_hidden(&self) -> bool {
+        !self.props.visible || self.props.collapsed
+    }
+
+    /// The Elements nested directly within this one, in the order in which they are drawn.
+    ///
+    /// This includes Elements placed within a collage's forms via `form::to_form`, however deeply
+    /// they are grouped. Elements produced at draw time (i.e. by `responsive`) aren't known until
+    /// drawn, so aren't included.
+    pub fn children(&self) -> Children<'_> {
+        let mut children = Vec::new();
+        match self.element {
+            Prim::Container(_, ref element) | Prim::Cleared(_, ref element) =>
+                children.push(&**element),
+            Prim::Flow(_, ref elements) => children.extend(elements.iter()),
+            Prim::Collage(_, _, ref forms) => push_form_elements(forms, &mut children),
+            Prim::Image(..) | Prim::Fill(_) | Prim::Responsive(_) | Prim::Spacer => (),
+        }
+        Children { children: children.into_iter() }
+    }
+
+    /// Every Element nested within this one, however deeply, in depth-first order.
+    pub fn descendants(&self) -> Descendants<'_> {
+        let mut stack: Vec<_> = self.children().collect();
+        stack.reverse();
+        Descendants { stack: stack }
+    }
+
+    /// The first Element with the given ID, searching this Element and then its descendants in
+    /// depth-first order.
+    pub fn find<I: Into<ElementId>>(&self, id: I) -> Option<&Element> {
+        let id = Some(id.into());
+        iter::once(self).chain(self.descendants()).find(|element| element.props.id == id)
+    }
+
+    /// Return whether or not a point is over the element, where the Element is drawn as the root
+    /// of the frame (i.e. the point is relative to the Element's center, with the y-axis pointing
+    /// up).
+    ///
+    /// The point is over the element if it lies within the Element's rectangle, or over any of
+    /// the Elements and forms nested within it that extend beyond it. The crops and rounded
+    /// corners of the Element and those containing its children are taken into account, while
+    /// opacity is not. A `background` is over every point, while hidden Elements are over none.
+    ///
+    /// Elements produced at draw time for the viewport (by `responsive`, `with_viewport`,
+    /// `size_in`, `animated_image` and the like) are never over any point, as no viewport is
+    /// known here. Hit test these with `ResolvedElement::node_at` once the Element is resolved
+    /// for its render target.
     pub fn is_over(&self, x: i32, y: i32) -> bool {
-        unimplemented!();
+        let point = (x as f64, y as f64);
+        is_over_element(self, point, point)
+    }
+
+    /// The `Tag` of the topmost tagged `Form` lying under the given point, where the Element is
+    /// drawn as the root of the frame (i.e. the point is relative to the Element's center).
+    ///
+    /// Forms are tested in the reverse of the order in which they are drawn, taking the crops
+    /// and rounded corners of the Elements containing them into account. Forms drawn with no
+    /// opacity are skipped, and untagged forms never block those beneath them. Where tagged
+    /// forms are nested (i.e. within a `group`) the innermost tag is returned.
+    pub fn pick_top(&self, x: f64, y: f64) -> Option<Tag> {
+        self.pick_top_with_min_alpha(x, y, 0.0)
+    }
+
+    /// As `pick_top`, but also skipping forms drawn with an opacity below `min_alpha`, so that
+    /// faint overlays may be clicked through.
+    pub fn pick_top_with_min_alpha(&self, x: f64, y: f64, min_alpha: f32) -> Option<Tag> {
+        pick_top_element(self, (x, y), (x, y), 1.0, min_alpha)
     }
 
 }
@@ -231,8 +746,13 @@ pub fn new_element(w: i32, h: i32, element: Prim) -> Element {
             width: w,
             height: h,
             opacity: 1.0,
-            color: None,
+            background: None,
+            corner_radius: 0.0,
             crop: None,
+            local_crop: None,
+            id: None,
+            visible: true,
+            collapsed: false,
         },
         element: element,
     }
@@ -251,20 +771,118 @@ pub fn empty() -> Element {
 }
 
 
+/// An Element that fills the entire render target with the given color, covering everything
+/// drawn before it. It takes up no space within other layouts.
+///
+/// Unlike `clear`, this may be used anywhere, e.g. as the bottom of some `layers`.
+pub fn background(color: Color) -> Element {
+    new_element(0, 0, Prim::Fill(color))
+}
+
+
+/// Position several elements within a single container of the given width and height.
+///
+/// This is equivalent to layering each element within its own container of the same size. The
+/// elements are drawn in the order given, so later elements appear above earlier ones.
+pub fn container_many(w: i32, h: i32, elements: Vec<(Position, Element)>) -> Element {
+    let containers = elements.into_iter().map(|(pos, element)| element.container(w, h, pos));
+    new_element(w, h, Prim::Flow(outward(), containers.collect()))
+}
+
+
+/// An Element produced at draw time for the size of the render target, so that layouts may adapt
+/// to the window without its size being threaded through the code that builds them.
+///
+/// The function is given the width and height of the render target. The resulting Element takes
+/// up no space within other layouts, as its size isn't known until drawn, so it is best used as
+/// the root of a tree or as a layer.
+pub fn responsive<F>(f: F) -> Element
+    where F: Fn(i32, i32) -> Element + Send + Sync + 'static,
+{
+    with_viewport(move |viewport| f(viewport.width as i32, viewport.height as i32))
+}
+
+
+/// An Element produced at draw time for the `Viewport` of the render target, against which any
+/// `Length`s may be resolved.
+///
+/// As with `responsive`, the resulting Element takes up no space within other layouts.
+pub fn with_viewport<F>(f: F) -> Element
+    where F: Fn(&Viewport) -> Element + Send + Sync + 'static,
+{
+    new_element(0, 0, Prim::Responsive(Responsive(Arc::new(f))))
+}
+
+
+/// An Element produced at draw time for the size of the render target within its safe area (see
+/// `Viewport::safe_size`). The Element is centered within the safe area, which is inset from the
+/// target's edges.
+///
+/// As with `responsive`, the resulting Element takes up no space within other layouts, so is best
+/// used as the root of a tree or as a layer.
+pub fn with_safe_area<F>(f: F) -> Element
+    where F: Fn(i32, i32) -> Element + Send + Sync + 'static,
+{
+    with_viewport(move |viewport| {
+        let (w, h) = viewport.safe_size();
+        let (w, h) = (w as i32, h as i32);
+        f(w, h).container(w, h, middle()).inset_by(viewport.safe_area)
+    })
+}
+
+
+/// An empty Element with a size resolved at draw time. See `Element::size_in`.
+pub fn spacer_in<W, H>(w: W, h: H) -> Element
+    where W: Into<Length>,
+          H: Into<Length>,
+{
+    empty().size_in(w, h)
+}
+
+
+/// A function producing an Element for the viewport of the render target. See `responsive`.
+#[derive(Clone)]
+pub struct Responsive(pub Arc<dyn Fn(&Viewport) -> Element + Send + Sync>);
+
+impl Responsive {
+    /// Produce the Element for the given viewport.
+    pub fn element(&self, viewport: &Viewport) -> Element {
+        (self.0)(viewport)
+    }
+}
+
+/// Responsive Elements can't be hashed by what they produce, so they are hashed by the address of
+/// their function. Two hashes agree only while the same `Arc` is reused between them.
+impl Hash for Responsive {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (&*self.0 as *const _ as *const () as usize).hash(state);
+    }
+}
+
+impl fmt::Debug for Responsive {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Responsive")
+    }
+}
+
+
 /// The various kinds of Elements.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Hash)]
 pub enum Prim {
     Image(ImageStyle, i32, i32, PathBuf),
     Container(Position, Box<Element>),
     Flow(Direction, Vec<Element>),
     Collage(i32, i32, Vec<Form>),
     Cleared(Color, Box<Element>),
+    Fill(Color),
+    Responsive(Responsive),
     Spacer,
 }
 
 
 /// Styling for the Image Element.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ImageStyle {
     Plain,
     Fitted,
@@ -295,47 +913,606 @@ pub fn tiled_image(w: i32, h: i32, path: PathBuf) -> Element {
     new_element(w, h, Prim::Image(ImageStyle::Tiled, w, h, path))
 }
 
+/// Create an image that cycles through the given frames at the given number of frames per
+/// second, such as for an animated billboard.
+///
+/// The frame is selected at draw time from the time given to the renderer (see
+/// `RenderOptions::time`), looping once the last frame has been shown. Nothing is drawn if there
+/// are no frames.
+pub fn animated_image(w: i32, h: i32, frames: Vec<PathBuf>, fps: f64) -> Element {
+    let frame = move |viewport: &Viewport| {
+        if frames.is_empty() {
+            return empty();
+        }
+        let index = ((viewport.time * fps).floor() as i64).rem_euclid(frames.len() as i64);
+        image(w, h, frames[index as usize].clone())
+    };
+    new_element(w, h, Prim::Responsive(Responsive(Arc::new(frame))))
+}
 
-#[derive(Copy, Clone, Debug)]
+/// Create an image that plays the animated GIF at the given path, such as for an animated
+/// billboard. Only available with the `gif` feature.
+///
+/// As backends draw images from files, the GIF is decoded up front and each of its frames is
+/// written as a PNG within `frames_dir`, named after the GIF. Each frame is shown for its own
+/// delay (or a tenth of a second where the GIF gives none) and, like `animated_image`, selected
+/// from the time given to the renderer, looping once the last frame has been shown.
+#[cfg(feature = "gif")]
+pub fn animated_gif<P, Q>(w: i32, h: i32, path: P, frames_dir: Q) -> io::Result<Element>
+    where
+        P: AsRef<Path>,
+        Q: AsRef<Path>,
+{
+    let frames = decode_gif(path.as_ref(), frames_dir.as_ref())?;
+    let duration: f64 = frames.iter().map(|&(_, delay)| delay).sum();
+    let frame = move |viewport: &Viewport| {
+        let mut time = viewport.time.rem_euclid(duration);
+        for &(ref path, delay) in frames.iter() {
+            if time < delay {
+                return image(w, h, path.clone());
+            }
+            time -= delay;
+        }
+        match frames.last() {
+            Some(last) => image(w, h, last.0.clone()),
+            None => empty(),
+        }
+    };
+    Ok(new_element(w, h, Prim::Responsive(Responsive(Arc::new(frame)))))
+}
+
+/// Composite each frame of the GIF at the given path, writing it as a PNG within `frames_dir`.
+///
+/// Returns the path of each frame along with its delay in seconds.
+#[cfg(feature = "gif")]
+fn decode_gif(path: &Path, frames_dir: &Path) -> io::Result<Vec<(PathBuf, f64)>> {
+    let mut options = gif::DecodeOptions::new();
+    options.set_color_output(gif::ColorOutput::RGBA);
+    let mut decoder = options.read_info(File::open(path)?)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    let (width, height) = (decoder.width() as usize, decoder.height() as usize);
+    let name = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or("frame");
+    fs::create_dir_all(frames_dir)?;
+
+    let mut canvas = vec![0u8; width * height * 4];
+    let mut frames = Vec::new();
+    while let Some(frame) = decoder.read_next_frame()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))? {
+        let previous = canvas.clone();
+        let (left, top) = (frame.left as usize, frame.top as usize);
+        let (frame_w, frame_h) = (frame.width as usize, frame.height as usize);
+        let rows = top..height.min(top + frame_h);
+        let columns = left..width.min(left + frame_w);
+
+        // Transparent pixels leave the frames beneath them showing.
+        for y in rows.clone() {
+            for x in columns.clone() {
+                let src = ((y - top) * frame_w + x - left) * 4;
+                let dst = (y * width + x) * 4;
+                if frame.buffer[src + 3] != 0 {
+                    canvas[dst..dst + 4].copy_from_slice(&frame.buffer[src..src + 4]);
+                }
+            }
+        }
+
+        let frame_path = frames_dir.join(format!("{}-{}.png", name, frames.len()));
+        write_png(&frame_path, width as u32, height as u32, &canvas)?;
+        let delay = if frame.delay == 0 { 0.1 } else { frame.delay as f64 / 100.0 };
+        frames.push((frame_path, delay));
+
+        match frame.dispose {
+            gif::DisposalMethod::Background => for y in rows {
+                for x in columns.clone() {
+                    let dst = (y * width + x) * 4;
+                    canvas[dst..dst + 4].copy_from_slice(&[0; 4]);
+                }
+            },
+            gif::DisposalMethod::Previous => canvas = previous,
+            gif::DisposalMethod::Any | gif::DisposalMethod::Keep => (),
+        }
+    }
+    Ok(frames)
+}
+
+/// Write the given RGBA pixels to a PNG file at the given path.
+#[cfg(feature = "gif")]
+fn write_png(path: &Path, width: u32, height: u32, pixels: &[u8]) -> io::Result<()> {
+    let mut encoder = png::Encoder::new(BufWriter::new(File::create(path)?), width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(pixels)?;
+    Ok(())
+}
+
+
+#[derive(Copy, Clone, Debug, Hash)]
 pub enum Three { P, Z, N }
 #[derive(Copy, Clone, Debug)]
 pub enum Pos { Absolute(i32), Relative(f32) }
 
+/// Relative positions are hashed by the bit patterns of their fractions.
+impl Hash for Pos {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match *self {
+            Pos::Absolute(n) => {
+                0u8.hash(state);
+                n.hash(state);
+            },
+            Pos::Relative(f) => {
+                1u8.hash(state);
+                hash_f32(f, state);
+            },
+        }
+    }
+}
+
 /// An element's Position.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, Hash)]
 pub struct Position {
-    horizontal: Three,
-    vertical: Three,
-    x: Pos,
-    y: Pos,
+    pub horizontal: Three,
+    pub vertical: Three,
+    pub x: Pos,
+    pub y: Pos,
+}
+
+impl Position {
+
+    /// The offset of the center of an `Element` of size `element` from the center of a container
+    /// of size `container` when placed at this `Position`, with the y-axis pointing up.
+    ///
+    /// As in Elm, `x` and `y` are measured inward from the container's edges - from the left and
+    /// top edges for `Z`, where they locate the `Element`'s center. `Relative` distances are
+    /// fractions of the container's size.
+    pub fn offset(&self, container: (i32, i32), element: (i32, i32)) -> (f64, f64) {
+        let (c_w, c_h) = (container.0 as f64, container.1 as f64);
+        let (e_w, e_h) = (element.0 as f64, element.1 as f64);
+        let distance = |pos: Pos, dim: f64| match pos {
+            Pos::Absolute(n) => n as f64,
+            Pos::Relative(f) => f as f64 * dim,
+        };
+        let (x, y) = (distance(self.x, c_w), distance(self.y, c_h));
+        let x = match self.horizontal {
+            N => x - (c_w - e_w) / 2.0,
+            Z => x - c_w / 2.0,
+            P => (c_w - e_w) / 2.0 - x,
+        };
+        let y = match self.vertical {
+            N => y - (c_h - e_h) / 2.0,
+            Z => c_h / 2.0 - y,
+            P => (c_h - e_h) / 2.0 - y,
+        };
+        (x, y)
+    }
+
+}
+
+/// The edge along which `above_aligned`, `below_aligned` and `beside_aligned` line up elements.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Alignment { Left, Center, Right, Top, Bottom }
+
+/// How `flow_justified` distributes the leftover space of a flow between its elements.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Justify {
+    /// Equal space between the elements with none before the first or after the last.
+    SpaceBetween,
+    /// Equal space on either side of each element, so the outer gaps are half the inner gaps.
+    SpaceAround,
+    /// Equal space between the elements and before the first and after the last.
+    SpaceEvenly,
 }
 
 /// The direction for a flow of `Element`s.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, Hash)]
 pub enum Direction { Up, Down, Left, Right, In, Out }
 
 
 /// Have a list of elements flow in a particular direction. The `Direction` starts from the first
 /// element in the list. The result is an `Element`.
+///
+/// Empty elements are skipped when sizing the flow and negative dimensions are treated as zero.
 pub fn flow(dir: Direction, elements: Vec<Element>) -> Element {
-    if elements.is_empty() { return empty() }
-    let max_w = elements.iter().map(|e| e.get_width()).max().unwrap();
-    let max_h = elements.iter().map(|e| e.get_height()).max().unwrap();
-    let sum_w = elements.iter().fold(0, |total, e| total + e.get_width());
-    let sum_h = elements.iter().fold(0, |total, e| total + e.get_height());
-    let new_flow = |w: i32, h: i32| new_element(w, h, Prim::Flow(dir, elements));
-    match dir {
-        Direction::Up | Direction::Down    => new_flow(max_w, sum_h),
-        Direction::Left | Direction::Right => new_flow(sum_w, max_h),
-        Direction::In | Direction::Out     => new_flow(max_w, max_h),
-    }
+    let (w, h) = flow_size(dir, &elements);
+    new_element(w, h, Prim::Flow(dir, elements))
 }
 
 /// Layer elements on top of each other, starting from the bottom.
 pub fn layers(elements: Vec<Element>) -> Element {
-    let max_w = elements.iter().map(|e| e.get_width()).max().unwrap_or(0);
-    let max_h = elements.iter().map(|e| e.get_height()).max().unwrap_or(0);
-    new_element(max_w, max_h, Prim::Flow(outward(), elements))
+    flow(outward(), elements)
+}
+
+/// Layer elements on top of each other, starting from the bottom, with the center of each offset
+/// from the center of the stack by the given `x` and `y` (with the y-axis pointing up).
+///
+/// The stack is just large enough to contain every element about its center. Empty elements are
+/// skipped when sizing the stack.
+///
+///   stack(vec![(0.0, 0.0, card), (40.0, 30.0, badge)])
+///
+pub fn stack(elements: Vec<(f64, f64, Element)>) -> Element {
+    let (w, h) = elements.iter()
+        .filter(|&&(_, _, ref e)| !e.is_empty())
+        .fold((0, 0), |(w, h), &(x, y, ref e)| {
+            let extent = |offset: f64, dim: i32| {
+                (2.0 * offset.abs() + ::std::cmp::max(0, dim) as f64).ceil() as i32
+            };
+            (::std::cmp::max(w, extent(x, e.get_width())),
+             ::std::cmp::max(h, extent(y, e.get_height())))
+        });
+    let forms = elements.into_iter().map(|(x, y, e)| form::to_form(e).shift(x, y));
+    form::collage(w, h, forms.collect())
+}
+
+/// Have a list of elements flow in a particular direction, distributing the space left over
+/// within `total_size` (along the direction of the flow) between them as described by the given
+/// `Justify`.
+///
+/// Empty elements are skipped. If the elements don't fit within `total_size` no space is added.
+/// `In` and `Out` flows have no space to distribute and are equivalent to `flow`.
+pub fn flow_justified(dir: Direction, total_size: i32, justify: Justify, elements: Vec<Element>)
+    -> Element
+{
+    if let Direction::In | Direction::Out = dir {
+        return flow(dir, elements);
+    }
+    let elements: Vec<_> = elements.into_iter().filter(|e| !e.is_empty()).collect();
+    let is_vertical = matches!(dir, Direction::Up | Direction::Down);
+    let length = |e: &Element| {
+        let (w, h) = flow_item_size(e);
+        if is_vertical { h } else { w }
+    };
+    let gap = |n: i32| if is_vertical { spacer(0, n) } else { spacer(n, 0) };
+    let leftover = total_size - elements.iter().map(&length).sum::<i32>();
+    if leftover <= 0 || elements.is_empty() {
+        return flow(dir, elements);
+    }
+
+    // The share of the leftover space given to each of the gaps before, between and after the
+    // elements, in half gaps.
+    let n = elements.len();
+    let mut weights: Vec<f64> = (0..n + 1).map(|i| {
+        let is_end = i == 0 || i == n;
+        match justify {
+            Justify::SpaceBetween => if is_end { 0.0 } else { 2.0 },
+            Justify::SpaceAround => if is_end { 1.0 } else { 2.0 },
+            Justify::SpaceEvenly => 1.0,
+        }
+    }).collect();
+    // A single element with `SpaceBetween` has nothing to space, so the space follows it.
+    if n == 1 && justify == Justify::SpaceBetween {
+        weights[n] = 1.0;
+    }
+
+    let mut children = Vec::with_capacity(2 * n + 1);
+    let mut elements = elements.into_iter();
+    for space in distribute(leftover, &weights) {
+        if space > 0 {
+            children.push(gap(space));
+        }
+        if let Some(element) = elements.next() {
+            children.push(element);
+        }
+    }
+    flow(dir, children)
+}
+
+/// Have a list of elements flow in a particular direction, growing the elements with a weight
+/// greater than zero to fill the space left over within `total_size` (along the direction of the
+/// flow) in proportion to their weights.
+///
+/// For example, a fixed-width sidebar beside some content that takes up the rest of the window:
+///
+///   flow_flex(right(), window_width, vec![(0.0, sidebar), (1.0, content)])
+///
+/// Grown elements are resized as with `size`, scaling their content to fill them. Wrap an element
+/// within a `container` to grow the space around it instead.
+///
+/// If the elements don't fit within `total_size`, or none have a weight, no element is grown.
+/// `In` and `Out` flows are equivalent to `flow`.
+pub fn flow_flex(dir: Direction, total_size: i32, elements: Vec<(f64, Element)>) -> Element {
+    let is_vertical = match dir {
+        Direction::Up | Direction::Down => true,
+        Direction::Left | Direction::Right => false,
+        Direction::In | Direction::Out =>
+            return flow(dir, elements.into_iter().map(|(_, e)| e).collect()),
+    };
+    let length = |e: &Element| {
+        let (w, h) = flow_item_size(e);
+        if is_vertical { h } else { w }
+    };
+    let leftover = total_size - elements.iter().map(|&(_, ref e)| length(e)).sum::<i32>();
+    let weights: Vec<f64> = elements.iter().map(|&(weight, ref e)| {
+        if e.props.collapsed { 0.0 } else { weight.max(0.0) }
+    }).collect();
+    let growth = if leftover > 0 { distribute(leftover, &weights) } else { vec![0; weights.len()] };
+    let elements = elements.into_iter().zip(growth).map(|((_, e), grow)| {
+        if grow <= 0 {
+            return e;
+        }
+        let new_length = length(&e) + grow;
+        if is_vertical { e.set_height_exact(new_length) } else { e.set_width_exact(new_length) }
+    });
+    flow(dir, elements.collect())
+}
+
+/// Divide `space` into whole pixels in proportion to the given weights.
+///
+/// Rounding happens at the cumulative edges so that the parts always sum to `space`, unless all
+/// weights are zero in which case every part is zero.
+fn distribute(space: i32, weights: &[f64]) -> Vec<i32> {
+    let total_weight: f64 = weights.iter().sum();
+    if !(total_weight > 0.0) {
+        return vec![0; weights.len()];
+    }
+    let (mut weight, mut prev_edge) = (0.0, 0);
+    weights.iter().map(|w| {
+        weight += *w;
+        let edge = (space as f64 * weight / total_weight).round() as i32;
+        let part = edge - prev_edge;
+        prev_edge = edge;
+        part
+    }).collect()
+}
+
+/// A strict version of `flow` that returns an error rather than silently producing a degenerate
+/// layout, i.e. when given no non-empty elements or an element with a negative dimension.
+pub fn try_flow(dir: Direction, elements: Vec<Element>) -> Result<Element, FlowError> {
+    if let Some(i) = elements.iter().position(|e| e.get_width() < 0 || e.get_height() < 0) {
+        let (w, h) = elements[i].get_size();
+        return Err(FlowError::NegativeSize(i, w, h));
+    }
+    if elements.iter().all(|e| e.is_empty()) {
+        return Err(FlowError::Empty);
+    }
+    Ok(flow(dir, elements))
+}
+
+/// A strict version of `layers`. See `try_flow`.
+pub fn try_layers(elements: Vec<Element>) -> Result<Element, FlowError> {
+    try_flow(outward(), elements)
+}
+
+/// The size of a flow of the given elements, skipping empty elements.
+fn flow_size(dir: Direction, elements: &[Element]) -> (i32, i32) {
+    let sizes = elements.iter().filter(|e| !e.is_empty()).map(flow_item_size);
+    sizes.fold((0, 0), |(w, h), (e_w, e_h)| match dir {
+        Direction::Up | Direction::Down    => (::std::cmp::max(w, e_w), h + e_h),
+        Direction::Left | Direction::Right => (w + e_w, ::std::cmp::max(h, e_h)),
+        Direction::In | Direction::Out     => (::std::cmp::max(w, e_w), ::std::cmp::max(h, e_h)),
+    })
+}
+
+
+/// The space taken up by an element within a flow: none if it is collapsed, and never less than
+/// zero along either axis.
+fn flow_item_size(e: &Element) -> (i32, i32) {
+    if e.props.collapsed {
+        return (0, 0);
+    }
+    (::std::cmp::max(0, e.get_width()), ::std::cmp::max(0, e.get_height()))
+}
+
+
+/// The offsets of the centers of a flow's elements from the center of a flow of the given size,
+/// with the y-axis pointing up.
+///
+/// Elements are placed one after another from the edge at which the flow starts, centered upon
+/// the other axis. Elements of an `In` or `Out` flow are all centered upon the flow.
+pub fn flow_offsets(dir: Direction, size: (i32, i32), elements: &[Element]) -> Vec<(f64, f64)> {
+    let (w, h) = (size.0 as f64, size.1 as f64);
+    let mut edge = match dir {
+        Direction::Down => h / 2.0,
+        Direction::Up => -h / 2.0,
+        Direction::Right => -w / 2.0,
+        Direction::Left => w / 2.0,
+        Direction::In | Direction::Out => 0.0,
+    };
+    elements.iter().map(|e| {
+        let (e_w, e_h) = flow_item_size(e);
+        let (e_w, e_h) = (e_w as f64, e_h as f64);
+        match dir {
+            Direction::Down => { edge -= e_h; (0.0, edge + e_h / 2.0) },
+            Direction::Up => { edge += e_h; (0.0, edge - e_h / 2.0) },
+            Direction::Right => { edge += e_w; (edge - e_w / 2.0, 0.0) },
+            Direction::Left => { edge -= e_w; (edge + e_w / 2.0, 0.0) },
+            Direction::In | Direction::Out => (0.0, 0.0),
+        }
+    }).collect()
+}
+
+
+/// How `table` sizes a column.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ColumnWidth {
+    /// As wide as the column's widest cell. This is the default.
+    Fit,
+    /// The given width in pixels. Cells wider than the column are cropped.
+    Fixed(i32),
+    /// As wide as the column's widest cell, along with a share of the width left over within the
+    /// table's `width` in proportion to the given weight. See `flow_flex`.
+    Weighted(f64),
+}
+
+
+/// A table of elements laid out in rows and columns. See `table`.
+#[derive(Clone, Debug)]
+pub struct Table {
+    pub rows: Vec<Vec<Element>>,
+    /// The sizing of each column. Columns beyond the end are sized by `ColumnWidth::Fit`.
+    pub columns: Vec<ColumnWidth>,
+    /// The total width shared between `ColumnWidth::Weighted` columns, if any.
+    pub width: Option<i32>,
+    /// The space in pixels between each cell's content and the edges of its cell.
+    pub padding: i32,
+    /// The position of each cell's content within its cell.
+    pub cell_position: Position,
+    /// The style of lines drawn along the edges of every cell, if any.
+    pub grid_lines: Option<LineStyle>,
+}
+
+
+/// Lay out the given rows of elements in a table. Each column is as wide as its widest cell and
+/// each row as tall as its tallest cell, with the content centered within each cell.
+///
+/// Rows with fewer cells than others are padded with empty cells.
+pub fn table(rows: Vec<Vec<Element>>) -> Table {
+    Table {
+        rows: rows,
+        columns: Vec::new(),
+        width: None,
+        padding: 0,
+        cell_position: middle(),
+        grid_lines: None,
+    }
+}
+
+
+impl Table {
+
+    /// Size the columns in order by the given `ColumnWidth`s.
+    pub fn columns(self, columns: Vec<ColumnWidth>) -> Table {
+        Table { columns: columns, ..self }
+    }
+
+    /// Share the space left over within the given total width between the `Weighted` columns.
+    pub fn width(self, width: i32) -> Table {
+        Table { width: Some(width), ..self }
+    }
+
+    /// Pad the content of each cell by the given number of pixels on every side.
+    pub fn padding(self, padding: i32) -> Table {
+        Table { padding: padding, ..self }
+    }
+
+    /// Position the content of each cell within its cell.
+    pub fn cell_position(self, position: Position) -> Table {
+        Table { cell_position: position, ..self }
+    }
+
+    /// Draw lines of the given style along the edges of every cell, centered upon the edges.
+    pub fn grid_lines(self, style: LineStyle) -> Table {
+        Table { grid_lines: Some(style), ..self }
+    }
+
+    /// The widths of the table's columns and the heights of its rows, including padding.
+    pub fn cell_sizes(&self) -> (Vec<i32>, Vec<i32>) {
+        let n_columns = self.rows.iter().map(|row| row.len()).max().unwrap_or(0);
+        let padding = 2 * ::std::cmp::max(0, self.padding);
+        let column_width = |i: usize| self.columns.get(i).cloned().unwrap_or(ColumnWidth::Fit);
+
+        let fit_widths: Vec<i32> = (0..n_columns).map(|i| {
+            let widest = self.rows.iter()
+                .filter_map(|row| row.get(i))
+                .map(|cell| ::std::cmp::max(0, cell.get_width()))
+                .max()
+                .unwrap_or(0);
+            widest + padding
+        }).collect();
+        let mut widths: Vec<i32> = fit_widths.iter().enumerate().map(|(i, &fit)| {
+            match column_width(i) {
+                ColumnWidth::Fixed(w) => ::std::cmp::max(0, w),
+                ColumnWidth::Fit | ColumnWidth::Weighted(_) => fit,
+            }
+        }).collect();
+        if let Some(total_width) = self.width {
+            let leftover = total_width - widths.iter().sum::<i32>();
+            let weights: Vec<f64> = (0..n_columns).map(|i| match column_width(i) {
+                ColumnWidth::Weighted(weight) => weight.max(0.0),
+                ColumnWidth::Fit | ColumnWidth::Fixed(_) => 0.0,
+            }).collect();
+            if leftover > 0 {
+                for (width, grow) in widths.iter_mut().zip(distribute(leftover, &weights)) {
+                    *width += grow;
+                }
+            }
+        }
+
+        let heights = self.rows.iter().map(|row| {
+            let tallest = row.iter().map(|cell| ::std::cmp::max(0, cell.get_height())).max();
+            tallest.unwrap_or(0) + padding
+        }).collect();
+        (widths, heights)
+    }
+
+    /// Lay out the table as an `Element`.
+    pub fn into_element(self) -> Element {
+        let (widths, heights) = self.cell_sizes();
+        let Table { rows, padding, cell_position, grid_lines, .. } = self;
+        let padding = ::std::cmp::max(0, padding);
+        let total_w = widths.iter().sum::<i32>();
+        let total_h = heights.iter().sum::<i32>();
+
+        let rows = rows.into_iter().zip(heights.iter()).map(|(row, &h)| {
+            let mut cells = row.into_iter();
+            let cells = widths.iter().map(|&w| {
+                let cell = cells.next().unwrap_or_else(empty);
+                let inner_w = ::std::cmp::max(0, w - 2 * padding);
+                let inner_h = ::std::cmp::max(0, h - 2 * padding);
+                let overflows = cell.get_width() > inner_w || cell.get_height() > inner_h;
+                let cell = cell.container(inner_w, inner_h, cell_position);
+                let cell = if overflows {
+                    cell.crop_local(0.0, 0.0, inner_w as f64, inner_h as f64)
+                } else {
+                    cell
+                };
+                cell.container(w, h, middle())
+            });
+            flow(right(), cells.collect())
+        });
+        let body = flow(down(), rows.collect());
+
+        match grid_lines {
+            None => body,
+            Some(style) => {
+                let (half_w, half_h) = (total_w as f64 / 2.0, total_h as f64 / 2.0);
+                let mut lines = Vec::with_capacity(widths.len() + heights.len() + 2);
+                let mut x = -half_w;
+                lines.push(form::segment((x, -half_h), (x, half_h)));
+                for &w in &widths {
+                    x += w as f64;
+                    lines.push(form::segment((x, -half_h), (x, half_h)));
+                }
+                let mut y = half_h;
+                lines.push(form::segment((-half_w, y), (half_w, y)));
+                for &h in &heights {
+                    y -= h as f64;
+                    lines.push(form::segment((-half_w, y), (half_w, y)));
+                }
+                let lines = lines.into_iter().map(|line| form::traced(style.clone(), line));
+                layers(vec![body, form::collage(total_w, total_h, lines.collect())])
+            },
+        }
+    }
+
+}
+
+
+/// The reasons for which `try_flow` and `try_layers` may reject a layout.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum FlowError {
+    /// There were no elements, or all of the elements were empty.
+    Empty,
+    /// The element at the given index had the given negative width or height.
+    NegativeSize(usize, i32, i32),
+}
+
+impl fmt::Display for FlowError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FlowError::Empty => write!(f, "the flow has no non-empty elements"),
+            FlowError::NegativeSize(i, w, h) =>
+                write!(f, "the element at {} has a negative size ({}x{})", i, w, h),
+        }
+    }
+}
+
+impl error::Error for FlowError {
+    fn description(&self) -> &str {
+        match *self {
+            FlowError::Empty => "empty flow",
+            FlowError::NegativeSize(..) => "negative element size",
+        }
+    }
 }
 
 
@@ -376,240 +1553,149 @@ pub fn inward() -> Direction { Direction::In }
 pub fn outward() -> Direction { Direction::Out }
 
 
-
-
-
-
-
-
-/// 
-/// CUSTOM NON-ELM FUNCTIONS
-///
-
-
-
-/// Used for rendering elmesque `Element`s.
-pub struct Renderer<'a, C: 'a, G: 'a> {
-    context: Context,
-    backend: &'a mut G,
-    maybe_character_cache: Option<&'a mut C>,
+/// Whether the point is cut away from the given Element by its crops or rounded corners, where
+/// `view` is the point in view space and `local` is the point relative to the Element's center.
+fn is_clipped(element: &Element, view: (f64, f64), local: (f64, f64)) -> bool {
+    let Properties { crop, local_crop, corner_radius, width, height, .. } = element.props;
+    let outside = |(x, y, w, h): (f64, f64, f64, f64), point| {
+        !Rect::from_xywh(x, y, w, h).contains_point(point)
+    };
+    if crop.is_some_and(|rect| outside(rect, view))
+        || local_crop.is_some_and(|rect| outside(rect, local)) {
+        return true;
+    }
+    if corner_radius > 0.0 {
+        let form::Shape(outline) = form::rounded_rect(width as f64, height as f64, corner_radius);
+        return !geometry::point_in_polygon(local, &outline);
+    }
+    false
 }
 
-impl<'a, C, G> Renderer<'a, C, G> {
 
-    /// Construct a renderer, used for rendering elmesque `Element`s.
-    pub fn new(context: Context, backend: &'a mut G) -> Renderer<'a, C, G> {
-        Renderer {
-            context: context,
-            backend: backend,
-            maybe_character_cache: None,
+/// Whether the point lies over the given Element or anything nested within it, where `view` is
+/// the point in view space and `local` is the point relative to the Element's center.
+fn is_over_element(element: &Element, view: (f64, f64), local: (f64, f64)) -> bool {
+    if element.is_hidden() || is_clipped(element, view, local) {
+        return false;
+    }
+    let (width, height) = element.get_size();
+    if width > 0 && height > 0 {
+        let rect = Rect::from_xywh(0.0, 0.0, width as f64, height as f64);
+        if rect.contains_point(local) {
+            return true;
         }
     }
 
-    /// Builder method for constructing a Renderer with a GlyphCache for drawing text.
-    pub fn character_cache(self, character_cache: &'a mut C) -> Renderer<'a, C, G> {
-        Renderer { maybe_character_cache: Some(character_cache), ..self }
+    let (scale_x, scale_y) = element.content_scale();
+    if scale_x == 0.0 || scale_y == 0.0 {
+        return false;
+    }
+    let local = (local.0 / scale_x, local.1 / scale_y);
+    let size = element.content_size();
+    let is_over_child = |child: &Element, (x, y): (f64, f64)| {
+        is_over_element(child, view, (local.0 - x, local.1 - y))
+    };
+    match element.element {
+        Prim::Fill(_) => true,
+        Prim::Collage(_, _, ref forms) => forms.iter().any(|form| form.contains(local)),
+        Prim::Container(position, ref child) =>
+            is_over_child(child, position.offset(size, child.get_size())),
+        Prim::Flow(direction, ref children) => {
+            let offsets = flow_offsets(direction, size, children);
+            children.iter().zip(offsets).any(|(child, offset)| is_over_child(child, offset))
+        },
+        Prim::Cleared(_, ref child) => is_over_child(child, (0.0, 0.0)),
+        Prim::Image(..) | Prim::Responsive(_) | Prim::Spacer => false,
     }
-
 }
 
 
+/// The topmost tag under the point within the given Element, where `view` is the point in view
+/// space and `local` is the point relative to the Element's center.
+fn pick_top_element(element: &Element, view: (f64, f64), local: (f64, f64), alpha: f32,
+                    min_alpha: f32) -> Option<Tag> {
+    if element.is_hidden() || is_clipped(element, view, local) {
+        return None;
+    }
 
-/// Draw an Element.
-pub fn draw_element<'a, C: CharacterCache, G: Graphics<Texture=C::Texture>>(
-    element: &Element,
-    opacity: f32,
-    backend: &mut G,
-    maybe_character_cache: &mut Option<&mut C>,
-    context: Context,
-) {
-    let Element { ref props, ref element } = *element;
-
-    // Crop the Element if some crop was given.
-    // We'll use the `DrawState::scissor` method for this.
-    //
-    // Because `DrawState`'s `scissor` `Rect` uses bottom-left origin coords, we'll have to convert
-    // from our centered-origin coordinate system.
-    //
-    // We'll also need to stretch our coords to match the correct viewport.draw_size.
-    let context = match props.crop {
-        Some((x, y, w, h)) => {
-            use utils::{clamp, map_range};
-            let Context { draw_state, .. } = context;
-
-            // Our view_dim is our virtual window size which is consistent no matter the display.
-            let view_dim = context.get_view_size();
-
-            // Our draw_dim is the actual window size in pixels. Our target crop area must be
-            // represented in this size.
-            let draw_dim = match context.viewport {
-                Some(viewport) => [viewport.draw_size[0] as f64, viewport.draw_size[1] as f64],
-                None => view_dim,
-            };
-
-            // Calculate the distance to the edges of the window from the center.
-            let left = -view_dim[0] / 2.0;
-            let right = view_dim[0] / 2.0;
-            let bottom = -view_dim[1] / 2.0;
-            let top = view_dim[1] / 2.0;
-
-            // We start with the x and y in the center of our crop area, however we need it to be
-            // at the top left of the crop area.
-            let left_x = x - w as f64 / 2.0;
-            let top_y = y - h as f64 / 2.0;
-
-            // Map the position at the top left of the crop area in view_dim to our draw_dim.
-            let x = map_range(left_x, left, right, 0, draw_dim[0] as i32);
-            let y = map_range(top_y, bottom, top, 0, draw_dim[1] as i32);
- 
-            // Convert the w and h from our view_dim to the draw_dim.
-            let w_scale = draw_dim[0] / view_dim[0];
-            let h_scale = draw_dim[1] / view_dim[1];
-            let w = w * w_scale;
-            let h = h * h_scale;
-
-            // If we ended up with negative coords for the crop area, we'll use 0 instead as we
-            // can't represent the negative coords with `u16` (the target DrawState dimension type).
-            // We'll hold onto the lost negative values (x_neg and y_neg) so that we can compensate
-            // with the width and height.
-            let x_neg = if x < 0 { x } else { 0 };
-            let y_neg = if y < 0 { y } else { 0 };
-            let mut x = ::std::cmp::max(0, x) as u16;
-            let mut y = ::std::cmp::max(0, y) as u16;
-            let mut w = ::std::cmp::max(0, (w as i32 + x_neg)) as u16;
-            let mut h = ::std::cmp::max(0, (h as i32 + y_neg)) as u16;
-            
-            // If there was already some scissor set, we must check for the intersection.
-            if let Some(rect) = draw_state.scissor {
-                if x + w < rect.x || rect.x + rect.w < x || y + h < rect.y || rect.y + rect.h < y {
-                    // If there is no intersection, we have no scissor.
-                    w = 0;
-                    h = 0;
-                } else {
-                    // If there is some intersection, calculate the overlapping rect.
-                    let (a_l, a_r, a_b, a_t) = (x, x+w, y, y+h);
-                    let (b_l, b_r, b_b, b_t) = (rect.x, rect.x+rect.w, rect.y, rect.y+rect.h);
-                    let l = if a_l > b_l { a_l } else { b_l };
-                    let r = if a_r < b_r { a_r } else { b_r };
-                    let b = if a_b > b_b { a_b } else { b_b };
-                    let t = if a_t < b_t { a_t } else { b_t };
-                    x = l;
-                    y = b;
-                    w = r - l;
-                    h = t - b;
-                }
-            }
-
-            Context { draw_state: draw_state.scissor(x, y, w, h), ..context }
-        },
-        None => context,
+    let alpha = alpha * element.props.opacity;
+    let (scale_x, scale_y) = element.content_scale();
+    if scale_x == 0.0 || scale_y == 0.0 {
+        return None;
+    }
+    let local = (local.0 / scale_x, local.1 / scale_y);
+    let size = element.content_size();
+    let pick_child = |child: &Element, (x, y): (f64, f64)| {
+        pick_top_element(child, view, (local.0 - x, local.1 - y), alpha, min_alpha)
     };
-
-    match *element {
-
-        Prim::Image(style, w, h, ref path) => {
-            let Properties { width, height, opacity, color, .. } = *props;
-            match style {
-                ImageStyle::Plain => {
-                    // let image = graphics::Image {
-                    //     color: None,
-                    //     rectangle: None,
-                    //     source_rectangle: Some([src_x, src_y, w, h]),
-                    // };
-                    // let image = Image::new();
-                    // let texture: &Texture = ::std::ops::Deref::deref(&texture);
-                    // image.draw(texture, draw_state, matrix, backend);
-                    unimplemented!();
-                },
-                ImageStyle::Fitted => {
-                    unimplemented!();
-                },
-                ImageStyle::Cropped(x, y) => {
-                    unimplemented!();
-                },
-                ImageStyle::Tiled => {
-                    unimplemented!();
-                },
+    match element.element {
+        Prim::Collage(_, _, ref forms) => pick_top_form(forms, view, local, alpha, min_alpha),
+        Prim::Container(position, ref child) =>
+            pick_child(child, position.offset(size, child.get_size())),
+        Prim::Flow(direction, ref children) => {
+            let offsets = flow_offsets(direction, size, children);
+            let mut children: Vec<_> = children.iter().zip(offsets).collect();
+            // Children are drawn in order, other than `inward` flows which are drawn in reverse.
+            match direction {
+                Direction::In => (),
+                _ => children.reverse(),
             }
+            children.into_iter().filter_map(|(child, offset)| pick_child(child, offset)).next()
         },
+        Prim::Cleared(_, ref child) => pick_child(child, (0.0, 0.0)),
+        _ => None,
+    }
+}
 
-        Prim::Container(position, ref element) => {
-            let Position { horizontal, vertical, x, y } = position;
-            let context = match (x, y) {
-                (Pos::Relative(x), Pos::Relative(y)) => context.trans(x as f64, y as f64),
-                (Pos::Absolute(x), Pos::Relative(y)) => Context {
-                    transform: transform_2d::matrix(1.0, 0.0, 0.0, 1.0, x as f64, 0.0).0,
-                    ..context
-                }.trans(0.0, y as f64),
-                (Pos::Relative(x), Pos::Absolute(y)) => Context {
-                    transform: transform_2d::matrix(1.0, 0.0, 0.0, 1.0, 0.0, y as f64).0,
-                    ..context
-                }.trans(x as f64, 0.0),
-                (Pos::Absolute(x), Pos::Absolute(y)) => Context {
-                    transform: transform_2d::matrix(1.0, 0.0, 0.0, 1.0, x as f64, y as f64).0,
-                    ..context
-                },
-            };
-            let new_opacity = opacity * props.opacity;
-            draw_element(element, new_opacity, backend, maybe_character_cache, context);
-        }
 
-        Prim::Flow(direction, ref elements) => {
-            let mut context = context;
-            match direction {
-                Direction::Up | Direction::Down => {
-                    let multi = if let Direction::Up = direction { 1.0 } else { -1.0 };
-                    let mut half_prev_height = 0.0;
-                    for element in elements.iter() {
-                        let half_height = element.get_height() as f64 / 2.0;
-                        let new_opacity = opacity * props.opacity;
-                        draw_element(element, new_opacity, backend, maybe_character_cache, context);
-                        let y_trans = half_height + half_prev_height;
-                        context = context.trans(0.0, y_trans * multi);
-                        half_prev_height = half_height;
-                    }
+/// The topmost tag under the point among the given forms, where `local` is the point within the
+/// forms' parent's coordinates.
+fn pick_top_form(forms: &[Form], view: (f64, f64), local: (f64, f64), alpha: f32,
+                 min_alpha: f32) -> Option<Tag> {
+    forms.iter().rev().filter_map(|form| {
+        let alpha = form.inherited_alpha(alpha);
+        if !form.visible || alpha <= 0.0 || alpha < min_alpha {
+            return None;
+        }
+        let nested = form.transform().invert().and_then(|inverse| {
+            let point = geometry::transform_point(&inverse, local);
+            match form.form {
+                BasicForm::Group(ref group_transform, ref forms) => {
+                    group_transform.invert().and_then(|inverse| {
+                        let point = geometry::transform_point(&inverse, point);
+                        pick_top_form(forms, view, point, alpha, min_alpha)
+                    })
                 },
-                Direction::Left | Direction::Right => {
-                    let multi = if let Direction::Right = direction { 1.0 } else { -1.0 };
-                    let mut half_prev_width = 0.0;
-                    for element in elements.iter() {
-                        let half_width = element.get_width() as f64 / 2.0;
-                        let new_opacity = opacity * props.opacity;
-                        draw_element(element, new_opacity, backend, maybe_character_cache, context);
-                        let x_trans = half_width + half_prev_width;
-                        context = context.trans(x_trans * multi, 0.0);
-                        half_prev_width = half_width;
-                    }
+                BasicForm::Positioned(ref forms) => {
+                    forms.iter().rev().filter_map(|&(ref child_transform, ref form)| {
+                        child_transform.invert().and_then(|inverse| {
+                            let point = geometry::transform_point(&inverse, point);
+                            let form = ::std::slice::from_ref(form);
+                            pick_top_form(form, view, point, alpha, min_alpha)
+                        })
+                    }).next()
                 },
-                Direction::Out => {
-                    for element in elements.iter() {
-                        let new_opacity = opacity * props.opacity;
-                        draw_element(element, new_opacity, backend, maybe_character_cache, context);
-                    }
-                }
-                Direction::In => {
-                    for element in elements.iter().rev() {
-                        let new_opacity = opacity * props.opacity;
-                        draw_element(element, new_opacity, backend, maybe_character_cache, context);
-                    }
-                }
-            }
-        },
-
-        Prim::Collage(w, h, ref forms) => {
-            for form in forms.iter() {
-                let new_opacity = opacity * props.opacity;
-                form::draw_form(form, new_opacity, backend, maybe_character_cache, context);
+                BasicForm::Instanced(ref form, ref instances) => {
+                    instances.iter().rev().filter_map(|instance| {
+                        instance.transform().invert().and_then(|inverse| {
+                            let point = geometry::transform_point(&inverse, point);
+                            let form = ::std::slice::from_ref(&**form);
+                            pick_top_form(form, view, point, alpha, min_alpha)
+                        })
+                    }).next()
+                },
+                BasicForm::Lod(ref levels) => levels.first().and_then(|&(_, ref form)| {
+                    let form = ::std::slice::from_ref(form);
+                    pick_top_form(form, view, point, alpha, min_alpha)
+                }),
+                BasicForm::Element(ref element) =>
+                    pick_top_element(element, view, point, alpha, min_alpha),
+                _ => None,
             }
-        },
-
-        Prim::Cleared(color, ref element) => {
-            backend.clear_color(color.to_fsa());
-            draw_element(element, opacity, backend, maybe_character_cache, context);
-        },
-
-        Prim::Spacer => {},
-
-    }
+        });
+        nested.or_else(|| match form.tag {
+            Some(tag) if form.contains(local) => Some(tag),
+            _ => None,
+        })
+    }).next()
 }
-
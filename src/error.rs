@@ -0,0 +1,44 @@
+//!
+//! Errors that may occur while drawing an `Element`.
+//!
+//! Drawing stops at the first failure and the `Error` is returned to the caller, so that an
+//! application may report or ignore it rather than being torn down mid-frame.
+//!
+
+use std::error;
+use std::fmt;
+use std::path::PathBuf;
+
+
+/// The reasons for which drawing may fail.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Error {
+    /// No texture was available for the image at the given path.
+    MissingTexture(PathBuf),
+    /// Some text was drawn without a character cache.
+    MissingCharacterCache,
+    /// The backend does not support the described style.
+    UnsupportedStyle(&'static str),
+}
+
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::MissingTexture(ref path) => write!(f, "no texture for the image at {:?}", path),
+            Error::MissingCharacterCache => write!(f, "text requires a character cache"),
+            Error::UnsupportedStyle(style) => write!(f, "{} are not supported", style),
+        }
+    }
+}
+
+
+impl error::Error for Error {
+    fn description(&self) -> &str {
+        match *self {
+            Error::MissingTexture(_) => "missing texture",
+            Error::MissingCharacterCache => "missing character cache",
+            Error::UnsupportedStyle(_) => "unsupported style",
+        }
+    }
+}
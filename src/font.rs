@@ -0,0 +1,501 @@
+//!
+//! Fonts parsed with [rusttype](https://gitlab.redox-os.org/redox-os/rusttype), allowing text to
+//! be laid out, measured and converted to vector shapes without any graphics backend.
+//!
+//! This is useful for computing the size of `Text` in pure logic (i.e. when sizing `Element`s
+//! before a window exists, or within tests), as a `CharacterCache` is otherwise tied to some
+//! graphics backend.
+//!
+//! rusttype can't rasterize the color tables of emoji fonts, so color glyphs are instead given to
+//! a `FontSet` as one image per character (i.e. from a set of emoji PNGs) via
+//! `FontSet::insert_color_glyph`. Any character that its style's typefaces can't draw is then laid
+//! out as a square of the text's height, and `FontSet::color_glyph_forms` draws those squares as
+//! textured quads to accompany the `FontSet::glyph_outlines`.
+//!
+
+use element::fitted_image;
+use form::{self, Form, Shape};
+use rusttype::{self, OutlineBuilder, Scale};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use text::{self, Text};
+use utils::{cubic_bezier, quadratic_bezier};
+
+
+/// The number of line segments used to approximate each curve within a glyph outline.
+pub const CURVE_SEGMENTS: usize = 8;
+
+/// The distance from the baseline to the center of a color glyph, as a fraction of its height.
+/// This places the bottom fifth of the glyph below the baseline, roughly as an emoji font would.
+pub const COLOR_GLYPH_RISE: f64 = 0.3;
+
+
+/// A glyph positioned by `Font::layout`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct LayoutGlyph {
+    pub character: char,
+    /// The index of the `TextUnit` to which the glyph belongs.
+    pub unit: usize,
+    /// The height of the glyph's text in pixels.
+    pub height: f64,
+    /// The position of the glyph's origin along the baseline, relative to the start of the text.
+    pub x: f64,
+    /// The distance by which the glyph's unit is raised above the baseline.
+    pub baseline: f64,
+    /// The horizontal distance from this glyph's origin to the next.
+    pub advance: f64,
+    /// Whether the glyph is drawn from an image given via `FontSet::insert_color_glyph` rather
+    /// than from a font.
+    pub color: bool,
+}
+
+
+/// Some `Text` laid out along a single baseline.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Layout {
+    pub glyphs: Vec<LayoutGlyph>,
+    /// The total advance width of the text.
+    pub width: f64,
+    /// The greatest distance above the baseline of any unit's font metrics, including any raised
+    /// units.
+    pub ascent: f64,
+    /// The greatest distance below the baseline of any unit's font metrics, including any lowered
+    /// units. Typically negative.
+    pub descent: f64,
+}
+
+
+impl Layout {
+
+    /// The height of the line from its lowest descent to its highest ascent.
+    pub fn height(&self) -> f64 {
+        self.ascent - self.descent
+    }
+
+    /// The width and height of the line.
+    pub fn size(&self) -> (f64, f64) {
+        (self.width, self.height())
+    }
+
+    /// The offset from the origin to the start of the baseline when the text is drawn aligned by
+    /// the given position and anchor.
+    pub fn origin(&self, position: text::Position, anchor: text::TextAnchor) -> (f64, f64) {
+        let x = -self.width * position.fraction();
+        let y = match anchor {
+            text::TextAnchor::Baseline => 0.0,
+            text::TextAnchor::Middle => -(self.ascent + self.descent) / 2.0,
+            text::TextAnchor::Top => -self.ascent,
+            text::TextAnchor::Bottom => -self.descent,
+        };
+        (x, y)
+    }
+
+}
+
+
+/// The vertical metrics of a font at some height, in pixels relative to the baseline with the
+/// y-axis pointing up.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Metrics {
+    /// The distance from the baseline to the top of the tallest glyphs.
+    pub ascent: f64,
+    /// The distance from the baseline to the bottom of the lowest glyphs. Typically negative.
+    pub descent: f64,
+    /// The recommended gap between the descent of one line and the ascent of the next.
+    pub line_gap: f64,
+    /// The height of capital letters above the baseline, measured from the glyph "H".
+    pub cap_height: f64,
+}
+
+
+impl Metrics {
+
+    /// The recommended distance between the baselines of consecutive lines.
+    pub fn line_height(&self) -> f64 {
+        self.ascent - self.descent + self.line_gap
+    }
+
+}
+
+
+/// A TrueType or OpenType font.
+#[derive(Clone)]
+pub struct Font {
+    font: rusttype::Font<'static>,
+}
+
+
+impl fmt::Debug for Font {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Font {{ glyph_count: {} }}", self.font.glyph_count())
+    }
+}
+
+
+impl Font {
+
+    /// Parse a font from its raw data. Returns `None` if the data is not a valid font.
+    pub fn from_bytes(bytes: Vec<u8>) -> Option<Font> {
+        rusttype::Font::try_from_vec(bytes).map(|font| Font { font: font })
+    }
+
+    /// Load and parse the font at the given path.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Font> {
+        let mut bytes = Vec::new();
+        File::open(path)?.read_to_end(&mut bytes)?;
+        Font::from_bytes(bytes)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid font data"))
+    }
+
+    /// The underlying rusttype font.
+    pub fn rusttype(&self) -> &rusttype::Font<'static> {
+        &self.font
+    }
+
+    /// Lay out the given text along a single baseline, applying kerning between glyphs.
+    ///
+    /// Each unit's `typeface` is ignored in favour of this font and units without a height use
+    /// `text::DEFAULT_HEIGHT`. With the `unicode-bidi` feature the text is first reordered by
+    /// `Text::visual_order`, in which case each glyph's `unit` indexes the reordered units.
+    pub fn layout(&self, text: &Text) -> Layout {
+        #[cfg(feature = "unicode-bidi")]
+        let text = &text.visual_order();
+        layout_glyphs(text, |_, _| Some(GlyphSource::Font(self)))
+    }
+
+    /// The vertical metrics of the font at the given height in pixels.
+    ///
+    /// These may be used to align text with other forms, i.e. offsetting a `text` form by
+    /// `-cap_height / 2.0` centers capital letters upon the baseline.
+    pub fn metrics(&self, height: f64) -> Metrics {
+        let scale = Scale::uniform(height as f32);
+        let v_metrics = self.font.v_metrics(scale);
+        // rusttype's glyph space has the y-axis pointing down.
+        let cap_height = self.font.glyph('H').scaled(scale).exact_bounding_box()
+            .map_or(v_metrics.ascent as f64, |rect| -rect.min.y as f64);
+        Metrics {
+            ascent: v_metrics.ascent as f64,
+            descent: v_metrics.descent as f64,
+            line_gap: v_metrics.line_gap as f64,
+            cap_height: cap_height,
+        }
+    }
+
+    /// Whether or not the font contains a glyph for the given character.
+    pub fn has_glyph(&self, c: char) -> bool {
+        self.font.glyph(c).id().0 != 0
+    }
+
+    /// The width of the given string at the given height in pixels.
+    pub fn width(&self, height: f64, s: &str) -> f64 {
+        self.layout(&Text::from_string(s.to_string()).height(height)).width
+    }
+
+    /// The outline of the given character at the given height in pixels, with its origin on the
+    /// baseline at `origin` and the y-axis pointing up.
+    ///
+    /// All of the glyph's contours are joined into a single `Shape`, so holes (i.e. the counter
+    /// of an "o") are preserved when the shape is filled using the non-zero winding rule. Returns
+    /// `None` for glyphs without an outline, such as whitespace.
+    pub fn glyph_shape(&self, c: char, height: f64, origin: (f64, f64)) -> Option<Shape> {
+        let glyph = self.font.glyph(c).scaled(Scale::uniform(height as f32));
+        let mut outline = Outline { origin: origin, points: Vec::new(), contour_start: None };
+        if glyph.build_outline(&mut outline) && outline.points.len() > 2 {
+            outline.close();
+            Some(Shape(outline.points))
+        } else {
+            None
+        }
+    }
+
+}
+
+
+/// A set of fonts keyed by the typeface paths used within `text::Style`s.
+///
+/// Each character of some `Text` is drawn with the first font of its style's typeface chain (the
+/// `typeface` followed by the `fallback_typefaces`) that contains a glyph for it, falling back to
+/// the default font. This allows mixed-script strings (i.e. Latin with CJK and symbols) to be
+/// drawn without missing glyphs.
+///
+/// Characters that no typeface within the chain contains but that have a color glyph are drawn
+/// from the color glyph's image in preference to the default font.
+#[derive(Clone, Debug)]
+pub struct FontSet {
+    fonts: HashMap<PathBuf, Font>,
+    default: Option<Font>,
+    color_glyphs: HashMap<char, PathBuf>,
+}
+
+
+/// Where the glyph for a character is drawn from.
+#[derive(Copy, Clone)]
+enum GlyphSource<'a> {
+    Font(&'a Font),
+    /// The image given via `FontSet::insert_color_glyph`.
+    Color,
+}
+
+
+impl Default for FontSet {
+    fn default() -> FontSet {
+        FontSet::new()
+    }
+}
+
+
+impl FontSet {
+
+    /// An empty set of fonts.
+    pub fn new() -> FontSet {
+        FontSet { fonts: HashMap::new(), default: None, color_glyphs: HashMap::new() }
+    }
+
+    /// Builder method for constructing a `FontSet` with a font for characters that no typeface
+    /// within a style's chain can draw, or for styles without a typeface.
+    pub fn default_font(self, font: Font) -> FontSet {
+        FontSet { default: Some(font), ..self }
+    }
+
+    /// Use the given font for the given typeface path.
+    pub fn insert(&mut self, path: PathBuf, font: Font) {
+        self.fonts.insert(path, font);
+    }
+
+    /// Load the font at the given typeface path.
+    pub fn load<P: AsRef<Path>>(&mut self, path: P) -> io::Result<()> {
+        let font = Font::from_file(path.as_ref())?;
+        self.insert(path.as_ref().to_path_buf(), font);
+        Ok(())
+    }
+
+    /// Draw the given character from the image at the given path wherever no typeface within a
+    /// style's chain contains a glyph for it, i.e. for emoji. The image is fitted to a square of
+    /// the text's height.
+    pub fn insert_color_glyph(&mut self, c: char, path: PathBuf) {
+        self.color_glyphs.insert(c, path);
+    }
+
+    /// The font with which the given character should be drawn in the given style.
+    ///
+    /// If no font within the style's chain contains a glyph for the character, the default font
+    /// is used, followed by the first font of the chain (which will draw its "missing" glyph).
+    pub fn resolve(&self, style: &text::Style, c: char) -> Option<&Font> {
+        let mut chain = style.typeface.iter()
+            .chain(style.fallback_typefaces.iter())
+            .filter_map(|path| self.fonts.get(path));
+        chain.clone().chain(self.default.iter()).find(|font| font.has_glyph(c))
+            .or(self.default.as_ref())
+            .or_else(|| chain.next())
+    }
+
+    /// Where the glyph for the given character in the given style is drawn from: the first font
+    /// within the style's chain containing it, then its color glyph, then `resolve`.
+    fn source(&self, style: &text::Style, c: char) -> Option<GlyphSource<'_>> {
+        let in_chain = style.typeface.iter()
+            .chain(style.fallback_typefaces.iter())
+            .filter_map(|path| self.fonts.get(path))
+            .find(|font| font.has_glyph(c));
+        match in_chain {
+            Some(font) => Some(GlyphSource::Font(font)),
+            None if self.color_glyphs.contains_key(&c) => Some(GlyphSource::Color),
+            None => self.resolve(style, c).map(GlyphSource::Font),
+        }
+    }
+
+    /// The metrics of the primary font for the given style (see `resolve`) at the style's height,
+    /// or `text::DEFAULT_HEIGHT` if it has none.
+    pub fn metrics(&self, style: &text::Style) -> Option<Metrics> {
+        let font = style.typeface.iter()
+            .chain(style.fallback_typefaces.iter())
+            .filter_map(|path| self.fonts.get(path))
+            .chain(self.default.iter())
+            .next();
+        font.map(|font| font.metrics(style.height.unwrap_or(text::DEFAULT_HEIGHT)))
+    }
+
+    /// Lay out the given text along a single baseline, resolving the font of each character via
+    /// `resolve` unless it is drawn from a color glyph. Characters without any font are skipped.
+    /// See `Font::layout`.
+    pub fn layout(&self, text: &Text) -> Layout {
+        #[cfg(feature = "unicode-bidi")]
+        let text = &text.visual_order();
+        layout_glyphs(text, |style, c| self.source(style, c))
+    }
+
+    /// Convert some text into vector shapes (one per glyph), resolving the font of each
+    /// character via `resolve`. Color glyphs are left out; see `color_glyph_forms`. See
+    /// `text::glyph_outlines`.
+    pub fn glyph_outlines(&self, text: &Text) -> Vec<Shape> {
+        #[cfg(feature = "unicode-bidi")]
+        let text = &text.visual_order();
+        let layout = layout_glyphs(text, |style, c| self.source(style, c));
+        let (x, y) = layout.origin(text.position, text.anchor);
+        layout.glyphs.iter()
+            .filter(|glyph| !glyph.color)
+            .filter_map(|glyph| {
+                let font = self.resolve(&text.sequence[glyph.unit].style, glyph.character);
+                let origin = (x + glyph.x, y + glyph.baseline);
+                font.and_then(|font| font.glyph_shape(glyph.character, glyph.height, origin))
+            })
+            .collect()
+    }
+
+    /// The images of the text's color glyphs, each fitted to a square of its text's height and
+    /// positioned to match the shapes returned by `glyph_outlines`.
+    ///
+    ///   let glyphs = fonts.glyph_outlines(&text).into_iter().map(|shape| shape.filled(black()));
+    ///   group(glyphs.chain(fonts.color_glyph_forms(&text)).collect())
+    ///
+    pub fn color_glyph_forms(&self, text: &Text) -> Vec<Form> {
+        #[cfg(feature = "unicode-bidi")]
+        let text = &text.visual_order();
+        let layout = layout_glyphs(text, |style, c| self.source(style, c));
+        let (x, y) = layout.origin(text.position, text.anchor);
+        layout.glyphs.iter()
+            .filter(|glyph| glyph.color)
+            .map(|glyph| {
+                let path = self.color_glyphs[&glyph.character].clone();
+                let size = glyph.height.round() as i32;
+                let center_x = x + glyph.x + glyph.height / 2.0;
+                let center_y = y + glyph.baseline + glyph.height * COLOR_GLYPH_RISE;
+                form::to_form(fitted_image(size, size, path)).shift(center_x, center_y)
+            })
+            .collect()
+    }
+
+}
+
+
+/// Lay out text that is already in visual order, using the font given by `resolve` for each
+/// character of each unit's style.
+fn layout_glyphs<'a, F>(text: &Text, resolve: F) -> Layout
+    where F: Fn(&text::Style, char) -> Option<GlyphSource<'a>>,
+{
+    let mut layout = Layout { glyphs: Vec::new(), width: 0.0, ascent: 0.0, descent: 0.0 };
+    for (i, unit) in text.sequence.iter().enumerate() {
+        let height = unit.style.height.unwrap_or(text::DEFAULT_HEIGHT);
+        let scale = Scale::uniform(height as f32);
+        let baseline = unit.style.baseline;
+        let mut prev: Option<(&Font, rusttype::GlyphId)> = None;
+        for c in unit.string.chars() {
+            let font = match resolve(&unit.style, c) {
+                Some(GlyphSource::Font(font)) => font,
+                Some(GlyphSource::Color) => {
+                    // Color glyphs are squares spanning the glyph's height.
+                    let bottom = baseline + height * (COLOR_GLYPH_RISE - 0.5);
+                    layout.ascent = f64::max(layout.ascent, bottom + height);
+                    layout.descent = f64::min(layout.descent, bottom);
+                    prev = None;
+                    layout.glyphs.push(LayoutGlyph {
+                        character: c,
+                        unit: i,
+                        height: height,
+                        x: layout.width,
+                        baseline: baseline,
+                        advance: height,
+                        color: true,
+                    });
+                    layout.width += height;
+                    continue;
+                },
+                None => continue,
+            };
+            let v_metrics = font.font.v_metrics(scale);
+            layout.ascent = f64::max(layout.ascent, v_metrics.ascent as f64 + baseline);
+            layout.descent = f64::min(layout.descent, v_metrics.descent as f64 + baseline);
+            let glyph = font.font.glyph(c);
+            // Kerning only applies between glyphs of the same font.
+            if let Some((prev_font, prev_id)) = prev {
+                if ::std::ptr::eq(prev_font, font) {
+                    layout.width += font.font.pair_kerning(scale, prev_id, glyph.id()) as f64;
+                }
+            }
+            prev = Some((font, glyph.id()));
+            let advance = glyph.scaled(scale).h_metrics().advance_width as f64;
+            layout.glyphs.push(LayoutGlyph {
+                character: c,
+                unit: i,
+                height: height,
+                x: layout.width,
+                baseline: baseline,
+                advance: advance,
+                color: false,
+            });
+            layout.width += advance;
+        }
+    }
+    layout
+}
+
+
+/// Collects the points of a glyph's contours.
+struct Outline {
+    origin: (f64, f64),
+    points: Vec<(f64, f64)>,
+    /// The first point of the current contour.
+    contour_start: Option<(f64, f64)>,
+}
+
+
+impl Outline {
+
+    /// Map a point from rusttype's y-down glyph space.
+    fn point(&self, x: f32, y: f32) -> (f64, f64) {
+        (self.origin.0 + x as f64, self.origin.1 - y as f64)
+    }
+
+    fn last(&self) -> (f64, f64) {
+        self.points.last().cloned().unwrap_or(self.origin)
+    }
+
+}
+
+
+impl OutlineBuilder for Outline {
+
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.close();
+        let point = self.point(x, y);
+        self.contour_start = Some(point);
+        self.points.push(point);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let point = self.point(x, y);
+        self.points.push(point);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let (from, ctrl, to) = (self.last(), self.point(x1, y1), self.point(x, y));
+        for i in 1..CURVE_SEGMENTS + 1 {
+            let t = i as f64 / CURVE_SEGMENTS as f64;
+            self.points.push(quadratic_bezier(from, ctrl, to, t));
+        }
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let (from, ctrl_a) = (self.last(), self.point(x1, y1));
+        let (ctrl_b, to) = (self.point(x2, y2), self.point(x, y));
+        for i in 1..CURVE_SEGMENTS + 1 {
+            let t = i as f64 / CURVE_SEGMENTS as f64;
+            self.points.push(cubic_bezier(from, ctrl_a, ctrl_b, to, t));
+        }
+    }
+
+    /// Return to the start of the contour and then to the first point of the glyph, so that each
+    /// edge bridging between contours is cancelled out by an edge in the opposite direction.
+    fn close(&mut self) {
+        if let Some(start) = self.contour_start.take() {
+            self.points.push(start);
+            let first = self.points[0];
+            if start != first {
+                self.points.push(first);
+            }
+        }
+    }
+
+}
@@ -11,36 +11,62 @@
 //! graphics libraries. Furthermore, the y-axis points up, so shifting a form 10 units in the
 //! y-axis will move it up screen.
 //!
+//! # Creating Collages
+//! collage, CollageBuilder
+//!
 //! # Creating Forms
-//! to_form, filled, textured, gradient, outlined, traced, text, outlined_text
+//! to_form, filled, textured, gradient, outlined, traced, arc_traced, text, outlined_text, custom
+//!
+//! # Animating Forms
+//! animated, animated_sprite
 //!
 //! # Transforming Forms
-//! shift, shift_x, shift_y, scale, rotate, alpha
+//! shift, shift_x, shift_y, scale, rotate, alpha, tag, instanced, instanced_with
+//!
+//! # Levels of Detail
+//! lod, lod_level, Shape::simplified
+//!
+//! # Picking Forms
+//! Collage::pick, Collage::forms_in_rect, Collage::forms_in_rect_exact
 //!
 //! # Grouping Forms
 //! Grouping forms makes it easier to write modular graphics code. You can create a form that is a
 //! composite of many subforms. From there it is easy to transform it as a single unit.
-//! group, group_transform
+//! group, group_transform, group_positioned
 //!
 //! # Shapes
-//! rect, oval, square, circle, ngon, polygon
+//! rect, rounded_rect, oval, oval_with_resolution, square, circle, ngon, ngon_oriented, hexagon,
+//! polygon, shape_from_svg_path, rect_border
 //!
 //! # Paths
-//! segment, path
+//! segment, arc, path
 //!
 //! # Line Styles
-//! solid, dashed, dotted, LineStyle, LineCap, LineJoin
+//! solid, dashed, dotted, LineStyle, LineCap, LineJoin, StrokeAlignment, Sides
 //!
 
 
-use color::{Color, Gradient};
+use angle::Angle;
+use color::{self, Color, Gradient};
 use element::{self, Element, new_element};
-use graphics::{self, Context, Graphics, Transformed};
-use graphics::character::CharacterCache;
+use geometry::{self, Rect};
+use svg;
+use length::Viewport;
 use std::f64::consts::PI;
+use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::path::PathBuf;
+use std::sync::Arc;
 use text::Text;
 use transform_2d::{self, Transform2D};
+use utils::{hash_f32, hash_f64};
+
+#[cfg(feature = "piston-backend")]
+pub use backend::piston::{draw_form, CustomDraw, RawGraphics};
+#[cfg(feature = "piston-backend")]
+use graphics::DrawState;
+#[cfg(feature = "piston-backend")]
+use graphics::math::Matrix2d;
 
 
 /// A general, freeform 2D graphics structure.
@@ -52,10 +78,39 @@ pub struct Form {
     pub y: f64,
     pub alpha: f32,
     pub form: BasicForm,
+    /// Identifies the Form when picked. See `Form::tag`.
+    pub tag: Option<Tag>,
+    /// Whether the Form ignores the alpha of its ancestors. See `Form::isolate_alpha`.
+    pub isolate_alpha: bool,
+    /// Whether the Form is drawn. See `Form::visible`.
+    pub visible: bool,
+}
+
+/// Forms are hashed by the bit patterns of their floats. See `Element::content_hash`.
+impl Hash for Form {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let Form { theta, scale, x, y, alpha, ref form, tag, isolate_alpha, visible } = *self;
+        hash_f64(theta, state);
+        hash_f64(scale, state);
+        hash_f64(x, state);
+        hash_f64(y, state);
+        hash_f32(alpha, state);
+        form.hash(state);
+        tag.hash(state);
+        isolate_alpha.hash(state);
+        visible.hash(state);
+    }
 }
 
 
-#[derive(Clone, Debug)]
+/// An identifier given to a `Form` so that it may be recognised when picked, e.g. by
+/// `Element::pick_top`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Tag(pub u64);
+
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum FillStyle {
     Solid(Color),
     Texture(PathBuf),
@@ -63,7 +118,8 @@ pub enum FillStyle {
 }
 
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum LineCap {
     Flat,
     Round,
@@ -71,15 +127,32 @@ pub enum LineCap {
 }
 
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum LineJoin {
     Smooth,
     Sharp(f64),
     Clipped,
 }
 
+impl Eq for LineJoin {}
+
+impl Hash for LineJoin {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match *self {
+            LineJoin::Smooth => 0u8.hash(state),
+            LineJoin::Sharp(limit) => {
+                1u8.hash(state);
+                hash_f64(limit, state);
+            },
+            LineJoin::Clipped => 2u8.hash(state),
+        }
+    }
+}
+
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct LineStyle {
     pub color: Color,
     pub width: f64,
@@ -89,6 +162,22 @@ pub struct LineStyle {
     pub dash_offset: i64,
 }
 
+/// Styles are compared by value and their floats are hashed by their bit patterns, so `Eq` only
+/// holds for styles without `NaN`s.
+impl Eq for LineStyle {}
+
+impl Hash for LineStyle {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let LineStyle { color, width, cap, join, ref dashing, dash_offset } = *self;
+        color.hash(state);
+        hash_f64(width, state);
+        cap.hash(state);
+        join.hash(state);
+        dashing.hash(state);
+        dash_offset.hash(state);
+    }
+}
+
 
 impl LineStyle {
 
@@ -129,6 +218,44 @@ pub fn dotted(color: Color) -> LineStyle {
 }
 
 
+/// Where the line outlining a shape lies relative to the shape's edges. See
+/// `Shape::outlined_aligned`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum StrokeAlignment {
+    /// The line lies entirely within the shape, so outlining doesn't grow the shape.
+    Inside,
+    /// The line is centered upon the shape's edges, as with `Shape::outlined`.
+    Center,
+    /// The line lies entirely outside of the shape, leaving its area uncovered.
+    Outside,
+}
+
+
+/// Which of the sides of a rectangle to draw. See `rect_border`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Sides {
+    pub top: bool,
+    pub right: bool,
+    pub bottom: bool,
+    pub left: bool,
+}
+
+
+impl Sides {
+
+    /// Every side.
+    pub fn all() -> Sides {
+        Sides { top: true, right: true, bottom: true, left: true }
+    }
+
+    /// No sides.
+    pub fn none() -> Sides {
+        Sides { top: false, right: false, bottom: false, left: false }
+    }
+
+}
+
+
 /// The basic variants a Form can consist of.
 #[derive(Clone, Debug)]
 pub enum BasicForm {
@@ -139,14 +266,166 @@ pub enum BasicForm {
     Image(i32, i32, (i32, i32), PathBuf),
     Element(Element),
     Group(Transform2D, Vec<Form>),
+    /// A group whose forms each carry their own transform. See `group_positioned`.
+    Positioned(Vec<(Transform2D, Form)>),
+    /// A form drawn once at each of the instances. See `Form::instanced`.
+    Instanced(Box<Form>, Vec<Instance>),
+    /// Levels of detail, of which one is drawn depending upon its size on screen. See `lod`.
+    Lod(Vec<(f64, Form)>),
+    /// A form produced at draw time for the frame being drawn. See `animated`.
+    Animated(Animation),
+    /// Drawing passed straight through to the piston graphics backend. See `custom`.
+    #[cfg(feature = "piston-backend")]
+    Custom(CustomDraw),
 }
 
+impl Hash for BasicForm {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match *self {
+            BasicForm::PointPath(ref style, ref path) => {
+                0u8.hash(state);
+                style.hash(state);
+                path.hash(state);
+            },
+            BasicForm::Shape(ref style, ref shape) => {
+                1u8.hash(state);
+                style.hash(state);
+                shape.hash(state);
+            },
+            BasicForm::OutlinedText(ref style, ref text) => {
+                2u8.hash(state);
+                style.hash(state);
+                text.hash(state);
+            },
+            BasicForm::Text(ref text) => {
+                3u8.hash(state);
+                text.hash(state);
+            },
+            BasicForm::Image(w, h, src, ref path) => {
+                4u8.hash(state);
+                (w, h, src).hash(state);
+                path.hash(state);
+            },
+            BasicForm::Element(ref element) => {
+                5u8.hash(state);
+                element.hash(state);
+            },
+            BasicForm::Group(ref transform, ref forms) => {
+                6u8.hash(state);
+                transform.hash(state);
+                forms.hash(state);
+            },
+            BasicForm::Animated(ref animation) => {
+                7u8.hash(state);
+                animation.hash(state);
+            },
+            #[cfg(feature = "piston-backend")]
+            BasicForm::Custom(ref custom) => {
+                8u8.hash(state);
+                custom.hash(state);
+            },
+            BasicForm::Positioned(ref forms) => {
+                9u8.hash(state);
+                forms.hash(state);
+            },
+            BasicForm::Instanced(ref form, ref instances) => {
+                10u8.hash(state);
+                form.hash(state);
+                instances.hash(state);
+            },
+            BasicForm::Lod(ref levels) => {
+                11u8.hash(state);
+                for &(min_size, ref form) in levels.iter() {
+                    hash_f64(min_size, state);
+                    form.hash(state);
+                }
+            },
+        }
+    }
+}
 
-/// Whether a shape is outlined or filled.
-#[derive(Clone, Debug)]
+
+/// A function producing a form for the frame being drawn. See `animated`.
+#[derive(Clone)]
+pub struct Animation(pub Arc<dyn Fn(&Viewport) -> Form + Send + Sync>);
+
+impl Animation {
+    /// Produce the form for the frame described by the given viewport.
+    pub fn form(&self, viewport: &Viewport) -> Form {
+        (self.0)(viewport)
+    }
+}
+
+/// Animations can't be hashed by what they produce, so they are hashed by the address of their
+/// function.
+impl Hash for Animation {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (&*self.0 as *const _ as *const () as usize).hash(state);
+    }
+}
+
+impl fmt::Debug for Animation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Animation")
+    }
+}
+
+
+/// The placement of a single copy of an instanced form. See `Form::instanced_with`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Instance {
+    pub x: f64,
+    pub y: f64,
+    pub theta: f64,
+    pub scale: f64,
+}
+
+/// Instances are hashed by the bit patterns of their floats.
+impl Hash for Instance {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let Instance { x, y, theta, scale } = *self;
+        hash_f64(x, state);
+        hash_f64(y, state);
+        hash_f64(theta, state);
+        hash_f64(scale, state);
+    }
+}
+
+impl Instance {
+
+    /// An instance at the given position, neither rotated nor scaled.
+    pub fn at(x: f64, y: f64) -> Instance {
+        Instance { x: x, y: y, theta: 0.0, scale: 1.0 }
+    }
+
+    /// The instance rotated by the given `Angle` (or radians).
+    pub fn rotate<A: Into<Angle>>(self, theta: A) -> Instance {
+        Instance { theta: self.theta + theta.into().radians(), ..self }
+    }
+
+    /// The instance scaled by the given factor.
+    pub fn scale(self, scale: f64) -> Instance {
+        Instance { scale: self.scale * scale, ..self }
+    }
+
+    /// The transform mapping the instanced form's coordinates into those of its parent, composed
+    /// in the same order as `Form::transform`.
+    pub fn transform(&self) -> Transform2D {
+        transform_2d::translation(self.x, self.y)
+            .multiply(transform_2d::scale(self.scale))
+            .multiply(transform_2d::rotation(self.theta))
+    }
+
+}
+
+
+/// Whether a shape is outlined, filled or both.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum ShapeStyle {
     Line(LineStyle),
     Fill(FillStyle),
+    /// Filled and then outlined. See `Shape::styled`.
+    FillAndLine(FillStyle, LineStyle),
 }
 
 
@@ -160,6 +439,9 @@ impl Form {
             y: 0.0,
             alpha: 1.0,
             form: basic_form,
+            tag: None,
+            isolate_alpha: false,
+            visible: true,
         }
     }
 
@@ -196,11 +478,12 @@ impl Form {
     }
 
 
-    /// Rotate a form by a given angle. Rotate takes radians and turns things counterclockwise.
-    /// So to turn `form` 30 degrees to the left you would say `rotate(degrees(30), form)`.
+    /// Rotate a form by a given angle. Rotate takes an `Angle` (or radians) and turns things
+    /// counterclockwise. So to turn `form` 30 degrees to the left you would say
+    /// `form.rotate(Angle::deg(30.0))`.
     #[inline]
-    pub fn rotate(self, theta: f64) -> Form {
-        Form { theta: self.theta + theta, ..self }
+    pub fn rotate<A: Into<Angle>>(self, theta: A) -> Form {
+        Form { theta: self.theta + theta.into().radians(), ..self }
     }
 
 
@@ -210,6 +493,326 @@ impl Form {
         Form { alpha: alpha, ..self }
     }
 
+
+    /// Draw the Form with its own alpha alone, ignoring the alpha of any groups or collages in
+    /// which it resides. Its children still inherit its alpha as usual.
+    ///
+    /// Useful for keeping overlays such as a HUD opaque within a scene that is being faded.
+    #[inline]
+    pub fn isolate_alpha(self) -> Form {
+        Form { isolate_alpha: true, ..self }
+    }
+
+
+    /// Show or hide the Form. Hidden forms (and any forms grouped within them) are neither drawn
+    /// nor picked, but keep their place within the collage, so they may be shown again without
+    /// rebuilding the forms around them. The default is visible.
+    #[inline]
+    pub fn visible(self, visible: bool) -> Form {
+        Form { visible: visible, ..self }
+    }
+
+
+    /// The alpha at which the Form is drawn, given the alpha inherited from its ancestors.
+    #[inline]
+    pub fn inherited_alpha(&self, ancestors_alpha: f32) -> f32 {
+        if self.isolate_alpha { self.alpha } else { ancestors_alpha * self.alpha }
+    }
+
+
+    /// Tag a Form so that it may be identified when picked. Untagged forms are ignored by
+    /// `Element::pick_top`.
+    #[inline]
+    pub fn tag(self, tag: Tag) -> Form {
+        Form { tag: Some(tag), ..self }
+    }
+
+
+    /// Draw the Form once at each of the given positions, e.g. for particles or the tiles of a
+    /// map.
+    ///
+    /// The Form's geometry is shared by every instance rather than copied, and backends draw the
+    /// same geometry with each instance's transform. The resulting Form may itself be shifted,
+    /// rotated and scaled as a whole.
+    pub fn instanced(self, positions: &[(f64, f64)]) -> Form {
+        let instances = positions.iter().map(|&(x, y)| Instance::at(x, y)).collect();
+        self.instanced_with(instances)
+    }
+
+
+    /// As `instanced`, but where each instance may also be rotated and scaled.
+    pub fn instanced_with(self, instances: Vec<Instance>) -> Form {
+        Form::new(BasicForm::Instanced(Box::new(self), instances))
+    }
+
+
+    /// The transform described by the Form's position, scale and rotation, mapping the Form's own
+    /// coordinates into those of its parent.
+    pub fn transform(&self) -> Transform2D {
+        transform_2d::translation(self.x, self.y)
+            .multiply(transform_2d::scale(self.scale))
+            .multiply(transform_2d::rotation(self.theta))
+    }
+
+
+    /// The axis-aligned bounds of the Form within its parent's coordinates, including the width
+    /// of any lines.
+    ///
+    /// Returns `None` for text (which can't be measured without a font) and for empty shapes and
+    /// groups.
+    pub fn bounds(&self) -> Option<Rect> {
+        let transform = self.transform();
+        let points_bounds = |points: &[(f64, f64)], pad: f64| {
+            Rect::from_points(points.iter().map(|&p| geometry::transform_point(&transform, p)))
+                .map(|rect| rect.pad(pad * self.scale.abs()))
+        };
+        match self.form {
+            BasicForm::PointPath(ref style, PointPath(ref points)) |
+            BasicForm::Shape(ShapeStyle::Line(ref style), Shape(ref points)) |
+            BasicForm::Shape(ShapeStyle::FillAndLine(_, ref style), Shape(ref points)) =>
+                points_bounds(points, style.width / 2.0),
+            BasicForm::Shape(ShapeStyle::Fill(_), Shape(ref points)) => points_bounds(points, 0.0),
+            BasicForm::OutlinedText(..) | BasicForm::Text(_) | BasicForm::Animated(_) => None,
+            #[cfg(feature = "piston-backend")]
+            BasicForm::Custom(_) => None,
+            BasicForm::Image(w, h, _, _) =>
+                Some(Rect::from_xywh(0.0, 0.0, w as f64, h as f64).transform(&transform)),
+            BasicForm::Element(ref element) => {
+                let (w, h) = (element.get_width() as f64, element.get_height() as f64);
+                Some(Rect::from_xywh(0.0, 0.0, w, h).transform(&transform))
+            },
+            BasicForm::Group(ref group_transform, ref forms) => {
+                let transform = transform.multiply(group_transform.clone());
+                forms.iter()
+                    .filter_map(|form| form.bounds())
+                    .map(|rect| rect.transform(&transform))
+                    .fold(None, |bounds: Option<Rect>, rect| match bounds {
+                        Some(bounds) => Some(bounds.union(&rect)),
+                        None => Some(rect),
+                    })
+            },
+            BasicForm::Positioned(ref forms) => {
+                forms.iter()
+                    .filter_map(|&(ref child_transform, ref form)| {
+                        let transform = transform.clone().multiply(child_transform.clone());
+                        form.bounds().map(|rect| rect.transform(&transform))
+                    })
+                    .fold(None, |bounds: Option<Rect>, rect| match bounds {
+                        Some(bounds) => Some(bounds.union(&rect)),
+                        None => Some(rect),
+                    })
+            },
+            BasicForm::Instanced(ref form, ref instances) => {
+                form.bounds().and_then(|rect| instances.iter()
+                    .map(|instance| {
+                        rect.transform(&transform.clone().multiply(instance.transform()))
+                    })
+                    .fold(None, |bounds: Option<Rect>, rect| match bounds {
+                        Some(bounds) => Some(bounds.union(&rect)),
+                        None => Some(rect),
+                    }))
+            },
+            BasicForm::Lod(ref levels) => match levels.first() {
+                Some(&(_, ref form)) => form.bounds().map(|rect| rect.transform(&transform)),
+                None => None,
+            },
+        }
+    }
+
+
+    /// Whether or not the given point (within the Form's parent's coordinates) lies upon the
+    /// Form.
+    ///
+    /// Filled shapes, images and elements contain the points within their area, while lines and
+    /// outlines contain the points within half of the line width. Text and hidden forms never
+    /// contain a point.
+    pub fn contains(&self, point: (f64, f64)) -> bool {
+        if !self.visible {
+            return false;
+        }
+        if !self.bounds().is_some_and(|bounds| bounds.contains_point(point)) {
+            return false;
+        }
+        let transform = self.transform();
+        let to_parent = |points: &[(f64, f64)]| -> Vec<(f64, f64)> {
+            points.iter().map(|&p| geometry::transform_point(&transform, p)).collect()
+        };
+        let near_line = |points: Vec<(f64, f64)>, closed: bool, width: f64| {
+            let half_width = width * self.scale.abs() / 2.0;
+            let n = points.len();
+            let segments = if closed && n > 2 { n } else { n.saturating_sub(1) };
+            (0..segments).any(|i| {
+                let (a, b) = (points[i], points[(i + 1) % n]);
+                geometry::distance_to_segment(point, a, b) <= half_width
+            })
+        };
+        match self.form {
+            BasicForm::PointPath(ref style, PointPath(ref points)) =>
+                near_line(to_parent(points), false, style.width),
+            BasicForm::Shape(ShapeStyle::Line(ref style), Shape(ref points)) =>
+                near_line(to_parent(points), true, style.width),
+            BasicForm::Shape(ShapeStyle::Fill(_), Shape(ref points)) =>
+                geometry::point_in_polygon(point, &to_parent(points)),
+            BasicForm::Shape(ShapeStyle::FillAndLine(_, ref style), Shape(ref points)) => {
+                let points = to_parent(points);
+                geometry::point_in_polygon(point, &points) || near_line(points, true, style.width)
+            },
+            BasicForm::OutlinedText(..) | BasicForm::Text(_) | BasicForm::Animated(_) => false,
+            #[cfg(feature = "piston-backend")]
+            BasicForm::Custom(_) => false,
+            BasicForm::Image(w, h, _, _) => {
+                let corners = Rect::from_xywh(0.0, 0.0, w as f64, h as f64).corners();
+                geometry::point_in_polygon(point, &to_parent(&corners))
+            },
+            BasicForm::Element(ref element) => {
+                let (w, h) = (element.get_width() as f64, element.get_height() as f64);
+                let corners = Rect::from_xywh(0.0, 0.0, w, h).corners();
+                geometry::point_in_polygon(point, &to_parent(&corners))
+            },
+            BasicForm::Group(ref group_transform, ref forms) => {
+                let transform = transform.multiply(group_transform.clone());
+                match transform.invert() {
+                    Some(inverse) => {
+                        let local = geometry::transform_point(&inverse, point);
+                        forms.iter().any(|form| form.contains(local))
+                    },
+                    None => false,
+                }
+            },
+            BasicForm::Positioned(ref forms) => {
+                forms.iter().any(|&(ref child_transform, ref form)| {
+                    match transform.clone().multiply(child_transform.clone()).invert() {
+                        Some(inverse) => form.contains(geometry::transform_point(&inverse, point)),
+                        None => false,
+                    }
+                })
+            },
+            BasicForm::Instanced(ref form, ref instances) => {
+                instances.iter().any(|instance| {
+                    match transform.clone().multiply(instance.transform()).invert() {
+                        Some(inverse) => form.contains(geometry::transform_point(&inverse, point)),
+                        None => false,
+                    }
+                })
+            },
+            BasicForm::Lod(ref levels) => match (levels.first(), transform.invert()) {
+                (Some(&(_, ref form)), Some(inverse)) =>
+                    form.contains(geometry::transform_point(&inverse, point)),
+                _ => false,
+            },
+        }
+    }
+
+
+
+    /// Whether or not the Form overlaps the other Form, where both Forms reside within the same
+    /// coordinate system (i.e. the same collage).
+    ///
+    /// Lines and outlines are treated as having their full width. Text never intersects anything.
+    pub fn intersects(&self, other: &Form) -> bool {
+        match (self.bounds(), other.bounds()) {
+            (Some(a), Some(b)) if a.intersects(&b) => (),
+            _ => return false,
+        }
+        let (mut a, mut b) = (Vec::new(), Vec::new());
+        self.collision_outlines(&transform_2d::identity(), &mut a);
+        other.collision_outlines(&transform_2d::identity(), &mut b);
+        a.iter().any(|a| b.iter().any(|b| a.overlaps(b)))
+    }
+
+
+    /// Collect the outlines of the Form's geometry within the coordinates described by `parent`.
+    fn collision_outlines(&self, parent: &Transform2D, outlines: &mut Vec<CollisionOutline>) {
+        let transform = parent.clone().multiply(self.transform());
+        let mut push = |points: &[(f64, f64)], closed: bool, filled: bool, width: f64| {
+            outlines.push(CollisionOutline {
+                points: points.iter().map(|&p| geometry::transform_point(&transform, p)).collect(),
+                closed: closed,
+                filled: filled,
+                radius: width * geometry::transform_scale(&transform) / 2.0,
+            });
+        };
+        match self.form {
+            BasicForm::PointPath(ref style, PointPath(ref points)) =>
+                push(points, false, false, style.width),
+            BasicForm::Shape(ShapeStyle::Line(ref style), Shape(ref points)) =>
+                push(points, true, false, style.width),
+            BasicForm::Shape(ShapeStyle::Fill(_), Shape(ref points)) =>
+                push(points, true, true, 0.0),
+            BasicForm::Shape(ShapeStyle::FillAndLine(_, ref style), Shape(ref points)) =>
+                push(points, true, true, style.width),
+            BasicForm::OutlinedText(..) | BasicForm::Text(_) | BasicForm::Animated(_) => (),
+            #[cfg(feature = "piston-backend")]
+            BasicForm::Custom(_) => (),
+            BasicForm::Image(w, h, _, _) =>
+                push(&Rect::from_xywh(0.0, 0.0, w as f64, h as f64).corners(), true, true, 0.0),
+            BasicForm::Element(ref element) => {
+                let (w, h) = (element.get_width() as f64, element.get_height() as f64);
+                push(&Rect::from_xywh(0.0, 0.0, w, h).corners(), true, true, 0.0);
+            },
+            BasicForm::Group(ref group_transform, ref forms) => {
+                let transform = transform.clone().multiply(group_transform.clone());
+                for form in forms.iter() {
+                    form.collision_outlines(&transform, outlines);
+                }
+            },
+            BasicForm::Positioned(ref forms) => {
+                for &(ref child_transform, ref form) in forms.iter() {
+                    form.collision_outlines(&transform.clone().multiply(child_transform.clone()),
+                                            outlines);
+                }
+            },
+            BasicForm::Instanced(ref form, ref instances) => {
+                for instance in instances.iter() {
+                    form.collision_outlines(&transform.clone().multiply(instance.transform()),
+                                            outlines);
+                }
+            },
+            BasicForm::Lod(ref levels) => {
+                if let Some(&(_, ref form)) = levels.first() {
+                    form.collision_outlines(&transform, outlines);
+                }
+            },
+        }
+    }
+}
+
+
+/// The geometry of a single primitive used for collision tests.
+struct CollisionOutline {
+    points: Vec<(f64, f64)>,
+    closed: bool,
+    filled: bool,
+    /// Half of the width of the outline.
+    radius: f64,
+}
+
+
+impl CollisionOutline {
+
+    /// The segments along the outline.
+    fn segments(&self) -> Vec<((f64, f64), (f64, f64))> {
+        let n = self.points.len();
+        let count = if self.closed && n > 2 { n } else { n.saturating_sub(1) };
+        (0..count).map(|i| (self.points[i], self.points[(i + 1) % n])).collect()
+    }
+
+    fn overlaps(&self, other: &CollisionOutline) -> bool {
+        if self.points.is_empty() || other.points.is_empty() {
+            return false;
+        }
+        // Either outline may lie entirely within the other's area.
+        if (self.filled && geometry::point_in_polygon(other.points[0], &self.points))
+            || (other.filled && geometry::point_in_polygon(self.points[0], &other.points)) {
+            return true;
+        }
+        let reach = self.radius + other.radius;
+        let other_segments = other.segments();
+        self.segments().into_iter().any(|(a1, a2)| other_segments.iter().any(|&(b1, b2)| {
+            geometry::distance_between_segments(a1, a2, b1, b2) <= reach
+        }))
+    }
+
 }
 
 
@@ -233,6 +836,50 @@ pub fn group_transform(matrix: Transform2D, forms: Vec<Form>) -> Form {
 }
 
 
+/// Flatten many forms into a single `Form`, applying each form's matrix transformation to it
+/// alone.
+///
+/// This is cheaper than wrapping each form within its own `group_transform`, e.g. when placing
+/// many copies of a template form.
+pub fn group_positioned(forms: Vec<(Transform2D, Form)>) -> Form {
+    Form::new(BasicForm::Positioned(forms))
+}
+
+
+/// A form drawn with one of many levels of detail, depending upon how large it appears on
+/// screen, e.g. so that a zoomed out camera draws simplified versions of complex shapes.
+///
+/// Each level is paired with the minimum size at which it is drawn, and the levels should be
+/// ordered from the most to the least detailed. The size on screen is the larger of the width
+/// and height of the first level's bounds once transformed into view space. The first level
+/// whose minimum size is met is drawn, or the last level if none are. The first level is also
+/// the one used for picking and collision tests.
+///
+///   let shape = oval_with_resolution(200.0, 100.0, 200);
+///   lod(vec![
+///       (100.0, shape.clone().filled(blue())),
+///       (20.0, shape.clone().simplified(1.0).filled(blue())),
+///       (0.0, shape.simplified(4.0).filled(blue())),
+///   ])
+///
+pub fn lod(levels: Vec<(f64, Form)>) -> Form {
+    Form::new(BasicForm::Lod(levels))
+}
+
+
+/// The level of detail of a `lod` form to draw, where `scale` is the amount by which its
+/// coordinates are scaled into view space (see `geometry::transform_scale`).
+pub fn lod_level(levels: &[(f64, Form)], scale: f64) -> Option<&Form> {
+    let size = levels.first()
+        .and_then(|&(_, ref form)| form.bounds())
+        .map_or(f64::INFINITY, |rect| rect.width().max(rect.height()) * scale.abs());
+    levels.iter()
+        .find(|&&(min_size, _)| size >= min_size)
+        .or(levels.last())
+        .map(|&(_, ref form)| form)
+}
+
+
 /// Trace a path with a given line style.
 pub fn traced(style: LineStyle, path: PointPath) -> Form {
     Form::new(BasicForm::PointPath(style, path))
@@ -245,23 +892,308 @@ pub fn line(style: LineStyle, x1: f64, y1: f64, x2: f64, y2: f64) -> Form {
 }
 
 
+/// Trace an open circular arc of the given radius, centered on the origin, with a given line
+/// style. Useful for progress rings and gauges.
+///
+/// The arc runs from the `start` angle to the `end` angle in radians, counter-clockwise from the
+/// positive x-axis, or clockwise if `end` is less than `start`. See `arc`.
+pub fn arc_traced(style: LineStyle, radius: f64, start: f64, end: f64) -> Form {
+    traced(style, arc(radius, start, end))
+}
+
+
 /// Create a sprite from a sprite sheet. It cuts out a rectangle at a given position.
 pub fn sprite(w: i32, h: i32, pos: (i32, i32), path: PathBuf) -> Form {
     Form::new(BasicForm::Image(w, h, pos, path))
 }
 
 
+/// Create a form that is produced at draw time for the frame being drawn, so that animations
+/// needn't be rebuilt into the collage every frame.
+///
+/// The function is given the `Viewport` of the frame, whose `time` and `dt` are those given to
+/// the renderer (see `RenderOptions::time`). The form it produces is drawn within this one, so
+/// this form's transform and alpha apply to it. As the form isn't known until drawn, animated
+/// forms are skipped by bounds, hit testing and picking.
+pub fn animated<F>(f: F) -> Form
+    where F: Fn(&Viewport) -> Form + Send + Sync + 'static,
+{
+    Form::new(BasicForm::Animated(Animation(Arc::new(f))))
+}
+
+
+/// Create an animation from a sprite sheet, cycling through sprites of the given size at each of
+/// the given positions at the given number of frames per second.
+pub fn animated_sprite(w: i32, h: i32, frames: Vec<(i32, i32)>, fps: f64, path: PathBuf) -> Form {
+    animated(move |viewport| {
+        if frames.is_empty() {
+            return group(vec![]);
+        }
+        let index = ((viewport.time * fps).floor() as i64).rem_euclid(frames.len() as i64);
+        sprite(w, h, frames[index as usize], path.clone())
+    })
+}
+
+
+/// Create a form that draws with the given function, for anything the other forms can't
+/// describe such as a mesh.
+///
+/// The function is given the transform accumulated from the form and all of its ancestors and the
+/// `DrawState` (including any crop) at its place within the collage, so that its drawing lines up
+/// with the forms around it. Only the piston `Renderer` draws custom forms - other backends skip
+/// them, as do bounds, hit testing and picking. The form's alpha is only used to skip drawing it
+/// altogether when it's invisible.
+#[cfg(feature = "piston-backend")]
+pub fn custom<F>(draw: F) -> Form
+    where F: Fn(Matrix2d, &DrawState, &mut dyn RawGraphics) + Send + Sync + 'static,
+{
+    Form::new(BasicForm::Custom(CustomDraw(Arc::new(draw))))
+}
+
+
 /// A collage is a collection of 2D forms. There are no strict positioning relationships between
 /// forms, so you are free to do all kinds of 2D graphics.
+///
+/// The forms are laid out within the given width and height. If the resulting `Element` is later
+/// resized, the forms are scaled along with it.
 pub fn collage(w: i32, h: i32, forms: Vec<Form>) -> Element {
     new_element(w, h, element::Prim::Collage(w, h, forms))
 }
 
 
+/// Builds a collage `Element` incrementally, as an alternative to collecting every `Form` up
+/// front for `collage`.
+///
+///   let scene = CollageBuilder::new(400, 300)
+///       .background(black())
+///       .camera(player_x, player_y, 2.0)
+///       .add(player)
+///       .add_all(enemies)
+///       .into_element();
+///
+#[derive(Clone, Debug)]
+pub struct CollageBuilder {
+    pub width: i32,
+    pub height: i32,
+    pub forms: Vec<Form>,
+    pub background: Option<Color>,
+    /// The point at the center of the collage and the zoom factor. See `camera`.
+    pub camera: Option<(f64, f64, f64)>,
+}
+
+
+impl CollageBuilder {
+
+    /// Begin building a collage of the given width and height with no forms.
+    pub fn new(w: i32, h: i32) -> CollageBuilder {
+        CollageBuilder { width: w, height: h, forms: Vec::new(), background: None, camera: None }
+    }
+
+    /// Add a form, drawn above those already added.
+    pub fn add(mut self, form: Form) -> Self {
+        self.forms.push(form);
+        self
+    }
+
+    /// Add every form yielded by the iterator, in order, above those already added.
+    pub fn add_all<I>(mut self, forms: I) -> Self
+        where I: IntoIterator<Item=Form>,
+    {
+        self.forms.extend(forms);
+        self
+    }
+
+    /// Fill the collage's area with the given color behind its forms.
+    pub fn background(mut self, color: Color) -> Self {
+        self.background = Some(color);
+        self
+    }
+
+    /// View the forms through a camera centered on the point `(x, y)` and scaled by `zoom`, so
+    /// that the point appears at the center of the collage.
+    pub fn camera(mut self, x: f64, y: f64, zoom: f64) -> Self {
+        self.camera = Some((x, y, zoom));
+        self
+    }
+
+    /// Produce the collage `Element`.
+    ///
+    /// When a camera is set, the forms are wrapped in a single `group_transform`, which picking
+    /// via `Collage::pick` sees through.
+    pub fn into_element(self) -> Element {
+        let CollageBuilder { width, height, forms, background, camera } = self;
+        let forms = match camera {
+            Some((x, y, zoom)) => {
+                let view = transform_2d::scale(zoom).multiply(transform_2d::translation(-x, -y));
+                vec![group_transform(view, forms)]
+            },
+            None => forms,
+        };
+        let element = collage(width, height, forms);
+        match background {
+            Some(color) => element.color(color),
+            None => element,
+        }
+    }
+
+}
+
+
+/// A view of the forms of a collage, used to query them.
+#[derive(Copy, Clone, Debug)]
+pub struct Collage<'a> {
+    pub forms: &'a [Form],
+}
+
+
+impl<'a> Collage<'a> {
+
+    /// View the given forms as a collage.
+    pub fn new(forms: &'a [Form]) -> Collage<'a> {
+        Collage { forms: forms }
+    }
+
+    /// View the forms of the given collage `Element`.
+    ///
+    /// Returns `None` if the `Element` is not a collage.
+    pub fn from_element(element: &'a Element) -> Option<Collage<'a>> {
+        match element.element {
+            element::Prim::Collage(_, _, ref forms) => Some(Collage::new(forms)),
+            _ => None,
+        }
+    }
+
+    /// The forms lying under the given point within the collage's coordinates, topmost (i.e. last
+    /// drawn) first.
+    ///
+    /// Groups and `Element`s wrapped via `to_form` are searched too, with the point mapped through
+    /// the inverse of every transform between the collage and each nested form. A nested form is
+    /// listed before the form that contains it, so the first form is always the most specific.
+    pub fn pick(&self, x: f64, y: f64) -> Vec<&'a Form> {
+        let mut picked = Vec::new();
+        pick_forms(self.forms, (x, y), &mut picked);
+        picked
+    }
+
+    /// The tags of the collage's tagged forms whose bounds intersect the given rectangle, in the
+    /// order in which they are drawn, e.g. for marquee selection.
+    ///
+    /// Only the collage's own forms are considered, so a tagged `group` is selected as a whole.
+    /// Forms without bounds (i.e. text) are never selected.
+    pub fn forms_in_rect(&self, rect: &Rect) -> Vec<Tag> {
+        self.forms.iter()
+            .filter(|form| form.bounds().is_some_and(|bounds| bounds.intersects(rect)))
+            .filter_map(|form| form.tag)
+            .collect()
+    }
+
+    /// As `forms_in_rect`, but only selecting forms whose geometry actually overlaps the
+    /// rectangle rather than just their bounds (see `Form::intersects`).
+    pub fn forms_in_rect_exact(&self, rect: &Rect) -> Vec<Tag> {
+        let (x, y) = rect.center();
+        let marquee = self::rect(rect.width(), rect.height()).filled(color::black()).shift(x, y);
+        self.forms.iter()
+            .filter(|form| form.tag.is_some() && form.intersects(&marquee))
+            .filter_map(|form| form.tag)
+            .collect()
+    }
+
+}
+
+
+/// Push the forms lying under the point (within the forms' parent's coordinates) onto `picked`,
+/// topmost first.
+fn pick_forms<'a>(forms: &'a [Form], point: (f64, f64), picked: &mut Vec<&'a Form>) {
+    for form in forms.iter().rev().filter(|form| form.visible) {
+        let nested = picked.len();
+        if let Some(inverse) = form.transform().invert() {
+            let local = geometry::transform_point(&inverse, point);
+            match form.form {
+                BasicForm::Group(ref group_transform, ref forms) => {
+                    if let Some(inverse) = group_transform.invert() {
+                        pick_forms(forms, geometry::transform_point(&inverse, local), picked);
+                    }
+                },
+                BasicForm::Positioned(ref forms) => {
+                    for &(ref child_transform, ref form) in forms.iter().rev() {
+                        if let Some(inverse) = child_transform.invert() {
+                            let point = geometry::transform_point(&inverse, local);
+                            pick_forms(::std::slice::from_ref(form), point, picked);
+                        }
+                    }
+                },
+                BasicForm::Instanced(ref form, ref instances) => {
+                    for instance in instances.iter().rev() {
+                        if let Some(inverse) = instance.transform().invert() {
+                            let point = geometry::transform_point(&inverse, local);
+                            pick_forms(::std::slice::from_ref(&**form), point, picked);
+                        }
+                    }
+                },
+                BasicForm::Lod(ref levels) => {
+                    if let Some(&(_, ref form)) = levels.first() {
+                        pick_forms(::std::slice::from_ref(form), local, picked);
+                    }
+                },
+                BasicForm::Element(ref element) => pick_element(element, local, picked),
+                _ => (),
+            }
+        }
+        if picked.len() > nested || form.contains(point) {
+            picked.push(form);
+        }
+    }
+}
+
+
+/// Push the forms of any collages under the point (relative to the center of the `Element`)
+/// onto `picked`, topmost first.
+fn pick_element<'a>(element: &'a Element, (x, y): (f64, f64), picked: &mut Vec<&'a Form>) {
+    use element::{Direction, Prim};
+    if element.is_hidden() {
+        return;
+    }
+    let (scale_x, scale_y) = element.content_scale();
+    if scale_x == 0.0 || scale_y == 0.0 {
+        return;
+    }
+    let point = (x / scale_x, y / scale_y);
+    let size = element.content_size();
+    let offset = |(x, y): (f64, f64), (dx, dy): (f64, f64)| (x - dx, y - dy);
+    match element.element {
+        Prim::Collage(_, _, ref forms) => pick_forms(forms, point, picked),
+        Prim::Container(position, ref child) => {
+            let child_offset = position.offset(size, child.get_size());
+            pick_element(child, offset(point, child_offset), picked);
+        },
+        Prim::Flow(direction, ref children) => {
+            let offsets = element::flow_offsets(direction, size, children);
+            let mut children: Vec<_> = children.iter().zip(offsets).collect();
+            // Children are drawn in order, other than `inward` flows which are drawn in reverse.
+            match direction {
+                Direction::In => (),
+                _ => children.reverse(),
+            }
+            for (child, child_offset) in children {
+                pick_element(child, offset(point, child_offset), picked);
+            }
+        },
+        Prim::Cleared(_, ref child) => pick_element(child, point, picked),
+        _ => (),
+    }
+}
+
+
 /// A path described by a sequence of points.
 #[derive(Clone, Debug)]
 pub struct PointPath(pub Vec<(f64, f64)>);
 
+impl Hash for PointPath {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_points(&self.0, state);
+    }
+}
+
 
 /// Create a PointPath that follows a sequence of points.
 pub fn point_path(points: Vec<(f64, f64)>) -> PointPath {
@@ -275,10 +1207,48 @@ pub fn segment(a: (f64, f64), b: (f64, f64)) -> PointPath {
 }
 
 
+/// Create a PointPath along a circular arc of the given radius centered on the origin, from the
+/// `start` angle to the `end` angle in radians.
+///
+/// The arc is approximated with the same density of segments as `circle`, and is never closed -
+/// an arc from `0.0` to `2.0 * PI` ends where it starts. Arcs sweeping more than one turn are
+/// clamped to a single turn, and the path is empty if any of the arguments aren't finite.
+pub fn arc(radius: f64, start: f64, end: f64) -> PointPath {
+    let sweep = end - start;
+    if !radius.is_finite() || !start.is_finite() || !sweep.is_finite() {
+        return PointPath(vec![]);
+    }
+    let sweep = sweep.clamp(-2.0 * PI, 2.0 * PI);
+    let turns = sweep.abs() / (2.0 * PI);
+    let segments = ((turns * OVAL_SEGMENTS as f64).ceil() as usize).max(1);
+    let points = (0..segments + 1).map(|i| {
+        let a = start + sweep * i as f64 / segments as f64;
+        (radius * a.cos(), radius * a.sin())
+    }).collect();
+    PointPath(points)
+}
+
+
 /// A shape described by its edges.
 #[derive(Clone, Debug)]
 pub struct Shape(pub Vec<(f64, f64)>);
 
+impl Hash for Shape {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_points(&self.0, state);
+    }
+}
+
+
+/// Feed a sequence of points to the given hasher by the bit patterns of their coordinates.
+fn hash_points<H: Hasher>(points: &[(f64, f64)], state: &mut H) {
+    points.len().hash(state);
+    for &(x, y) in points.iter() {
+        hash_f64(x, state);
+        hash_f64(y, state);
+    }
+}
+
 
 impl Shape {
 
@@ -316,6 +1286,140 @@ impl Shape {
         Form::new(BasicForm::Shape(ShapeStyle::Line(style), self))
     }
 
+
+    /// Fill a shape with the given fill style and then outline it with the given line style, as a
+    /// single form that moves, picks and collides as one.
+    #[inline]
+    pub fn styled(self, fill: FillStyle, line: LineStyle) -> Form {
+        Form::new(BasicForm::Shape(ShapeStyle::FillAndLine(fill, line), self))
+    }
+
+
+    /// Outline a shape with a given line style, with the line lying inside of, centered upon or
+    /// outside of the shape's edges, e.g. so that outlining a UI box doesn't change its size.
+    ///
+    /// The shape is offset by half of the line's width (see `geometry::offset_polygon`) and then
+    /// outlined as usual.
+    pub fn outlined_aligned(self, style: LineStyle, alignment: StrokeAlignment) -> Form {
+        let distance = match alignment {
+            StrokeAlignment::Inside => -style.width / 2.0,
+            StrokeAlignment::Center => 0.0,
+            StrokeAlignment::Outside => style.width / 2.0,
+        };
+        Shape(geometry::offset_polygon(&self.0, distance)).outlined(style)
+    }
+
+
+    /// Whether or not the areas of the two shapes overlap, where both shapes share the same
+    /// coordinate system.
+    #[inline]
+    pub fn overlaps(&self, other: &Shape) -> bool {
+        geometry::polygons_overlap(&self.0, &other.0)
+    }
+
+
+    /// Replace each of the shape's corners with a circular arc of the given radius, e.g.
+    /// `ngon(6, 40.0).round_corners(8.0)` for a hexagon with rounded corners.
+    ///
+    /// The radius is reduced at corners whose edges are too short for it, so that the arcs of
+    /// neighbouring corners never overlap. Both convex and concave corners are rounded.
+    pub fn round_corners(self, radius: f64) -> Shape {
+        let Shape(points) = self;
+        let n = points.len();
+        if n < 3 || !(radius > 0.0) {
+            return Shape(points);
+        }
+        // The greatest angle turned between consecutive points along an arc.
+        let step = PI / 16.0;
+        let mut rounded = Vec::with_capacity(n * 4);
+        for (i, &(x, y)) in points.iter().enumerate() {
+            let (prev_x, prev_y) = points[(i + n - 1) % n];
+            let (next_x, next_y) = points[(i + 1) % n];
+            let prev_len = (prev_x - x).hypot(prev_y - y);
+            let next_len = (next_x - x).hypot(next_y - y);
+            if prev_len == 0.0 || next_len == 0.0 {
+                rounded.push((x, y));
+                continue;
+            }
+            let (u1x, u1y) = ((prev_x - x) / prev_len, (prev_y - y) / prev_len);
+            let (u2x, u2y) = ((next_x - x) / next_len, (next_y - y) / next_len);
+            // Half of the angle between the corner's two edges.
+            let half = (u1x * u2x + u1y * u2y).clamp(-1.0, 1.0).acos() / 2.0;
+            if !(half > 0.0) || half >= PI / 2.0 - 1e-9 {
+                rounded.push((x, y));
+                continue;
+            }
+            let tangent = (radius / half.tan()).min(prev_len / 2.0).min(next_len / 2.0);
+            let r = tangent * half.tan();
+            let (bx, by) = (u1x + u2x, u1y + u2y);
+            let b_len = bx.hypot(by);
+            let center_dist = r / half.sin();
+            let (cx, cy) = (x + bx / b_len * center_dist, y + by / b_len * center_dist);
+            let start = (y + u1y * tangent - cy).atan2(x + u1x * tangent - cx);
+            let end = (y + u2y * tangent - cy).atan2(x + u2x * tangent - cx);
+            let mut sweep = end - start;
+            if sweep > PI { sweep -= 2.0 * PI } else if sweep < -PI { sweep += 2.0 * PI }
+            let segments = ((sweep.abs() / step).ceil() as usize).max(1);
+            for k in 0..segments + 1 {
+                let a = start + sweep * k as f64 / segments as f64;
+                rounded.push((cx + r * a.cos(), cy + r * a.sin()));
+            }
+        }
+        Shape(rounded)
+    }
+
+
+    /// A simplified version of the shape, with every corner removed that lies within the given
+    /// distance of the outline without it. Useful for producing the lower levels of a `lod`.
+    ///
+    /// The simplified shape always keeps at least three corners.
+    pub fn simplified(self, tolerance: f64) -> Shape {
+        let Shape(points) = self;
+        let n = points.len();
+        if n <= 3 || !(tolerance > 0.0) {
+            return Shape(points);
+        }
+        // Split the outline in two at the corner farthest from the first, simplifying each half.
+        let distance = |&(x, y): &(f64, f64)| (x - points[0].0).hypot(y - points[0].1);
+        let far = (1..n).fold(1, |far, i| if distance(&points[i]) > distance(&points[far]) {
+            i
+        } else {
+            far
+        });
+        let mut simplified = Vec::new();
+        simplify_polyline(&points[..far + 1], tolerance, &mut simplified);
+        let mut rest = points[far..].to_vec();
+        rest.push(points[0]);
+        simplify_polyline(&rest, tolerance, &mut simplified);
+        if simplified.len() < 3 {
+            return Shape(points);
+        }
+        Shape(simplified)
+    }
+
+}
+
+
+/// Push the points of the polyline that lie farther than `tolerance` from the simplified line
+/// onto `simplified`, excluding the last point (Ramer-Douglas-Peucker).
+fn simplify_polyline(points: &[(f64, f64)], tolerance: f64, simplified: &mut Vec<(f64, f64)>) {
+    let n = points.len();
+    if n == 0 {
+        return;
+    }
+    simplified.push(points[0]);
+    if n <= 2 {
+        return;
+    }
+    let (first, last) = (points[0], points[n - 1]);
+    let (far, far_distance) = (1..n - 1)
+        .map(|i| (i, geometry::distance_to_segment(points[i], first, last)))
+        .fold((0, 0.0), |far, next| if next.1 > far.1 { next } else { far });
+    if far_distance > tolerance {
+        simplified.pop();
+        simplify_polyline(&points[..far + 1], tolerance, simplified);
+        simplify_polyline(&points[far..], tolerance, simplified);
+    }
 }
 
 
@@ -327,6 +1431,63 @@ pub fn polygon(points: Vec<(f64, f64)>) -> Shape {
 }
 
 
+/// Create a shape from [SVG path data](https://www.w3.org/TR/SVG11/paths.html#PathData), i.e.
+/// `shape_from_svg_path("M 0 0 L 10 10 L 20 0 Z")`.
+///
+/// The y-axis is flipped so that the shape appears as it would in an SVG viewer, and curves and
+/// arcs are flattened into line segments. Only the first subpath is used - see `svg::parse_path`
+/// for path data that describes many subpaths.
+pub fn shape_from_svg_path(data: &str) -> Result<Shape, svg::Error> {
+    let subpaths = svg::parse_path(data)?;
+    match subpaths.into_iter().next() {
+        Some(subpath) => Ok(Shape(subpath.points.into_iter().map(|(x, y)| (x, -y)).collect())),
+        None => Err(svg::Error::Empty),
+    }
+}
+
+
+/// The border of a rectangle with a given width and height, drawn with the line style's color and
+/// width along only the given sides, e.g. for a divider beneath a UI row.
+///
+/// Each side is drawn as a filled band aligned to the rectangle's edge as described by
+/// `alignment`. The bands meet without overlapping, so translucent borders have even corners.
+/// The line style's caps, joins and dashing are ignored.
+pub fn rect_border(w: f64, h: f64, style: LineStyle, alignment: StrokeAlignment, sides: Sides)
+    -> Form
+{
+    let (hw, hh) = (w.abs() / 2.0, h.abs() / 2.0);
+    // How far each band reaches outside of and inside of the rectangle's edges.
+    let (outer, inner) = match alignment {
+        StrokeAlignment::Inside => (0.0, style.width),
+        StrokeAlignment::Center => (style.width / 2.0, style.width / 2.0),
+        StrokeAlignment::Outside => (style.width, 0.0),
+    };
+    let band = |left: f64, bottom: f64, right: f64, top: f64| {
+        let (x, y) = ((left + right) / 2.0, (bottom + top) / 2.0);
+        self::rect(right - left, top - bottom).filled(style.color).shift(x, y)
+    };
+    // The top and bottom bands cover the corners of the sides that are drawn.
+    let x_min = if sides.left { -hw - outer } else { -hw };
+    let x_max = if sides.right { hw + outer } else { hw };
+    let y_min = if sides.bottom { -hh + inner } else { -hh };
+    let y_max = if sides.top { hh - inner } else { hh };
+    let mut bands = Vec::new();
+    if sides.top {
+        bands.push(band(x_min, hh - inner, x_max, hh + outer));
+    }
+    if sides.bottom {
+        bands.push(band(x_min, -hh - outer, x_max, -hh + inner));
+    }
+    if sides.left {
+        bands.push(band(-hw - outer, y_min, -hw + inner, y_max));
+    }
+    if sides.right {
+        bands.push(band(hw - inner, y_min, hw + outer, y_max));
+    }
+    group(bands)
+}
+
+
 /// A rectangle with a given width and height.
 pub fn rect(w: f64, h: f64) -> Shape {
     let hw = w / 2.0;
@@ -335,20 +1496,57 @@ pub fn rect(w: f64, h: f64) -> Shape {
 }
 
 
+/// A rectangle with a given width and height whose corners are rounded with the given radius.
+///
+/// The radius is limited to half of the shorter side. A radius of zero produces a plain `rect`.
+pub fn rounded_rect(w: f64, h: f64, radius: f64) -> Shape {
+    let hw = w.abs() / 2.0;
+    let hh = h.abs() / 2.0;
+    let r = radius.min(hw).min(hh);
+    if !(r > 0.0) {
+        return rect(w, h);
+    }
+    // The number of segments used to approximate each quarter circle.
+    let n: usize = 8;
+    let t = PI / 2.0 / n as f64;
+    let corners = [(hw - r, hh - r), (r - hw, hh - r), (r - hw, r - hh), (hw - r, r - hh)];
+    let points = corners.iter().enumerate().flat_map(|(corner, &(cx, cy))| {
+        let start = corner as f64 * PI / 2.0;
+        (0..n + 1).map(move |i| {
+            let a = start + t * i as f64;
+            (cx + r * a.cos(), cy + r * a.sin())
+        })
+    }).collect();
+    Shape(points)
+}
+
+
 /// A square with a given edge length.
 pub fn square(n: f64) -> Shape {
     rect(n, n)
 }
 
 
-/// An oval with a given width and height.
+/// An oval with a given width and height, approximated by `OVAL_SEGMENTS` segments.
 pub fn oval(w: f64, h: f64) -> Shape {
-    let n: usize = 50;
-    let t = 2.0 * PI / n as f64;
+    oval_with_resolution(w, h, OVAL_SEGMENTS)
+}
+
+
+/// The number of segments with which `oval` and `circle` approximate their curve.
+pub const OVAL_SEGMENTS: usize = 50;
+
+
+/// An oval with a given width and height, approximated by the given number of segments.
+///
+/// Fewer segments are cheaper to fill and outline while more give a smoother curve, which matters
+/// most for large ovals.
+pub fn oval_with_resolution(w: f64, h: f64, segments: usize) -> Shape {
+    let t = 2.0 * PI / segments as f64;
     let hw = w / 2.0;
     let hh = h / 2.0;
     let f = |i: f64| (hw * (t*i).cos(), hh * (t*i).sin());
-    let points = (0..n-1).map(|i| f(i as f64)).collect();
+    let points = (0..segments).map(|i| f(i as f64)).collect();
     Shape(points)
 }
 
@@ -362,178 +1560,56 @@ pub fn circle(r: f64) -> Shape {
 
 /// A regular polygon with N sides. The first argument specifies the number of sides and the second
 /// is the radius. So to create a pentagon with radius 30, you would say `ngon(5, 30.0)`
+///
+/// The first corner lies along the positive x-axis - see `ngon_oriented` to start elsewhere.
 pub fn ngon(n: usize, r: f64) -> Shape {
+    ngon_oriented(n, r, 0.0)
+}
+
+
+/// A regular polygon with N sides and the given radius whose first corner lies at the given
+/// `Angle` (or radians), counter-clockwise from the positive x-axis.
+///
+/// E.g. `ngon_oriented(4, r, Angle::deg(45.0))` gives an axis-aligned square rather than a
+/// diamond, and `ngon_oriented(3, r, Angle::deg(90.0))` a triangle pointing up.
+pub fn ngon_oriented<A: Into<Angle>>(n: usize, r: f64, start_angle: A) -> Shape {
+    let start_angle = start_angle.into().radians();
     let t = 2.0 * PI / n as f64;
-    let f = |i: f64| (r * (t*i).cos(), r * (t*i).sin());
+    let f = |i: f64| (r * (start_angle + t*i).cos(), r * (start_angle + t*i).sin());
     let points = (0..n).map(|i| f(i as f64)).collect();
     Shape(points)
 }
 
 
-/// Create some text. Details like size and color are part of the `Text` value itself, so you can
-/// mix colors and sizes and fonts easily.
-pub fn text(t: Text) -> Form {
-    Form::new(BasicForm::Text(t))
+/// Which way up a hexagon sits.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum HexOrientation {
+    /// With a flat edge along the top and bottom, and corners to the left and right.
+    FlatTop,
+    /// With a corner at the top and bottom, and flat edges to the left and right.
+    PointyTop,
 }
 
-
-
-
-
-
-
-
-
-/// 
-/// CUSTOM NON-ELM FUNCTIONS.
-/// 
-/// Normally Elm renders to html and javascript, however the aim of elmesque is to render to GL.
-///
-
-
-/// This function draws a form with some given transform using the generic [Piston graphics]
-/// (https://github.com/PistonDevelopers/graphics) backend.
-pub fn draw_form<'a, C: CharacterCache, G: Graphics<Texture=C::Texture>>(
-    form: &Form,
-    alpha: f32,
-    backend: &mut G,
-    maybe_character_cache: &mut Option<&mut C>,
-    context: Context,
-) {
-    let Form { theta, scale, x, y, alpha, ref form } = *form;
-    let context = context.trans(x, y).scale(scale, scale).rot_rad(theta);
-    match *form {
-
-        BasicForm::PointPath(ref line_style, PointPath(ref points)) => {
-            // NOTE: join, dashing and dash_offset are not yet handled properly.
-            let LineStyle { color, width, cap, join, ref dashing, dash_offset } = *line_style;
-            let color = convert_color(color, alpha);
-            let mut draw_line = |(x1, y1), (x2, y2)| {
-                if dashing.is_empty() {
-                    let line = match cap {
-                        LineCap::Flat => graphics::Line::new(color, width / 2.0),
-                        LineCap::Round => graphics::Line::new_round(color, width / 2.0),
-                        LineCap::Padded => unimplemented!(),
-                    };
-                    line.draw([x1, y1, x2, y2], &context.draw_state, context.transform, backend);
-                } else {
-                    unimplemented!();
-                }
-            };
-            for window in points.windows(2) {
-                let (a, b) = (window[0], window[1]);
-                draw_line(a, b);
-            }
-        },
-
-        BasicForm::Shape(ref shape_style, Shape(ref points)) => {
-            match *shape_style {
-                ShapeStyle::Line(ref line_style) => {
-                    // NOTE: join, dashing and dash_offset are not yet handled properly.
-                    let LineStyle { color, width, cap, join, ref dashing, dash_offset } = *line_style;
-                    let color = convert_color(color, alpha);
-                    let mut draw_line = |(x1, y1), (x2, y2)| {
-                        let line = match cap {
-                            LineCap::Flat => graphics::Line::new(color, width / 2.0),
-                            LineCap::Round => graphics::Line::new_round(color, width / 2.0),
-                            LineCap::Padded => unimplemented!(),
-                        };
-                        line.draw([x1, y1, x2, y2], &context.draw_state, context.transform, backend);
-                    };
-                    for window in points.windows(2) {
-                        let (a, b) = (window[0], window[1]);
-                        draw_line(a, b);
-                    }
-                    if points.len() > 2 {
-                        draw_line(points[points.len()-1], points[0])
-                    }
-                },
-                ShapeStyle::Fill(ref fill_style) => match *fill_style {
-                    FillStyle::Solid(color) => {
-                        let color = convert_color(color, alpha);
-                        let polygon = graphics::Polygon::new(color);
-                        let points: Vec<_> = points.iter().map(|&(x, y)| [x, y]).collect();
-                        polygon.draw(&points[..], &context.draw_state, context.transform, backend);
-                    },
-                    FillStyle::Texture(ref path) => {
-                        unimplemented!();
-                    },
-                    FillStyle::Grad(ref gradient) => {
-                        unimplemented!();
-                    },
-                },
-            }
-        },
-
-        BasicForm::OutlinedText(ref line_style, ref text) => {
-            unimplemented!();
-        },
-
-        BasicForm::Text(ref text) => {
-            let context = context.scale(1.0, -1.0);
-            if let Some(ref mut character_cache) = *maybe_character_cache {
-                use text::Style as TextStyle;
-                use text::Position as TextPosition;
-                use text::TextUnit;
-                let (total_width, max_height) = text.sequence.iter().fold((0.0, 0.0), |(w, h), unit| {
-                    let TextUnit { ref string, ref style } = *unit;
-                    let TextStyle { ref typeface, height, color, bold, italic, line, monospace } = *style;
-                    let height = height.unwrap_or(16.0);
-                    let new_total_width = w + character_cache.width(height as u32, &string);
-                    let new_max_height = if height > h { height } else { h };
-                    (new_total_width, new_max_height)
-                });
-                let x_offset = match text.position {
-                        TextPosition::Center  => -(total_width / 2.0).floor(),
-                        TextPosition::ToLeft  => -total_width.floor(),
-                        TextPosition::ToRight => 0.0
-                    };
-                let y_offset = (max_height / 3.0).floor(); // TODO: FIX THIS (3.0)
-                let context = context.trans(x_offset, y_offset);
-                for unit in text.sequence.iter() {
-                    let TextUnit { ref string, ref style } = *unit;
-                    let TextStyle { ref typeface, height, color, bold, italic, line, monospace } = *style;
-                    let height = height.unwrap_or(16.0).floor();
-                    let color = convert_color(color, alpha);
-                    graphics::text::Text::new_color(color, height as u32)
-                        .round()
-                        .draw(&string[..], *character_cache, &context.draw_state, context.transform, backend);
-                }
-            }
-        },
-
-        BasicForm::Image(src_x, src_y, (w, h), ref path) => {
-            // let image = graphics::Image {
-            //     color: None,
-            //     rectangle: None,
-            //     source_rectangle: Some([src_x, src_y, w, h]),
-            // };
-            // let texture: &Texture = ::std::ops::Deref::deref(&texture);
-            // image.draw(texture, draw_state, matrix, backend);
-            unimplemented!();
-        },
-
-        BasicForm::Group(ref group_transform, ref forms) => {
-            let Transform2D(matrix) = Transform2D(context.transform.clone())
-                .multiply(group_transform.clone());
-            let context = Context { transform: matrix, ..context };
-            for form in forms.iter() {
-                draw_form(form, alpha, backend, maybe_character_cache, context);
-            }
-        },
-
-        BasicForm::Element(ref element) =>
-            element::draw_element(element, alpha, backend, maybe_character_cache, context),
+impl HexOrientation {
+    /// The angle in radians of the first corner of a hexagon in this orientation.
+    pub fn start_angle(self) -> f64 {
+        match self {
+            HexOrientation::FlatTop => 0.0,
+            HexOrientation::PointyTop => PI / 6.0,
+        }
     }
 }
 
-/// Convert an elmesque color to a piston-graphics color.
-fn convert_color(color: Color, alpha: f32) -> [f32; 4] {
-    use color::hsl_to_rgb;
-    let ((r, g, b), a) = match color {
-        Color::Hsla(h, s, l, a) => (hsl_to_rgb(h, s, l), a),
-        Color::Rgba(r, g, b, a) => ((r, g, b), a),
-    };
-    [r, g, b, a * alpha]
+
+/// A regular hexagon with the given radius (the distance from its center to each corner), sitting
+/// in the given orientation.
+pub fn hexagon(r: f64, orientation: HexOrientation) -> Shape {
+    ngon_oriented(6, r, orientation.start_angle())
 }
 
+
+/// Create some text. Details like size and color are part of the `Text` value itself, so you can
+/// mix colors and sizes and fonts easily.
+pub fn text(t: Text) -> Form {
+    Form::new(BasicForm::Text(t))
+}
@@ -0,0 +1,374 @@
+//!
+//! Simple 2D geometry used for hit testing, spatial queries and clipping.
+//!
+//! All functions use the collage coordinate system, where the y-axis points up.
+//!
+
+use transform_2d::Transform2D;
+
+
+/// An axis-aligned rectangle described by its minimum and maximum corners.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Rect {
+    pub min: (f64, f64),
+    pub max: (f64, f64),
+}
+
+
+impl Rect {
+
+    /// Construct a rectangle from its center and its size.
+    pub fn from_xywh(x: f64, y: f64, w: f64, h: f64) -> Rect {
+        let (hw, hh) = (w.abs() / 2.0, h.abs() / 2.0);
+        Rect { min: (x - hw, y - hh), max: (x + hw, y + hh) }
+    }
+
+    /// The smallest rectangle containing all of the given points, or `None` if there are none.
+    pub fn from_points<I: IntoIterator<Item=(f64, f64)>>(points: I) -> Option<Rect> {
+        points.into_iter().fold(None, |rect, (x, y)| match rect {
+            None => Some(Rect { min: (x, y), max: (x, y) }),
+            Some(Rect { min, max }) => Some(Rect {
+                min: (min.0.min(x), min.1.min(y)),
+                max: (max.0.max(x), max.1.max(y)),
+            }),
+        })
+    }
+
+    /// The center of the rectangle.
+    pub fn center(&self) -> (f64, f64) {
+        ((self.min.0 + self.max.0) / 2.0, (self.min.1 + self.max.1) / 2.0)
+    }
+
+    pub fn width(&self) -> f64 {
+        self.max.0 - self.min.0
+    }
+
+    pub fn height(&self) -> f64 {
+        self.max.1 - self.min.1
+    }
+
+    /// The four corners of the rectangle in counter-clockwise order.
+    pub fn corners(&self) -> [(f64, f64); 4] {
+        [self.min, (self.max.0, self.min.1), self.max, (self.min.0, self.max.1)]
+    }
+
+    /// The smallest rectangle containing both rectangles.
+    pub fn union(&self, other: &Rect) -> Rect {
+        Rect {
+            min: (self.min.0.min(other.min.0), self.min.1.min(other.min.1)),
+            max: (self.max.0.max(other.max.0), self.max.1.max(other.max.1)),
+        }
+    }
+
+    /// Grow the rectangle by the given amount in every direction.
+    pub fn pad(&self, pad: f64) -> Rect {
+        Rect {
+            min: (self.min.0 - pad, self.min.1 - pad),
+            max: (self.max.0 + pad, self.max.1 + pad),
+        }
+    }
+
+    /// Whether or not the point lies within the rectangle (including its edges).
+    pub fn contains_point(&self, (x, y): (f64, f64)) -> bool {
+        x >= self.min.0 && x <= self.max.0 && y >= self.min.1 && y <= self.max.1
+    }
+
+    /// Whether or not the other rectangle lies entirely within this one.
+    pub fn contains_rect(&self, other: &Rect) -> bool {
+        self.contains_point(other.min) && self.contains_point(other.max)
+    }
+
+    /// Whether or not the rectangles overlap (including touching edges).
+    pub fn intersects(&self, other: &Rect) -> bool {
+        self.min.0 <= other.max.0 && other.min.0 <= self.max.0
+            && self.min.1 <= other.max.1 && other.min.1 <= self.max.1
+    }
+
+    /// The axis-aligned bounds of the rectangle once transformed.
+    pub fn transform(&self, transform: &Transform2D) -> Rect {
+        let corners = self.corners();
+        Rect::from_points(corners.iter().map(|&p| transform_point(transform, p)))
+            .expect("a rect always has corners")
+    }
+
+}
+
+
+/// Apply the transform to the given point.
+#[inline]
+pub fn transform_point(&Transform2D(m): &Transform2D, (x, y): (f64, f64)) -> (f64, f64) {
+    (m[0][0] * x + m[0][1] * y + m[0][2], m[1][0] * x + m[1][1] * y + m[1][2])
+}
+
+
+/// The amount by which the transform scales lengths, averaged over both axes.
+pub fn transform_scale(&Transform2D(m): &Transform2D) -> f64 {
+    (m[0][0].hypot(m[1][0]) + m[0][1].hypot(m[1][1])) / 2.0
+}
+
+
+/// Whether or not the point lies within the polygon, using the non-zero winding rule.
+pub fn point_in_polygon((x, y): (f64, f64), polygon: &[(f64, f64)]) -> bool {
+    let n = polygon.len();
+    let mut winding = 0;
+    for i in 0..n {
+        let (a, b) = (polygon[i], polygon[(i + 1) % n]);
+        // Which side of the edge the point lies upon.
+        let side = (b.0 - a.0) * (y - a.1) - (x - a.0) * (b.1 - a.1);
+        if a.1 <= y {
+            if b.1 > y && side > 0.0 {
+                winding += 1;
+            }
+        } else if b.1 <= y && side < 0.0 {
+            winding -= 1;
+        }
+    }
+    winding != 0
+}
+
+
+/// The distance from the point to the line segment between `a` and `b`.
+pub fn distance_to_segment(p: (f64, f64), a: (f64, f64), b: (f64, f64)) -> f64 {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let len_sq = dx * dx + dy * dy;
+    let t = if len_sq == 0.0 {
+        0.0
+    } else {
+        (((p.0 - a.0) * dx + (p.1 - a.1) * dy) / len_sq).clamp(0.0, 1.0)
+    };
+    let (cx, cy) = (a.0 + t * dx, a.1 + t * dy);
+    (p.0 - cx).hypot(p.1 - cy)
+}
+
+
+/// Whether or not the segments `a1`-`a2` and `b1`-`b2` intersect (including touching).
+pub fn segments_intersect(a1: (f64, f64), a2: (f64, f64), b1: (f64, f64), b2: (f64, f64)) -> bool {
+    let cross = |o: (f64, f64), p: (f64, f64), q: (f64, f64)| {
+        (p.0 - o.0) * (q.1 - o.1) - (p.1 - o.1) * (q.0 - o.0)
+    };
+    let straddles = |d1: f64, d2: f64| (d1 > 0.0 && d2 < 0.0) || (d1 < 0.0 && d2 > 0.0);
+    let (d1, d2) = (cross(b1, b2, a1), cross(b1, b2, a2));
+    let (d3, d4) = (cross(a1, a2, b1), cross(a1, a2, b2));
+    if straddles(d1, d2) && straddles(d3, d4) {
+        return true;
+    }
+    // Otherwise the segments only intersect if an endpoint of one lies upon the other.
+    (d1 == 0.0 && distance_to_segment(a1, b1, b2) == 0.0)
+        || (d2 == 0.0 && distance_to_segment(a2, b1, b2) == 0.0)
+        || (d3 == 0.0 && distance_to_segment(b1, a1, a2) == 0.0)
+        || (d4 == 0.0 && distance_to_segment(b2, a1, a2) == 0.0)
+}
+
+
+/// The shortest distance between the segments `a1`-`a2` and `b1`-`b2`.
+pub fn distance_between_segments(a1: (f64, f64), a2: (f64, f64), b1: (f64, f64), b2: (f64, f64))
+    -> f64
+{
+    if segments_intersect(a1, a2, b1, b2) {
+        return 0.0;
+    }
+    distance_to_segment(a1, b1, b2)
+        .min(distance_to_segment(a2, b1, b2))
+        .min(distance_to_segment(b1, a1, a2))
+        .min(distance_to_segment(b2, a1, a2))
+}
+
+
+/// Whether or not the areas of the two polygons overlap (including touching edges).
+pub fn polygons_overlap(a: &[(f64, f64)], b: &[(f64, f64)]) -> bool {
+    if a.is_empty() || b.is_empty() {
+        return false;
+    }
+    // Either polygon may lie entirely within the other.
+    if point_in_polygon(a[0], b) || point_in_polygon(b[0], a) {
+        return true;
+    }
+    let (n, m) = (a.len(), b.len());
+    (0..n).any(|i| (0..m).any(|j| {
+        segments_intersect(a[i], a[(i + 1) % n], b[j], b[(j + 1) % m])
+    }))
+}
+
+
+/// The twice-signed area of the polygon, positive when its points wind counter-clockwise.
+fn signed_area(polygon: &[(f64, f64)]) -> f64 {
+    let n = polygon.len();
+    (0..n).fold(0.0, |area, i| {
+        let (a, b) = (polygon[i], polygon[(i + 1) % n]);
+        area + a.0 * b.1 - b.0 * a.1
+    })
+}
+
+
+/// The polygon with each of its edges moved outward by the given distance (or inward, for a
+/// negative distance), whichever way its points wind.
+///
+/// Corners are mitred, with the mitre limited to ten times the distance so that very sharp
+/// corners don't spike.
+pub fn offset_polygon(polygon: &[(f64, f64)], distance: f64) -> Vec<(f64, f64)> {
+    let n = polygon.len();
+    let orientation = signed_area(polygon).signum();
+    if n < 3 || orientation == 0.0 || distance == 0.0 {
+        return polygon.to_vec();
+    }
+    // The outward unit normal of the edge from `a` to `b`.
+    let normal = |a: (f64, f64), b: (f64, f64)| {
+        let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+        let len = dx.hypot(dy);
+        if len == 0.0 { (0.0, 0.0) } else { (orientation * dy / len, -orientation * dx / len) }
+    };
+    (0..n).map(|i| {
+        let (prev, point, next) = (polygon[(i + n - 1) % n], polygon[i], polygon[(i + 1) % n]);
+        let (n1, n2) = (normal(prev, point), normal(point, next));
+        let (mx, my) = (n1.0 + n2.0, n1.1 + n2.1);
+        let dot = n1.0 * n2.0 + n1.1 * n2.1;
+        let scale = if 1.0 + dot > 1e-9 { 1.0 / (1.0 + dot) } else { 0.0 };
+        let (mut ox, mut oy) = (mx * scale * distance, my * scale * distance);
+        let (len, limit) = (ox.hypot(oy), 10.0 * distance.abs());
+        if len > limit {
+            ox *= limit / len;
+            oy *= limit / len;
+        }
+        (point.0 + ox, point.1 + oy)
+    }).collect()
+}
+
+
+/// The parts of the `subject` polygon lying within the convex `clip` polygon, which may wind in
+/// either direction.
+///
+/// Uses the Sutherland-Hodgman algorithm, so a concave subject clipped into several pieces is
+/// returned as a single polygon joined along the clip's edges.
+pub fn clip_polygon(subject: &[(f64, f64)], clip: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    let orientation = signed_area(clip).signum();
+    if orientation == 0.0 {
+        return Vec::new();
+    }
+    let n = clip.len();
+    let mut output = subject.to_vec();
+    for i in 0..n {
+        if output.is_empty() {
+            break;
+        }
+        let (a, b) = (clip[i], clip[(i + 1) % n]);
+        let side = |p: (f64, f64)| {
+            orientation * ((b.0 - a.0) * (p.1 - a.1) - (b.1 - a.1) * (p.0 - a.0))
+        };
+        let input = ::std::mem::take(&mut output);
+        let m = input.len();
+        for j in 0..m {
+            let (p, q) = (input[j], input[(j + 1) % m]);
+            let (side_p, side_q) = (side(p), side(q));
+            if side_p >= 0.0 {
+                output.push(p);
+            }
+            if (side_p >= 0.0) != (side_q >= 0.0) {
+                let t = side_p / (side_p - side_q);
+                output.push((p.0 + t * (q.0 - p.0), p.1 + t * (q.1 - p.1)));
+            }
+        }
+    }
+    output
+}
+
+
+/// The part of the segment from `a` to `b` lying within the convex `clip` polygon, which may
+/// wind in either direction.
+///
+/// Uses the Cyrus-Beck algorithm.
+pub fn clip_segment(a: (f64, f64), b: (f64, f64), clip: &[(f64, f64)])
+    -> Option<((f64, f64), (f64, f64))>
+{
+    let orientation = signed_area(clip).signum();
+    if orientation == 0.0 {
+        return None;
+    }
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let (mut t_min, mut t_max) = (0.0f64, 1.0f64);
+    let n = clip.len();
+    for i in 0..n {
+        let (c, d) = (clip[i], clip[(i + 1) % n]);
+        let (ex, ey) = (d.0 - c.0, d.1 - c.1);
+        // How far inside the edge `a` lies, and how quickly the segment moves inward.
+        let inside = orientation * (ex * (a.1 - c.1) - ey * (a.0 - c.0));
+        let rate = orientation * (ex * dy - ey * dx);
+        if rate == 0.0 {
+            if inside < 0.0 {
+                return None;
+            }
+        } else {
+            let t = -inside / rate;
+            if rate > 0.0 { t_min = t_min.max(t) } else { t_max = t_max.min(t) }
+            if t_min > t_max {
+                return None;
+            }
+        }
+    }
+    Some(((a.0 + t_min * dx, a.1 + t_min * dy), (a.0 + t_max * dx, a.1 + t_max * dy)))
+}
+
+
+/// Split the path along the given points into the dashes described by `dashing`, which
+/// alternates between the lengths of the dashes and of the gaps between them, starting
+/// `dash_offset` along the pattern (as with `LineStyle`). An odd number of lengths is repeated to
+/// make the number even, as in SVG.
+///
+/// If `closed` is true the closing edge is dashed too, continuing the pattern from the last
+/// point back to the first. If the pattern is empty or has no length, the whole path is returned
+/// as a single dash.
+pub fn dash_path(points: &[(f64, f64)], closed: bool, dashing: &[f64], dash_offset: f64)
+    -> Vec<Vec<(f64, f64)>>
+{
+    let mut path = points.to_vec();
+    if closed && points.len() > 2 {
+        path.push(points[0]);
+    }
+    let mut pattern = dashing.to_vec();
+    if pattern.len() % 2 == 1 {
+        pattern.extend_from_slice(dashing);
+    }
+    let total: f64 = pattern.iter().sum();
+    if path.len() < 2 || !(total > 0.0) || pattern.iter().any(|&len| len < 0.0) {
+        return vec![path];
+    }
+
+    // Find where along the pattern the path begins.
+    let mut phase = dash_offset % total;
+    if phase < 0.0 {
+        phase += total;
+    }
+    let mut index = 0;
+    while phase > 0.0 && phase >= pattern[index] {
+        phase -= pattern[index];
+        index = (index + 1) % pattern.len();
+    }
+    let mut remaining = pattern[index] - phase;
+
+    let mut dashes = Vec::new();
+    let mut dash = if index % 2 == 0 { vec![path[0]] } else { Vec::new() };
+    for window in path.windows(2) {
+        let (a, b) = (window[0], window[1]);
+        let len = (b.0 - a.0).hypot(b.1 - a.1);
+        let mut along = 0.0;
+        while len - along >= remaining {
+            along += remaining;
+            let t = along / len;
+            let point = (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t);
+            // The point either ends a dash or begins the next.
+            dash.push(point);
+            if index % 2 == 0 {
+                dashes.push(::std::mem::take(&mut dash));
+            }
+            index = (index + 1) % pattern.len();
+            remaining = pattern[index];
+        }
+        remaining -= len - along;
+        if index % 2 == 0 {
+            dash.push(b);
+        }
+    }
+    if dash.len() > 1 {
+        dashes.push(dash);
+    }
+    dashes
+}
@@ -0,0 +1,93 @@
+//!
+//! Layout helpers for hex and isometric tile grids.
+//!
+//! Each function converts between the coordinates of a tile within a grid and the position of
+//! its center within a collage, where tile `(0, 0)` is centered on the origin. Shift a tile's
+//! `Form` to the position given by `hex_to_xy` or `iso_to_xy`, and convert a point (such as the
+//! mouse position) back to the tile beneath it with `xy_to_hex` or `xy_to_iso`.
+//!
+//! Hex grids use [axial coordinates](https://www.redblobgames.com/grids/hexagons/#coordinates),
+//! where `q` is the column and `r` the row, and rows increase up the collage.
+//!
+
+use form::{self, HexOrientation, Shape};
+
+
+/// The position of the center of the hex tile at the given axial coordinates, for hexagons of the
+/// given radius (the distance from the center of a tile to each of its corners).
+pub fn hex_to_xy(q: i32, r: i32, size: f64, orientation: HexOrientation) -> (f64, f64) {
+    let (q, r) = (q as f64, r as f64);
+    let sqrt_3 = 3.0f64.sqrt();
+    match orientation {
+        HexOrientation::FlatTop => (size * 1.5 * q, size * sqrt_3 * (r + q / 2.0)),
+        HexOrientation::PointyTop => (size * sqrt_3 * (q + r / 2.0), size * 1.5 * r),
+    }
+}
+
+
+/// The axial coordinates of the hex tile lying beneath the given position, for hexagons of the
+/// given radius. The inverse of `hex_to_xy`.
+pub fn xy_to_hex(x: f64, y: f64, size: f64, orientation: HexOrientation) -> (i32, i32) {
+    let sqrt_3 = 3.0f64.sqrt();
+    let (q, r) = match orientation {
+        HexOrientation::FlatTop => {
+            let q = x / (size * 1.5);
+            (q, y / (size * sqrt_3) - q / 2.0)
+        },
+        HexOrientation::PointyTop => {
+            let r = y / (size * 1.5);
+            (x / (size * sqrt_3) - r / 2.0, r)
+        },
+    };
+    round_axial(q, r)
+}
+
+
+/// Round fractional axial coordinates to those of the hex tile containing them.
+///
+/// Rounding `q` and `r` independently may land on a neighbouring tile, so all three cube
+/// coordinates are rounded and the one that changed the most is recomputed from the others.
+fn round_axial(q: f64, r: f64) -> (i32, i32) {
+    let s = -q - r;
+    let (mut rq, mut rr, rs) = (q.round(), r.round(), s.round());
+    let (dq, dr, ds) = ((rq - q).abs(), (rr - r).abs(), (rs - s).abs());
+    if dq > dr && dq > ds {
+        rq = -rr - rs;
+    } else if dr > ds {
+        rr = -rq - rs;
+    }
+    (rq as i32, rr as i32)
+}
+
+
+/// The outline of a single hex tile of the given radius, centered on the origin.
+pub fn hex_tile(size: f64, orientation: HexOrientation) -> Shape {
+    form::hexagon(size, orientation)
+}
+
+
+/// The position of the center of the isometric tile at the given column and row, for diamond
+/// tiles of the given width and height.
+///
+/// Columns run down and to the right of the collage and rows down and to the left, as is usual
+/// for isometric maps.
+pub fn iso_to_xy(col: i32, row: i32, tile_w: f64, tile_h: f64) -> (f64, f64) {
+    let (col, row) = (col as f64, row as f64);
+    ((col - row) * tile_w / 2.0, -(col + row) * tile_h / 2.0)
+}
+
+
+/// The column and row of the isometric tile lying beneath the given position, for diamond tiles
+/// of the given width and height. The inverse of `iso_to_xy`.
+pub fn xy_to_iso(x: f64, y: f64, tile_w: f64, tile_h: f64) -> (i32, i32) {
+    let (u, v) = (x / (tile_w / 2.0), -y / (tile_h / 2.0));
+    (((v + u) / 2.0).round() as i32, ((v - u) / 2.0).round() as i32)
+}
+
+
+/// The outline of a single diamond-shaped isometric tile of the given width and height, centered
+/// on the origin.
+pub fn iso_tile(tile_w: f64, tile_h: f64) -> Shape {
+    let (hw, hh) = (tile_w / 2.0, tile_h / 2.0);
+    form::polygon(vec![(0.0, hh), (hw, 0.0), (0.0, -hh), (-hw, 0.0)])
+}
@@ -0,0 +1,211 @@
+//!
+//! Lengths described in units other than pixels.
+//!
+//! A `Length` may be relative to the size of the render target (`vw` and `vh`) or a physical
+//! size (`mm` and `inches`) converted to pixels via the target's DPI. As neither is known until
+//! the `Element` is drawn, lengths are resolved at draw time by a `Viewport`; see
+//! `element::with_viewport` and the `Element::size_in` family of methods.
+//!
+//! The viewport also carries the target's safe area: the `Insets` by which a display's notches,
+//! rounded corners or overscan obscure its edges. See `element::with_safe_area`.
+//!
+
+use std::ops::Mul;
+
+
+/// The DPI assumed for a render target when none is given, matching CSS's reference pixel.
+pub const DEFAULT_DPI: f64 = 96.0;
+
+/// The number of millimetres within an inch.
+const MM_PER_INCH: f64 = 25.4;
+
+
+/// A length that is resolved to pixels at draw time.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Length {
+    /// A number of pixels.
+    Px(f64),
+    /// A percentage of the render target's width.
+    Vw(f64),
+    /// A percentage of the render target's height.
+    Vh(f64),
+    /// A number of millimetres.
+    Mm(f64),
+}
+
+
+/// The size and DPI of a render target, against which `Length`s are resolved, along with the
+/// time at which the frame is drawn.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Viewport {
+    pub width: f64,
+    pub height: f64,
+    /// The number of pixels within an inch.
+    pub dpi: f64,
+    /// The time in seconds at which the frame is drawn. See `RenderOptions::time`.
+    pub time: f64,
+    /// The time in seconds since the previous frame was drawn.
+    pub dt: f64,
+    /// The distances in pixels from each edge of the render target within which content may be
+    /// obscured. See `RenderOptions::safe_area`.
+    pub safe_area: Insets,
+}
+
+
+/// Distances in pixels inward from each edge of a rectangle.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Insets {
+    pub top: f64,
+    pub right: f64,
+    pub bottom: f64,
+    pub left: f64,
+}
+
+
+/// A number of pixels.
+pub fn px(n: f64) -> Length {
+    Length::Px(n)
+}
+
+/// A percentage of the render target's width.
+pub fn vw(percent: f64) -> Length {
+    Length::Vw(percent)
+}
+
+/// A percentage of the render target's height.
+pub fn vh(percent: f64) -> Length {
+    Length::Vh(percent)
+}
+
+/// A number of millimetres.
+pub fn mm(n: f64) -> Length {
+    Length::Mm(n)
+}
+
+/// A number of inches.
+pub fn inches(n: f64) -> Length {
+    Length::Mm(n * MM_PER_INCH)
+}
+
+
+impl Length {
+
+    /// The number of pixels described by the length within the given viewport.
+    pub fn resolve(&self, viewport: &Viewport) -> f64 {
+        match *self {
+            Length::Px(n) => n,
+            Length::Vw(percent) => viewport.width * percent / 100.0,
+            Length::Vh(percent) => viewport.height * percent / 100.0,
+            Length::Mm(n) => n / MM_PER_INCH * viewport.dpi,
+        }
+    }
+
+    /// Scale the length by the given factor.
+    pub fn scale(self, factor: f64) -> Length {
+        match self {
+            Length::Px(n) => Length::Px(n * factor),
+            Length::Vw(percent) => Length::Vw(percent * factor),
+            Length::Vh(percent) => Length::Vh(percent * factor),
+            Length::Mm(n) => Length::Mm(n * factor),
+        }
+    }
+
+}
+
+
+impl Viewport {
+
+    /// Construct a viewport of the given size with the `DEFAULT_DPI`.
+    pub fn new(width: f64, height: f64) -> Viewport {
+        Viewport {
+            width: width,
+            height: height,
+            dpi: DEFAULT_DPI,
+            time: 0.0,
+            dt: 0.0,
+            safe_area: Insets::none(),
+        }
+    }
+
+    /// Builder method for a viewport with the given DPI.
+    pub fn dpi(self, dpi: f64) -> Viewport {
+        Viewport { dpi: dpi, ..self }
+    }
+
+    /// Builder method for a viewport of a frame drawn at the given time in seconds.
+    pub fn time(self, time: f64) -> Viewport {
+        Viewport { time: time, ..self }
+    }
+
+    /// Builder method for a viewport of a frame drawn the given number of seconds after the
+    /// previous frame.
+    pub fn dt(self, dt: f64) -> Viewport {
+        Viewport { dt: dt, ..self }
+    }
+
+    /// Builder method for a viewport whose edges are obscured by the given insets.
+    pub fn safe_area(self, safe_area: Insets) -> Viewport {
+        Viewport { safe_area: safe_area, ..self }
+    }
+
+    /// The width and height of the viewport within its safe area.
+    pub fn safe_size(&self) -> (f64, f64) {
+        let Insets { top, right, bottom, left } = self.safe_area;
+        ((self.width - left - right).max(0.0), (self.height - top - bottom).max(0.0))
+    }
+
+    /// The given length resolved to a whole number of pixels, as used for `Element` sizes.
+    pub fn px<L: Into<Length>>(&self, length: L) -> i32 {
+        length.into().resolve(self).round() as i32
+    }
+
+}
+
+
+impl Insets {
+
+    /// Construct insets from the distance inward from each edge.
+    pub fn new(top: f64, right: f64, bottom: f64, left: f64) -> Insets {
+        Insets { top: top, right: right, bottom: bottom, left: left }
+    }
+
+    /// The same distance inward from every edge.
+    pub fn uniform(n: f64) -> Insets {
+        Insets::new(n, n, n, n)
+    }
+
+    /// No inset from any edge.
+    pub fn none() -> Insets {
+        Insets::uniform(0.0)
+    }
+
+    /// The greater of the two insets along each edge, i.e. to keep a margin around content
+    /// beyond that required by the safe area.
+    pub fn max(self, other: Insets) -> Insets {
+        Insets::new(self.top.max(other.top), self.right.max(other.right),
+                    self.bottom.max(other.bottom), self.left.max(other.left))
+    }
+
+}
+
+
+impl From<i32> for Length {
+    fn from(n: i32) -> Length {
+        Length::Px(n as f64)
+    }
+}
+
+impl From<f64> for Length {
+    fn from(n: f64) -> Length {
+        Length::Px(n)
+    }
+}
+
+
+impl Mul<f64> for Length {
+    type Output = Length;
+    fn mul(self, factor: f64) -> Length {
+        self.scale(factor)
+    }
+}
@@ -8,20 +8,105 @@
 //!
 //! Ported to Rust by Mitchell Nordine.
 //!
+//!
+//! Scenes are plain data: `Element`, `Form`, `Text` and the types they're built from are all
+//! `Send` and `Sync`, so a scene may be built on a worker thread and handed to the render thread.
+//!
+
+// The crate keeps to the style it was written in: explicit `field: field` initialisers and `ref`
+// patterns, `!(a > b)` comparisons that deliberately treat NaN as failing, and the long-standing
+// public `add` and `default` constructors.
+#![allow(
+    clippy::needless_borrowed_reference,
+    clippy::neg_cmp_op_on_partial_ord,
+    clippy::redundant_field_names,
+    clippy::should_implement_trait,
+    clippy::too_many_arguments,
+)]
 
+#[cfg(feature = "conrod")]
+extern crate conrod_core;
+#[cfg(feature = "gif")]
+extern crate gif;
+#[cfg(feature = "piston-backend")]
 extern crate graphics;
-extern crate num;
+#[cfg(feature = "canvas")]
+extern crate js_sys;
+extern crate num_traits;
+#[cfg(feature = "gif")]
+extern crate png;
 extern crate rand;
-extern crate rustc_serialize;
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde;
+#[cfg(feature = "rusttype")]
+extern crate rusttype;
+#[cfg(feature = "recording")]
+extern crate serde_json;
+#[cfg(feature = "syntect")]
+extern crate syntect;
+#[cfg(feature = "tiny-skia")]
+extern crate tiny_skia;
+#[cfg(feature = "unicode-bidi")]
+extern crate unicode_bidi;
 extern crate vecmath;
+#[cfg(feature = "canvas")]
+extern crate wasm_bindgen;
+#[cfg(feature = "canvas")]
+extern crate web_sys;
 
+pub use angle::Angle;
+pub use backend::RenderOptions;
 pub use color as colour;
-pub use element::{Element, Renderer};
+pub use element::Element;
+#[cfg(feature = "piston-backend")]
+pub use element::Renderer;
+pub use error::Error;
 pub use form::{Form};
+pub use length::Length;
+pub use stats::RenderStats;
 
+#[macro_use]
+mod macros;
+
+pub mod angle;
+pub mod backend;
+#[cfg(feature = "bench-scenes")]
+pub mod bench_scenes;
 pub mod color;
+#[cfg(feature = "conrod")]
+pub mod conrod;
 pub mod element;
+pub mod error;
+#[cfg(feature = "rusttype")]
+pub mod font;
 pub mod form;
+pub mod geometry;
+pub mod grid;
+pub mod length;
+pub mod scene_buffer;
+pub mod spatial;
+pub mod stats;
+pub mod svg;
+pub mod testing;
 pub mod text;
 pub mod transform_2d;
 pub mod utils;
+
+
+/// Fails to compile should any of the types that make up a scene stop being `Send` and `Sync`.
+#[allow(dead_code)]
+fn assert_scene_types_are_send_and_sync() {
+    fn assert<T: Send + Sync>() {}
+    assert::<Element>();
+    assert::<element::Prim>();
+    assert::<element::Responsive>();
+    assert::<Form>();
+    assert::<form::BasicForm>();
+    assert::<form::Animation>();
+    assert::<text::Text>();
+    assert::<color::Color>();
+    assert::<color::Gradient>();
+    assert::<backend::display_list::DisplayList>();
+    assert::<backend::resolved::ResolvedElement>();
+}
@@ -0,0 +1,70 @@
+//!
+//! Macros for building the lists of `Form`s and `Element`s that make up a scene.
+//!
+
+
+/// Build a `Vec<Form>` from a list of forms, `for` loops and `if`s.
+///
+/// Works like `vec!`, except that each item may also be a `for` loop or an `if`, so that
+/// repeated and optional parts of a scene may be written inline:
+///
+///   let forms = forms![
+///       rect(200.0, 200.0).filled(blue()),
+///       for i in 0..10 => circle(i as f64 * 10.0).outlined(solid(white())),
+///       if selected => square(20.0).filled(red()),
+///   ];
+///
+/// An item of the form `for PATTERN in ITERATOR => ITEM` adds an item for every element of the
+/// iterator, while `if CONDITION => ITEM` adds the item only when the condition holds.
+#[macro_export(local_inner_macros)]
+macro_rules! forms {
+    ($($items:tt)*) => {{
+        let mut forms: Vec<$crate::form::Form> = Vec::new();
+        __elmesque_push!(forms; $($items)*);
+        forms
+    }};
+}
+
+
+/// Build a `Vec<Element>` from a list of elements, `for` loops and `if`s. See `forms!`.
+#[macro_export(local_inner_macros)]
+macro_rules! elements {
+    ($($items:tt)*) => {{
+        let mut elements: Vec<$crate::element::Element> = Vec::new();
+        __elmesque_push!(elements; $($items)*);
+        elements
+    }};
+}
+
+
+/// Push each of the items of a `forms!` or `elements!` list to the given `Vec`.
+#[doc(hidden)]
+#[macro_export(local_inner_macros)]
+macro_rules! __elmesque_push {
+    ($vec:ident;) => {};
+    ($vec:ident; for $pat:pat in $iter:expr => $item:expr) => {
+        for $pat in $iter {
+            $vec.push($item);
+        }
+    };
+    ($vec:ident; for $pat:pat in $iter:expr => $item:expr, $($rest:tt)*) => {
+        __elmesque_push!($vec; for $pat in $iter => $item);
+        __elmesque_push!($vec; $($rest)*);
+    };
+    ($vec:ident; if $cond:expr => $item:expr) => {
+        if $cond {
+            $vec.push($item);
+        }
+    };
+    ($vec:ident; if $cond:expr => $item:expr, $($rest:tt)*) => {
+        __elmesque_push!($vec; if $cond => $item);
+        __elmesque_push!($vec; $($rest)*);
+    };
+    ($vec:ident; $item:expr) => {
+        $vec.push($item);
+    };
+    ($vec:ident; $item:expr, $($rest:tt)*) => {
+        $vec.push($item);
+        __elmesque_push!($vec; $($rest)*);
+    };
+}
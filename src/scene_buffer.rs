@@ -0,0 +1,97 @@
+//!
+//! Handing scenes built on one thread to another that draws them.
+//!
+//! A `SceneBuffer` holds the `Element` that the render thread is currently drawing, along with
+//! the next `Element` submitted by the thread that builds scenes through a `SceneSender`. The
+//! render thread calls `SceneBuffer::swap` once per frame to pick up the latest submitted scene,
+//! so that neither thread ever waits upon the other to build or draw a whole scene.
+//!
+//!   let mut scenes = SceneBuffer::new(element::empty());
+//!   let sender = scenes.sender();
+//!   thread::spawn(move || loop {
+//!       sender.submit(build_scene());
+//!   });
+//!   // Within the render loop:
+//!   scenes.swap();
+//!   scenes.current().draw(&mut renderer)?;
+//!
+
+use element::Element;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+
+/// The scene being drawn along with the next scene to draw, if one has been submitted since the
+/// last `swap`.
+pub struct SceneBuffer {
+    current: Element,
+    next: Arc<Mutex<Option<Element>>>,
+}
+
+
+/// Submits scenes to a `SceneBuffer`, possibly from another thread.
+#[derive(Clone)]
+pub struct SceneSender {
+    next: Arc<Mutex<Option<Element>>>,
+}
+
+
+impl SceneBuffer {
+
+    /// Construct a buffer drawing the given scene until another is submitted.
+    pub fn new(initial: Element) -> SceneBuffer {
+        SceneBuffer {
+            current: initial,
+            next: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// A sender through which scenes may be submitted to the buffer.
+    pub fn sender(&self) -> SceneSender {
+        SceneSender { next: self.next.clone() }
+    }
+
+    /// The scene to draw.
+    pub fn current(&self) -> &Element {
+        &self.current
+    }
+
+    /// Replace the current scene with the most recently submitted one, if any have been
+    /// submitted since the last swap.
+    ///
+    /// Returns whether or not the current scene was replaced.
+    pub fn swap(&mut self) -> bool {
+        match lock(&self.next).take() {
+            Some(next) => {
+                self.current = next;
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Consume the buffer, returning the current scene.
+    pub fn into_current(self) -> Element {
+        self.current
+    }
+
+}
+
+
+impl SceneSender {
+
+    /// Submit the next scene to draw, replacing any scene submitted earlier that hasn't yet been
+    /// swapped in.
+    ///
+    /// Returns the replaced scene, if any, so that it may be dropped away from the render thread.
+    pub fn submit(&self, scene: Element) -> Option<Element> {
+        lock(&self.next).replace(scene)
+    }
+
+}
+
+
+/// Lock the next scene. Scenes are plain data, so a thread that panicked while holding the lock
+/// can't have left one half written and the lock is taken regardless.
+fn lock(next: &Mutex<Option<Element>>) -> MutexGuard<'_, Option<Element>> {
+    next.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
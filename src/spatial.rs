@@ -0,0 +1,168 @@
+//!
+//! A spatial index over the `Form`s of a collage for fast hit queries.
+//!
+//! Scanning every `Form` to find those under the cursor is fine for small scenes, but editors
+//! with tens of thousands of forms need something better. A `SpatialIndex` sorts the forms'
+//! bounds into a quadtree so that point and rectangle queries only need to visit the forms near
+//! the query.
+//!
+//! The index borrows the forms it was built from, so it must be rebuilt whenever they change.
+//!
+
+use element::{Element, Prim};
+use form::Form;
+use geometry::Rect;
+
+
+/// The number of forms a node may hold before it is split into quadrants.
+const MAX_NODE_FORMS: usize = 8;
+
+/// The maximum depth of the quadtree.
+const MAX_DEPTH: usize = 12;
+
+
+/// A quadtree over the bounds of some `Form`s.
+#[derive(Clone, Debug)]
+pub struct SpatialIndex<'a> {
+    forms: &'a [Form],
+    /// The bounds of each form, or `None` for forms without bounds (i.e. text).
+    bounds: Vec<Option<Rect>>,
+    root: Node,
+}
+
+
+#[derive(Clone, Debug)]
+struct Node {
+    rect: Rect,
+    /// Indices of the forms whose bounds intersect this node but no single child node.
+    forms: Vec<usize>,
+    children: Option<Box<[Node; 4]>>,
+}
+
+
+impl<'a> SpatialIndex<'a> {
+
+    /// Build an index over the given forms, which are assumed to share a coordinate system.
+    pub fn new(forms: &'a [Form]) -> SpatialIndex<'a> {
+        let bounds: Vec<_> = forms.iter().map(|form| form.bounds()).collect();
+        let rect = bounds.iter()
+            .filter_map(|&b| b)
+            .fold(None, |total: Option<Rect>, b| Some(total.map_or(b, |t| t.union(&b))))
+            .unwrap_or(Rect { min: (0.0, 0.0), max: (0.0, 0.0) });
+        let mut root = Node::new(rect);
+        for (i, b) in bounds.iter().enumerate() {
+            if let Some(ref b) = *b {
+                root.insert(i, b, &bounds, 0);
+            }
+        }
+        SpatialIndex { forms: forms, bounds: bounds, root: root }
+    }
+
+    /// Build an index over the forms of the given collage `Element`.
+    ///
+    /// Returns `None` if the `Element` is not a collage.
+    pub fn from_collage(element: &'a Element) -> Option<SpatialIndex<'a>> {
+        match element.element {
+            Prim::Collage(_, _, ref forms) => Some(SpatialIndex::new(forms)),
+            _ => None,
+        }
+    }
+
+    /// The forms that were indexed.
+    pub fn forms(&self) -> &'a [Form] {
+        self.forms
+    }
+
+    /// The indices of the forms whose bounds contain the given point, in no particular order.
+    ///
+    /// This is a broad-phase query - see `at_point` for the forms that actually lie under the
+    /// point.
+    pub fn bounds_at_point(&self, point: (f64, f64)) -> Vec<usize> {
+        let mut indices = Vec::new();
+        self.root.query(&|rect| rect.contains_point(point), &self.bounds, &mut indices);
+        indices
+    }
+
+    /// The indices of the forms lying under the given point, topmost (i.e. last drawn) first.
+    pub fn at_point(&self, point: (f64, f64)) -> Vec<usize> {
+        let mut indices = self.bounds_at_point(point);
+        indices.retain(|&i| self.forms[i].contains(point));
+        indices.sort_by(|a, b| b.cmp(a));
+        indices
+    }
+
+    /// The index of the topmost form lying under the given point.
+    pub fn topmost_at_point(&self, point: (f64, f64)) -> Option<usize> {
+        self.at_point(point).into_iter().next()
+    }
+
+    /// The indices of the forms whose bounds intersect the given rectangle, in the order in
+    /// which they are drawn.
+    pub fn in_rect(&self, rect: &Rect) -> Vec<usize> {
+        let mut indices = Vec::new();
+        self.root.query(&|b| b.intersects(rect), &self.bounds, &mut indices);
+        indices.sort();
+        indices
+    }
+
+}
+
+
+impl Node {
+
+    fn new(rect: Rect) -> Node {
+        Node { rect: rect, forms: Vec::new(), children: None }
+    }
+
+    /// The node's four quadrants.
+    fn quadrants(&self) -> [Rect; 4] {
+        let Rect { min, max } = self.rect;
+        let (cx, cy) = self.rect.center();
+        [
+            Rect { min: min, max: (cx, cy) },
+            Rect { min: (cx, min.1), max: (max.0, cy) },
+            Rect { min: (min.0, cy), max: (cx, max.1) },
+            Rect { min: (cx, cy), max: max },
+        ]
+    }
+
+    fn insert(&mut self, index: usize, rect: &Rect, bounds: &[Option<Rect>], depth: usize) {
+        if let Some(ref mut children) = self.children {
+            if let Some(child) = children.iter_mut().find(|c| c.rect.contains_rect(rect)) {
+                return child.insert(index, rect, bounds, depth + 1);
+            }
+        }
+        self.forms.push(index);
+
+        // Split once the node becomes too crowded, redistributing the forms that fit a child.
+        if self.children.is_none() && self.forms.len() > MAX_NODE_FORMS && depth < MAX_DEPTH {
+            let [a, b, c, d] = self.quadrants();
+            let children = [Node::new(a), Node::new(b), Node::new(c), Node::new(d)];
+            self.children = Some(Box::new(children));
+            let forms = ::std::mem::take(&mut self.forms);
+            for i in forms {
+                if let Some(ref rect) = bounds[i] {
+                    self.insert(i, rect, bounds, depth);
+                }
+            }
+        }
+    }
+
+    fn query<F>(&self, overlaps: &F, bounds: &[Option<Rect>], indices: &mut Vec<usize>)
+        where F: Fn(&Rect) -> bool,
+    {
+        if !overlaps(&self.rect) {
+            return;
+        }
+        indices.extend(self.forms.iter().cloned().filter(|&i| match bounds[i] {
+            Some(ref rect) => overlaps(rect),
+            None => false,
+        }));
+        if let Some(ref children) = self.children {
+            for child in children.iter() {
+                child.query(overlaps, bounds, indices);
+            }
+        }
+    }
+
+}
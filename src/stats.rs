@@ -0,0 +1,104 @@
+//!
+//! Optional instrumentation collected while rendering.
+//!
+//! Give the `Renderer` a `RenderStats` via `Renderer::stats` and it will be filled while the
+//! `Element` is drawn. This is useful for displaying a performance HUD or for tracking down
+//! expensive parts of a scene.
+//!
+
+use std::time::Duration;
+
+
+/// Statistics collected while drawing an `Element`.
+#[derive(Clone, Debug)]
+pub struct RenderStats {
+    /// The number of draw calls issued to the graphics backend.
+    pub draw_calls: usize,
+    /// The number of `Form`s that were drawn (including `group`s).
+    pub forms_drawn: usize,
+    /// The number of `Form`s that were skipped as they would not have been visible.
+    pub forms_culled: usize,
+    /// The number of text glyphs that were drawn.
+    pub text_glyphs: usize,
+    /// The time taken to draw each `Element` subtree in the order in which they finished drawing.
+    pub subtrees: Vec<SubtreeTime>,
+    /// The depth of the `Element` currently being drawn.
+    depth: usize,
+}
+
+
+/// The time taken to draw a single `Element` and all of its children.
+#[derive(Copy, Clone, Debug)]
+pub struct SubtreeTime {
+    /// The depth of the `Element` within the tree, where the root `Element` is at depth `0`.
+    pub depth: usize,
+    /// The width of the `Element`.
+    pub width: i32,
+    /// The height of the `Element`.
+    pub height: i32,
+    /// The time taken to draw the `Element` and all of its children.
+    pub duration: Duration,
+}
+
+
+impl Default for RenderStats {
+    fn default() -> RenderStats {
+        RenderStats::new()
+    }
+}
+
+
+impl RenderStats {
+
+    /// Construct an empty `RenderStats`.
+    pub fn new() -> RenderStats {
+        RenderStats {
+            draw_calls: 0,
+            forms_drawn: 0,
+            forms_culled: 0,
+            text_glyphs: 0,
+            subtrees: Vec::new(),
+            depth: 0,
+        }
+    }
+
+    /// Clear all collected statistics, ready for the next frame.
+    pub fn reset(&mut self) {
+        *self = RenderStats::new();
+    }
+
+    /// The total time spent drawing the root `Element`s.
+    pub fn total_time(&self) -> Duration {
+        self.subtrees.iter()
+            .filter(|subtree| subtree.depth == 0)
+            .fold(Duration::new(0, 0), |total, subtree| total + subtree.duration)
+    }
+
+    /// Called when beginning to draw an `Element`.
+    pub fn begin_element(&mut self) {
+        self.depth += 1;
+    }
+
+    /// Called when an `Element` of the given size has finished drawing.
+    ///
+    /// An unmatched call records the subtree at the root's depth rather than underflowing.
+    pub fn end_element(&mut self, width: i32, height: i32, duration: Duration) {
+        self.depth = self.depth.saturating_sub(1);
+        self.subtrees.push(SubtreeTime {
+            depth: self.depth,
+            width: width,
+            height: height,
+            duration: duration,
+        });
+    }
+
+}
+
+
+/// Apply `f` to the `RenderStats` if some are being collected.
+#[inline]
+pub fn record<F: FnOnce(&mut RenderStats)>(maybe_stats: &mut Option<&mut RenderStats>, f: F) {
+    if let Some(ref mut stats) = *maybe_stats {
+        f(stats);
+    }
+}
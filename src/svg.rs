@@ -0,0 +1,875 @@
+//!
+//! Import of [SVG path data](https://www.w3.org/TR/SVG11/paths.html#PathData) and simple SVG
+//! documents, allowing vector assets from design tools to be dropped into collages.
+//!
+//! SVG coordinates have their origin at the top left with the y-axis pointing down, whereas
+//! collages have their origin at the center with the y-axis pointing up. `parse_path` leaves
+//! coordinates as they are, while `form::shape_from_svg_path` and `import` flip the y-axis so
+//! that the result appears the right way up within a collage.
+//!
+//! Curves and arcs are flattened into line segments.
+//!
+
+use color::{self, Color};
+use form::{self, Form, PointPath, Shape};
+use std::error;
+use std::f64::consts::PI;
+use std::fmt;
+use transform_2d::{self, Transform2D};
+use utils::{cubic_bezier, quadratic_bezier};
+
+
+/// The number of line segments used to approximate each curve, or each quarter turn of an arc.
+pub const CURVE_SEGMENTS: usize = 16;
+
+
+/// A single continuous run of points within some path data.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SubPath {
+    pub points: Vec<(f64, f64)>,
+    /// Whether or not the subpath was closed with a `Z` command.
+    pub closed: bool,
+}
+
+
+/// The errors that may occur while importing SVG.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Error {
+    /// An unexpected character was found at the given byte index.
+    UnexpectedChar(char, usize),
+    /// A number was expected at the given byte index.
+    ExpectedNumber(usize),
+    /// Path data must begin with a move-to command.
+    MissingMoveTo,
+    /// The tag starting at the given byte index was never closed.
+    UnterminatedTag(usize),
+    /// There was nothing to draw.
+    Empty,
+}
+
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::UnexpectedChar(c, i) => write!(f, "unexpected character {:?} at {}", c, i),
+            Error::ExpectedNumber(i) => write!(f, "expected a number at {}", i),
+            Error::MissingMoveTo => write!(f, "path data must begin with a move-to command"),
+            Error::UnterminatedTag(i) => write!(f, "the tag at {} is not terminated", i),
+            Error::Empty => write!(f, "there is nothing to draw"),
+        }
+    }
+}
+
+
+impl error::Error for Error {
+    fn description(&self) -> &str {
+        match *self {
+            Error::UnexpectedChar(..) => "unexpected character",
+            Error::ExpectedNumber(_) => "expected a number",
+            Error::MissingMoveTo => "missing move-to command",
+            Error::UnterminatedTag(_) => "unterminated tag",
+            Error::Empty => "nothing to draw",
+        }
+    }
+}
+
+
+/// A cursor over the numbers and commands within an attribute.
+struct Parser<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+
+impl<'a> Parser<'a> {
+
+    fn new(s: &'a str) -> Parser<'a> {
+        Parser { bytes: s.as_bytes(), pos: 0 }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).cloned()
+    }
+
+    fn skip_separators(&mut self) {
+        while let Some(b) = self.peek() {
+            if b.is_ascii_whitespace() || b == b',' { self.pos += 1 } else { break }
+        }
+    }
+
+    /// Whether or not the next token is a number.
+    fn has_number(&mut self) -> bool {
+        self.skip_separators();
+        match self.peek() {
+            Some(b) => b.is_ascii_digit() || b == b'.' || b == b'-' || b == b'+',
+            None => false,
+        }
+    }
+
+    fn unexpected(&self) -> Error {
+        match self.peek() {
+            Some(b) => Error::UnexpectedChar(b as char, self.pos),
+            None => Error::ExpectedNumber(self.pos),
+        }
+    }
+
+    fn number(&mut self) -> Result<f64, Error> {
+        self.skip_separators();
+        let start = self.pos;
+        let digits = |parser: &mut Parser| {
+            let start = parser.pos;
+            while parser.peek().is_some_and(|b| b.is_ascii_digit()) {
+                parser.pos += 1;
+            }
+            parser.pos > start
+        };
+        if let Some(b'-') | Some(b'+') = self.peek() {
+            self.pos += 1;
+        }
+        let mut has_digits = digits(self);
+        if let Some(b'.') = self.peek() {
+            self.pos += 1;
+            has_digits |= digits(self);
+        }
+        if !has_digits {
+            return Err(Error::ExpectedNumber(start));
+        }
+        if let Some(b'e') | Some(b'E') = self.peek() {
+            let mantissa_end = self.pos;
+            self.pos += 1;
+            if let Some(b'-') | Some(b'+') = self.peek() {
+                self.pos += 1;
+            }
+            if !digits(self) {
+                self.pos = mantissa_end;
+            }
+        }
+        let s = ::std::str::from_utf8(&self.bytes[start..self.pos]).unwrap_or("");
+        s.parse().map_err(|_| Error::ExpectedNumber(start))
+    }
+
+    fn point(&mut self) -> Result<(f64, f64), Error> {
+        let x = self.number()?;
+        let y = self.number()?;
+        Ok((x, y))
+    }
+
+    /// Arc flags are a single `0` or `1` and need not be followed by a separator.
+    fn flag(&mut self) -> Result<bool, Error> {
+        self.skip_separators();
+        match self.peek() {
+            Some(b'0') => { self.pos += 1; Ok(false) },
+            Some(b'1') => { self.pos += 1; Ok(true) },
+            _ => Err(Error::ExpectedNumber(self.pos)),
+        }
+    }
+
+    /// All remaining numbers.
+    fn numbers(&mut self) -> Result<Vec<f64>, Error> {
+        let mut numbers = Vec::new();
+        while self.has_number() {
+            numbers.push(self.number()?);
+        }
+        Ok(numbers)
+    }
+
+}
+
+
+/// Parse SVG path data into its subpaths.
+///
+/// All commands are supported. Coordinates are left in the SVG coordinate system.
+pub fn parse_path(data: &str) -> Result<Vec<SubPath>, Error> {
+    let mut parser = Parser::new(data);
+    let mut subpaths = Vec::new();
+    let mut current: Option<SubPath> = None;
+    let mut cursor = (0.0, 0.0);
+    let mut start = (0.0, 0.0);
+    let mut command: Option<u8> = None;
+    // The second control point of the previous cubic or quadratic curve for smooth curves.
+    let mut prev_cubic: Option<(f64, f64)> = None;
+    let mut prev_quad: Option<(f64, f64)> = None;
+
+    loop {
+        parser.skip_separators();
+        let cmd = match parser.peek() {
+            None => break,
+            Some(b) if b.is_ascii_alphabetic() => { parser.pos += 1; b },
+            Some(_) if parser.has_number() => match command {
+                // Repeated move-tos are treated as line-tos.
+                Some(b'M') => b'L',
+                Some(b'm') => b'l',
+                Some(b'Z') | Some(b'z') => return Err(parser.unexpected()),
+                Some(c) => c,
+                None => return Err(Error::MissingMoveTo),
+            },
+            Some(_) => return Err(parser.unexpected()),
+        };
+        if command.is_none() && cmd != b'M' && cmd != b'm' {
+            return Err(Error::MissingMoveTo);
+        }
+        command = Some(cmd);
+
+        let relative = cmd.is_ascii_lowercase();
+        let offset = |(x, y): (f64, f64)| {
+            if relative { (cursor.0 + x, cursor.1 + y) } else { (x, y) }
+        };
+        let mut points = Vec::new();
+        let (mut cubic, mut quad) = (None, None);
+
+        match cmd.to_ascii_uppercase() {
+            b'M' => {
+                let point = offset(parser.point()?);
+                finish_subpath(&mut subpaths, current.take());
+                current = Some(SubPath { points: vec![point], closed: false });
+                cursor = point;
+                start = point;
+            },
+            b'Z' => {
+                if let Some(mut subpath) = current.take() {
+                    subpath.closed = true;
+                    finish_subpath(&mut subpaths, Some(subpath));
+                }
+                cursor = start;
+            },
+            b'L' => points.push(offset(parser.point()?)),
+            b'H' => {
+                let x = parser.number()?;
+                points.push((if relative { cursor.0 + x } else { x }, cursor.1));
+            },
+            b'V' => {
+                let y = parser.number()?;
+                points.push((cursor.0, if relative { cursor.1 + y } else { y }));
+            },
+            b'C' | b'S' => {
+                let ctrl_a = if cmd.eq_ignore_ascii_case(&b'C') {
+                    offset(parser.point()?)
+                } else {
+                    prev_cubic.map_or(cursor, |c| reflect(c, cursor))
+                };
+                let ctrl_b = offset(parser.point()?);
+                let end = offset(parser.point()?);
+                points.extend((1..CURVE_SEGMENTS + 1).map(|i| {
+                    cubic_bezier(cursor, ctrl_a, ctrl_b, end, i as f64 / CURVE_SEGMENTS as f64)
+                }));
+                cubic = Some(ctrl_b);
+            },
+            b'Q' | b'T' => {
+                let ctrl = if cmd.eq_ignore_ascii_case(&b'Q') {
+                    offset(parser.point()?)
+                } else {
+                    prev_quad.map_or(cursor, |c| reflect(c, cursor))
+                };
+                let end = offset(parser.point()?);
+                points.extend((1..CURVE_SEGMENTS + 1).map(|i| {
+                    quadratic_bezier(cursor, ctrl, end, i as f64 / CURVE_SEGMENTS as f64)
+                }));
+                quad = Some(ctrl);
+            },
+            b'A' => {
+                let rx = parser.number()?;
+                let ry = parser.number()?;
+                let rotation = parser.number()?;
+                let large_arc = parser.flag()?;
+                let sweep = parser.flag()?;
+                let end = offset(parser.point()?);
+                points = arc_points(cursor, (rx, ry), rotation, large_arc, sweep, end);
+            },
+            _ => return Err(Error::UnexpectedChar(cmd as char, parser.pos - 1)),
+        }
+
+        if let Some(&end) = points.last() {
+            // Drawing after a close-path begins a new subpath at the start of the last.
+            let subpath = current.get_or_insert_with(|| {
+                SubPath { points: vec![start], closed: false }
+            });
+            subpath.points.extend(points);
+            cursor = end;
+        }
+        prev_cubic = cubic;
+        prev_quad = quad;
+    }
+
+    finish_subpath(&mut subpaths, current);
+    Ok(subpaths)
+}
+
+
+/// Push the subpath if it has enough points to be drawn.
+fn finish_subpath(subpaths: &mut Vec<SubPath>, subpath: Option<SubPath>) {
+    if let Some(subpath) = subpath {
+        if subpath.points.len() > 1 {
+            subpaths.push(subpath);
+        }
+    }
+}
+
+
+/// Reflect the point `p` about the point `about`.
+fn reflect(p: (f64, f64), about: (f64, f64)) -> (f64, f64) {
+    (2.0 * about.0 - p.0, 2.0 * about.1 - p.1)
+}
+
+
+/// Flatten an elliptical arc in endpoint parameterization, following the
+/// [SVG implementation notes](https://www.w3.org/TR/SVG11/implnote.html#ArcImplementationNotes).
+///
+/// The returned points do not include `from`.
+fn arc_points(from: (f64, f64), (rx, ry): (f64, f64), rotation: f64, large_arc: bool,
+              sweep: bool, to: (f64, f64)) -> Vec<(f64, f64)> {
+    if from == to {
+        return Vec::new();
+    }
+    let (mut rx, mut ry) = (rx.abs(), ry.abs());
+    if rx == 0.0 || ry == 0.0 {
+        return vec![to];
+    }
+    let (sin, cos) = rotation.to_radians().sin_cos();
+
+    // The start point in the ellipse's rotated space, relative to the chord's midpoint.
+    let (dx, dy) = ((from.0 - to.0) / 2.0, (from.1 - to.1) / 2.0);
+    let (x1, y1) = (cos * dx + sin * dy, -sin * dx + cos * dy);
+
+    // Scale up the radii if they're too small to span the chord.
+    let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+    if lambda > 1.0 {
+        rx *= lambda.sqrt();
+        ry *= lambda.sqrt();
+    }
+
+    let num = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+    let den = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+    let sign = if large_arc == sweep { -1.0 } else { 1.0 };
+    let coef = sign * (num / den).max(0.0).sqrt();
+    let (cx1, cy1) = (coef * rx * y1 / ry, -coef * ry * x1 / rx);
+    let center = (cos * cx1 - sin * cy1 + (from.0 + to.0) / 2.0,
+                  sin * cx1 + cos * cy1 + (from.1 + to.1) / 2.0);
+
+    let angle = |(ux, uy): (f64, f64), (vx, vy): (f64, f64)| {
+        (ux * vy - uy * vx).atan2(ux * vx + uy * vy)
+    };
+    let u = ((x1 - cx1) / rx, (y1 - cy1) / ry);
+    let v = ((-x1 - cx1) / rx, (-y1 - cy1) / ry);
+    let theta = angle((1.0, 0.0), u);
+    let mut delta = angle(u, v);
+    if !sweep && delta > 0.0 {
+        delta -= 2.0 * PI;
+    } else if sweep && delta < 0.0 {
+        delta += 2.0 * PI;
+    }
+
+    let n = ((delta.abs() / (PI / 2.0)) * CURVE_SEGMENTS as f64).ceil().max(1.0) as usize;
+    let mut points: Vec<_> = (1..n).map(|i| {
+        let (s, c) = (theta + delta * i as f64 / n as f64).sin_cos();
+        (center.0 + rx * c * cos - ry * s * sin, center.1 + rx * c * sin + ry * s * cos)
+    }).collect();
+    points.push(to);
+    points
+}
+
+
+/// The points along an ellipse with the given center and radii.
+fn ellipse_points((cx, cy): (f64, f64), rx: f64, ry: f64) -> Vec<(f64, f64)> {
+    let n = CURVE_SEGMENTS * 4;
+    (0..n).map(|i| {
+        let t = 2.0 * PI * i as f64 / n as f64;
+        (cx + rx * t.cos(), cy + ry * t.sin())
+    }).collect()
+}
+
+
+/// Parse an SVG `transform` attribute.
+fn parse_transform(s: &str) -> Result<Transform2D, Error> {
+    let mut parser = Parser::new(s);
+    let mut transform = transform_2d::identity();
+    loop {
+        parser.skip_separators();
+        let name_start = parser.pos;
+        while parser.peek().is_some_and(|b| b.is_ascii_alphabetic()) {
+            parser.pos += 1;
+        }
+        if parser.pos == name_start {
+            match parser.peek() {
+                None => break,
+                Some(_) => return Err(parser.unexpected()),
+            }
+        }
+        let name = &s[name_start..parser.pos];
+        parser.skip_separators();
+        if parser.peek() != Some(b'(') {
+            return Err(parser.unexpected());
+        }
+        parser.pos += 1;
+        let args = parser.numbers()?;
+        parser.skip_separators();
+        if parser.peek() != Some(b')') {
+            return Err(parser.unexpected());
+        }
+        parser.pos += 1;
+
+        let arg = |i: usize, default: f64| args.get(i).cloned().unwrap_or(default);
+        let next = match name {
+            "matrix" => transform_2d::matrix(arg(0, 1.0), arg(2, 0.0), arg(1, 0.0), arg(3, 1.0),
+                                             arg(4, 0.0), arg(5, 0.0)),
+            "translate" => transform_2d::translation(arg(0, 0.0), arg(1, 0.0)),
+            "scale" => {
+                let x = arg(0, 1.0);
+                transform_2d::matrix(x, 0.0, 0.0, arg(1, x), 0.0, 0.0)
+            },
+            "rotate" => {
+                let (cx, cy) = (arg(1, 0.0), arg(2, 0.0));
+                transform_2d::translation(cx, cy)
+                    .multiply(transform_2d::rotation(arg(0, 0.0).to_radians()))
+                    .multiply(transform_2d::translation(-cx, -cy))
+            },
+            "skewX" => {
+                let skew = arg(0, 0.0).to_radians().tan();
+                transform_2d::matrix(1.0, skew, 0.0, 1.0, 0.0, 0.0)
+            },
+            "skewY" => {
+                let skew = arg(0, 0.0).to_radians().tan();
+                transform_2d::matrix(1.0, 0.0, skew, 1.0, 0.0, 0.0)
+            },
+            _ => return Err(Error::UnexpectedChar(name.chars().next().unwrap_or(' '), name_start)),
+        };
+        transform = transform.multiply(next);
+    }
+    Ok(transform)
+}
+
+
+/// Parse an SVG paint, where `Some(None)` describes `none`.
+///
+/// Returns `None` for unsupported paints such as gradient references.
+fn parse_paint(s: &str) -> Option<Option<Color>> {
+    let s = s.trim();
+    let hex = |s: &str| u8::from_str_radix(s, 16).ok();
+    if s == "none" {
+        return Some(None);
+    }
+    if let Some(digits) = s.strip_prefix('#') {
+        let (r, g, b) = match digits.len() {
+            3 => {
+                let double = |i: usize| hex(&digits[i..i + 1]).map(|n| n * 17);
+                (double(0), double(1), double(2))
+            },
+            6 => (hex(&digits[0..2]), hex(&digits[2..4]), hex(&digits[4..6])),
+            _ => return None,
+        };
+        return match (r, g, b) {
+            (Some(r), Some(g), Some(b)) => Some(Some(color::rgb_bytes(r, g, b))),
+            _ => None,
+        };
+    }
+    if s.starts_with("rgb(") && s.ends_with(')') {
+        let channels: Vec<u8> = s[4..s.len() - 1].split(',')
+            .filter_map(|c| c.trim().parse::<f64>().ok())
+            .map(|c| c.clamp(0.0, 255.0) as u8)
+            .collect();
+        return match channels[..] {
+            [r, g, b] => Some(Some(color::rgb_bytes(r, g, b))),
+            _ => None,
+        };
+    }
+    let (r, g, b) = match s {
+        "black" => (0, 0, 0),
+        "white" => (255, 255, 255),
+        "red" => (255, 0, 0),
+        "lime" => (0, 255, 0),
+        "green" => (0, 128, 0),
+        "blue" => (0, 0, 255),
+        "yellow" => (255, 255, 0),
+        "cyan" | "aqua" => (0, 255, 255),
+        "magenta" | "fuchsia" => (255, 0, 255),
+        "gray" | "grey" => (128, 128, 128),
+        "silver" => (192, 192, 192),
+        "maroon" => (128, 0, 0),
+        "olive" => (128, 128, 0),
+        "navy" => (0, 0, 128),
+        "purple" => (128, 0, 128),
+        "teal" => (0, 128, 128),
+        "orange" => (255, 165, 0),
+        _ => return None,
+    };
+    Some(Some(color::rgb_bytes(r, g, b)))
+}
+
+
+/// Parse a length attribute, ignoring any units.
+fn parse_length(s: &str) -> Option<f64> {
+    Parser::new(s).number().ok()
+}
+
+
+/// The inheritable presentation attributes.
+#[derive(Clone)]
+struct Paint {
+    fill: Option<Color>,
+    stroke: Option<Color>,
+    stroke_width: f64,
+    fill_opacity: f32,
+    stroke_opacity: f32,
+}
+
+
+impl Paint {
+
+    fn default() -> Paint {
+        Paint {
+            fill: Some(color::black()),
+            stroke: None,
+            stroke_width: 1.0,
+            fill_opacity: 1.0,
+            stroke_opacity: 1.0,
+        }
+    }
+
+    /// Apply the presentation attributes among the given attributes.
+    fn apply(&mut self, attributes: &[(&str, &str)]) {
+        for &(name, value) in attributes.iter() {
+            match name {
+                "fill" => if let Some(fill) = parse_paint(value) { self.fill = fill },
+                "stroke" => if let Some(stroke) = parse_paint(value) { self.stroke = stroke },
+                "stroke-width" => if let Some(w) = parse_length(value) { self.stroke_width = w },
+                "fill-opacity" =>
+                    if let Some(a) = parse_length(value) { self.fill_opacity = a as f32 },
+                "stroke-opacity" =>
+                    if let Some(a) = parse_length(value) { self.stroke_opacity = a as f32 },
+                _ => (),
+            }
+        }
+    }
+
+    /// Produce the forms for the given subpaths.
+    fn forms(&self, subpaths: Vec<SubPath>) -> Vec<Form> {
+        let mut forms = Vec::new();
+        for SubPath { points, closed } in subpaths {
+            if let Some(color) = self.fill {
+                if points.len() > 2 {
+                    forms.push(Shape(points.clone()).filled(color.alpha(self.fill_opacity)));
+                }
+            }
+            if let Some(color) = self.stroke {
+                let style = form::solid(color.alpha(self.stroke_opacity)).width(self.stroke_width);
+                forms.push(if closed {
+                    Shape(points).outlined(style)
+                } else {
+                    form::traced(style, PointPath(points))
+                });
+            }
+        }
+        forms
+    }
+
+}
+
+
+/// A tag within an SVG document.
+struct Tag<'a> {
+    name: &'a str,
+    attributes: Vec<(&'a str, &'a str)>,
+    closing: bool,
+    self_closing: bool,
+}
+
+
+impl<'a> Tag<'a> {
+
+    fn attribute(&self, name: &str) -> Option<&'a str> {
+        self.attributes.iter().rev().find(|&&(n, _)| n == name).map(|&(_, v)| v)
+    }
+
+    fn length(&self, name: &str) -> f64 {
+        self.attribute(name).and_then(parse_length).unwrap_or(0.0)
+    }
+
+}
+
+
+/// Parse the tag beginning at the `<` at `start`, returning it along with the index of the end of
+/// the tag. Returns `None` for comments, declarations and processing instructions.
+fn parse_tag<'a>(svg: &'a str, start: usize) -> Result<(Option<Tag<'a>>, usize), Error> {
+    let rest = &svg[start..];
+    let skip_to = |terminator: &str| rest.find(terminator)
+        .map(|i| start + i + terminator.len())
+        .ok_or(Error::UnterminatedTag(start));
+    if rest.starts_with("<!--") {
+        return Ok((None, skip_to("-->")?));
+    }
+    if rest.starts_with("<![CDATA[") {
+        return Ok((None, skip_to("]]>")?));
+    }
+    if rest.starts_with("<?") || rest.starts_with("<!") {
+        return Ok((None, skip_to(">")?));
+    }
+
+    let bytes = svg.as_bytes();
+    let mut pos = start + 1;
+    let closing = bytes.get(pos) == Some(&b'/');
+    if closing {
+        pos += 1;
+    }
+    let is_name_char = |b: u8| !b.is_ascii_whitespace() && b != b'/' && b != b'>' && b != b'=';
+    let name_start = pos;
+    while pos < bytes.len() && is_name_char(bytes[pos]) {
+        pos += 1;
+    }
+    let name = &svg[name_start..pos];
+
+    let mut attributes = Vec::new();
+    loop {
+        while pos < bytes.len() && bytes[pos].is_ascii_whitespace() {
+            pos += 1;
+        }
+        match bytes.get(pos) {
+            None => return Err(Error::UnterminatedTag(start)),
+            Some(&b'>') => {
+                let tag = Tag { name: name, attributes: attributes, closing: closing,
+                                self_closing: false };
+                return Ok((Some(tag), pos + 1));
+            },
+            Some(&b'/') if bytes.get(pos + 1) == Some(&b'>') => {
+                let tag = Tag { name: name, attributes: attributes, closing: closing,
+                                self_closing: true };
+                return Ok((Some(tag), pos + 2));
+            },
+            Some(&b) if !is_name_char(b) => return Err(Error::UnexpectedChar(b as char, pos)),
+            Some(_) => (),
+        }
+        let attr_start = pos;
+        while pos < bytes.len() && is_name_char(bytes[pos]) {
+            pos += 1;
+        }
+        let attr_name = &svg[attr_start..pos];
+        while pos < bytes.len() && bytes[pos].is_ascii_whitespace() {
+            pos += 1;
+        }
+        if bytes.get(pos) != Some(&b'=') {
+            // An attribute without a value.
+            attributes.push((attr_name, ""));
+            continue;
+        }
+        pos += 1;
+        while pos < bytes.len() && bytes[pos].is_ascii_whitespace() {
+            pos += 1;
+        }
+        let quote = match bytes.get(pos) {
+            Some(&q) if q == b'"' || q == b'\'' => q,
+            Some(&b) => return Err(Error::UnexpectedChar(b as char, pos)),
+            None => return Err(Error::UnterminatedTag(start)),
+        };
+        let value_start = pos + 1;
+        let value_end = bytes[value_start..].iter().position(|&b| b == quote)
+            .map(|i| value_start + i)
+            .ok_or(Error::UnterminatedTag(start))?;
+        let value = &svg[value_start..value_end];
+        if attr_name == "style" {
+            // Treat style properties as presentation attributes.
+            for property in value.split(';') {
+                let mut parts = property.splitn(2, ':');
+                if let (Some(n), Some(v)) = (parts.next(), parts.next()) {
+                    attributes.push((n.trim(), v.trim()));
+                }
+            }
+        } else {
+            attributes.push((attr_name, value));
+        }
+        pos = value_end + 1;
+    }
+}
+
+
+/// The subpaths described by some shape tag, or `None` if the tag does not describe a shape.
+fn shape_subpaths(tag: &Tag) -> Result<Option<Vec<SubPath>>, Error> {
+    let subpath = |points: Vec<(f64, f64)>, closed: bool| {
+        vec![SubPath { points: points, closed: closed }]
+    };
+    let subpaths = match tag.name {
+        "path" => parse_path(tag.attribute("d").unwrap_or(""))?,
+        "rect" => {
+            let (x, y) = (tag.length("x"), tag.length("y"));
+            let (w, h) = (tag.length("width"), tag.length("height"));
+            subpath(vec![(x, y), (x + w, y), (x + w, y + h), (x, y + h)], true)
+        },
+        "circle" => {
+            let r = tag.length("r");
+            subpath(ellipse_points((tag.length("cx"), tag.length("cy")), r, r), true)
+        },
+        "ellipse" => {
+            let center = (tag.length("cx"), tag.length("cy"));
+            subpath(ellipse_points(center, tag.length("rx"), tag.length("ry")), true)
+        },
+        "line" => {
+            let a = (tag.length("x1"), tag.length("y1"));
+            let b = (tag.length("x2"), tag.length("y2"));
+            subpath(vec![a, b], false)
+        },
+        "polyline" | "polygon" => {
+            let numbers = Parser::new(tag.attribute("points").unwrap_or("")).numbers()?;
+            let points = numbers.chunks(2).filter(|c| c.len() == 2).map(|c| (c[0], c[1])).collect();
+            subpath(points, tag.name == "polygon")
+        },
+        _ => return Ok(None),
+    };
+    Ok(Some(subpaths))
+}
+
+
+/// A tag that has been opened but not yet closed.
+struct Open {
+    name: String,
+    /// The transform, opacity, paint and forms collected so far if the tag is a group.
+    group: Option<(Option<Transform2D>, f32, Paint, Vec<Form>)>,
+}
+
+
+/// Push the form into the innermost open group, or into the root if there is none.
+fn push_form(stack: &mut [Open], root: &mut Vec<Form>, form: Form) {
+    match stack.iter_mut().rev().filter_map(|open| open.group.as_mut()).next() {
+        Some(&mut (_, _, _, ref mut forms)) => forms.push(form),
+        None => root.push(form),
+    }
+}
+
+
+/// Wrap the forms in a group with the tag's transform and opacity.
+fn group_forms(tag_transform: Option<Transform2D>, opacity: f32, mut forms: Vec<Form>) -> Form {
+    // Avoid needless nesting where a single form can carry the opacity itself.
+    if tag_transform.is_none() && forms.len() == 1 && forms[0].alpha == 1.0 {
+        return forms.pop().unwrap().alpha(opacity);
+    }
+    let group = form::group_transform(tag_transform.unwrap_or_else(transform_2d::identity), forms);
+    group.alpha(opacity)
+}
+
+
+/// Import a simple SVG document as a single group `Form`.
+///
+/// Supports `path`, `rect`, `circle`, `ellipse`, `line`, `polyline` and `polygon` shapes within
+/// nested `g` groups, along with the `transform`, `fill`, `stroke`, `stroke-width`, `opacity`,
+/// `fill-opacity` and `stroke-opacity` attributes (including those within a `style` attribute).
+/// Anything else, such as text, gradients, clipping and the contents of `defs`, is ignored.
+///
+/// The document is centered upon the origin using its `viewBox` (or its `width` and `height`) and
+/// flipped to match the collage coordinate system.
+pub fn import(svg: &str) -> Result<Form, Error> {
+    // Tags whose contents are never drawn directly.
+    const HIDDEN: &[&str] =
+        &["defs", "clipPath", "mask", "symbol", "pattern", "marker", "style", "title", "desc",
+          "metadata", "linearGradient", "radialGradient"];
+
+    let mut stack: Vec<Open> = Vec::new();
+    let mut root: Vec<Form> = Vec::new();
+    let mut view = transform_2d::scale_y(-1.0);
+    let mut hidden_depth = 0;
+    let mut pos = 0;
+
+    while let Some(i) = svg[pos..].find('<') {
+        let (tag, end) = parse_tag(svg, pos + i)?;
+        pos = end;
+        let tag = match tag {
+            Some(tag) => tag,
+            None => continue,
+        };
+
+        if tag.closing {
+            if let Some(open) = stack.pop() {
+                if HIDDEN.contains(&&open.name[..]) {
+                    hidden_depth -= 1;
+                }
+                if let Some((transform, opacity, _, forms)) = open.group {
+                    let group = group_forms(transform, opacity, forms);
+                    push_form(&mut stack, &mut root, group);
+                }
+            }
+            continue;
+        }
+
+        if HIDDEN.contains(&tag.name) {
+            if !tag.self_closing {
+                hidden_depth += 1;
+                stack.push(Open { name: tag.name.to_string(), group: None });
+            }
+            continue;
+        }
+        if hidden_depth > 0 {
+            if !tag.self_closing {
+                stack.push(Open { name: tag.name.to_string(), group: None });
+            }
+            continue;
+        }
+
+        let mut paint = stack.iter().rev().filter_map(|o| o.group.as_ref())
+            .map(|&(_, _, ref paint, _)| paint.clone())
+            .next()
+            .unwrap_or_else(Paint::default);
+        paint.apply(&tag.attributes);
+        let transform = match tag.attribute("transform") {
+            Some(t) => Some(parse_transform(t)?),
+            None => None,
+        };
+        let opacity = tag.attribute("opacity").and_then(parse_length).unwrap_or(1.0) as f32;
+
+        if tag.name == "svg" && stack.is_empty() {
+            view = view.multiply(document_transform(&tag));
+        }
+
+        if tag.name == "svg" || tag.name == "g" {
+            if !tag.self_closing {
+                let group = Some((transform, opacity, paint, Vec::new()));
+                stack.push(Open { name: tag.name.to_string(), group: group });
+            }
+            continue;
+        }
+
+        if let Some(subpaths) = shape_subpaths(&tag)? {
+            let forms = paint.forms(subpaths);
+            if !forms.is_empty() {
+                let form = group_forms(transform, opacity, forms);
+                push_form(&mut stack, &mut root, form);
+            }
+        }
+        if !tag.self_closing {
+            stack.push(Open { name: tag.name.to_string(), group: None });
+        }
+    }
+
+    // Close any groups that were left open.
+    while let Some(open) = stack.pop() {
+        if let Some((transform, opacity, _, forms)) = open.group {
+            let group = group_forms(transform, opacity, forms);
+            push_form(&mut stack, &mut root, group);
+        }
+    }
+
+    if root.is_empty() {
+        return Err(Error::Empty);
+    }
+    Ok(form::group_transform(view, root))
+}
+
+
+/// The transform that centers the document described by the given root `svg` tag upon the
+/// origin, scaling the `viewBox` to the `width` and `height` if both are given.
+fn document_transform(tag: &Tag) -> Transform2D {
+    let size = match (tag.attribute("width").and_then(parse_length),
+                      tag.attribute("height").and_then(parse_length)) {
+        (Some(w), Some(h)) => Some((w, h)),
+        _ => None,
+    };
+    let view_box = tag.attribute("viewBox")
+        .and_then(|v| Parser::new(v).numbers().ok())
+        .and_then(|v| if v.len() == 4 { Some((v[0], v[1], v[2], v[3])) } else { None });
+    match (view_box, size) {
+        (Some((x, y, vw, vh)), size) => {
+            let (sx, sy) = match size {
+                Some((w, h)) if vw > 0.0 && vh > 0.0 => (w / vw, h / vh),
+                _ => (1.0, 1.0),
+            };
+            transform_2d::matrix(sx, 0.0, 0.0, sy, 0.0, 0.0)
+                .multiply(transform_2d::translation(-x - vw / 2.0, -y - vh / 2.0))
+        },
+        (None, Some((w, h))) => transform_2d::translation(-w / 2.0, -h / 2.0),
+        (None, None) => transform_2d::identity(),
+    }
+}
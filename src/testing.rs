@@ -0,0 +1,532 @@
+//!
+//! Utilities for snapshot testing the rendering of `Element`s.
+//!
+//! `render` draws an `Element` into an in-memory RGBA `Image` using a small software
+//! implementation of piston's `Graphics` trait, so no window or GPU is required. It requires the
+//! `piston-backend` feature. With the `tiny-skia` feature, `render_skia` draws with the higher
+//! quality `backend::skia` renderer instead. Enabling a feature never changes which renderer an
+//! existing golden image was drawn by.
+//! The resulting `Image` can then be compared against a "golden" image stored on disk using
+//! `assert_golden`, allowing for some `Tolerance` in case of small rasterization differences.
+//!
+//! Golden images are stored in the binary [PAM](http://netpbm.sourceforge.net/doc/pam.html)
+//! format so that no image codec dependencies are required. If the golden file does not yet exist,
+//! or the `ELMESQUE_UPDATE_GOLDEN` environment variable is set, the golden file is (re)written
+//! from the rendered image instead.
+//!
+//!   let element = collage(64, 64, vec![circle(20.0).filled(red())]);
+//!   let image = testing::render(&element, 64, 64).unwrap();
+//!   testing::assert_golden("tests/golden/red_circle.pam", &image, Tolerance::default());
+//!
+
+#[cfg(feature = "piston-backend")]
+use color::f32_to_byte;
+#[cfg(any(feature = "piston-backend", feature = "tiny-skia"))]
+use element::Element;
+#[cfg(feature = "piston-backend")]
+use error::Error;
+#[cfg(feature = "piston-backend")]
+use graphics::{Graphics, ImageSize};
+#[cfg(feature = "piston-backend")]
+use graphics::character::{Character, CharacterCache, FontSize};
+#[cfg(feature = "piston-backend")]
+use graphics::draw_state::DrawState;
+#[cfg(feature = "piston-backend")]
+use graphics::draw_state::state::{Comparison, MASK_NONE, StencilOp, StencilSide};
+#[cfg(feature = "piston-backend")]
+use graphics::draw_state::target::Rect;
+use std::env;
+use std::fmt;
+use std::fs::File;
+use std::hash::Hasher;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+#[cfg(feature = "piston-backend")]
+use utils::clampf32;
+use utils::FnvHasher;
+
+
+/// The environment variable that, when set, causes `assert_golden` to overwrite golden files.
+pub const UPDATE_GOLDEN_VAR: &str = "ELMESQUE_UPDATE_GOLDEN";
+
+
+/// An 8-bit RGBA image, stored row by row from the top left corner.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Image {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+
+/// The maximum difference allowed between a rendered `Image` and its golden `Image`.
+#[derive(Copy, Clone, Debug)]
+pub struct Tolerance {
+    /// The largest difference allowed between any two channels before a pixel is considered
+    /// differing.
+    pub channel: u8,
+    /// The fraction (0.0 to 1.0) of all pixels that are allowed to differ.
+    pub pixels: f32,
+}
+
+
+/// The ways in which two `Image`s may fail to match.
+#[derive(Clone, Debug)]
+pub enum Mismatch {
+    /// The images have different dimensions.
+    Size { expected: (u32, u32), found: (u32, u32) },
+    /// Too many pixels differed by more than the tolerated amount.
+    Pixels { differing: usize, total: usize },
+}
+
+
+/// The texture type used by the software `Image` renderer.
+///
+/// Textures are not yet supported by the software renderer, so this only describes a size.
+#[cfg(feature = "piston-backend")]
+#[derive(Copy, Clone, Debug)]
+pub struct Texture {
+    pub width: u32,
+    pub height: u32,
+}
+
+
+/// An `Image` drawn along with a stencil buffer, so that `render` clips to shapes such as rounded
+/// corners as a window with a stencil buffer would.
+#[cfg(feature = "piston-backend")]
+struct StencilTarget<'a> {
+    image: &'a mut Image,
+    stencil: Vec<u8>,
+}
+
+
+/// A `CharacterCache` with no glyphs, used to satisfy the `Renderer`'s type parameters when
+/// rendering without text.
+#[cfg(feature = "piston-backend")]
+pub struct NoGlyphs {
+    texture: Texture,
+}
+
+
+impl Image {
+
+    /// Construct a fully transparent image of the given size.
+    pub fn new(width: u32, height: u32) -> Image {
+        Image {
+            width: width,
+            height: height,
+            pixels: vec![0; width as usize * height as usize * 4],
+        }
+    }
+
+    /// The RGBA value of the pixel at the given position.
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        let i = (y as usize * self.width as usize + x as usize) * 4;
+        [self.pixels[i], self.pixels[i+1], self.pixels[i+2], self.pixels[i+3]]
+    }
+
+    /// A hash of the image's dimensions and pixels.
+    ///
+    /// Uses 64-bit FNV-1a so that the result is stable across platforms and compiler versions.
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = FnvHasher::default();
+        hasher.write(&self.width.to_le_bytes());
+        hasher.write(&self.height.to_le_bytes());
+        hasher.write(&self.pixels);
+        hasher.finish()
+    }
+
+    /// Write the image in the binary PAM format.
+    pub fn write_pam<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        write!(writer, "P7\nWIDTH {}\nHEIGHT {}\nDEPTH 4\nMAXVAL 255\nTUPLTYPE RGB_ALPHA\nENDHDR\n",
+               self.width, self.height)?;
+        writer.write_all(&self.pixels)
+    }
+
+    /// Read an image in the binary PAM format. Only 8-bit RGB_ALPHA images are supported.
+    pub fn read_pam<R: Read>(reader: R) -> io::Result<Image> {
+        let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, msg.to_string());
+        let mut reader = BufReader::new(reader);
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        if line.trim() != "P7" {
+            return Err(invalid("not a PAM image"));
+        }
+        let (mut width, mut height, mut depth) = (None, None, None);
+        loop {
+            line.clear();
+            if reader.read_line(&mut line)? == 0 {
+                return Err(invalid("unexpected end of PAM header"));
+            }
+            let mut words = line.split_whitespace();
+            let value = |words: &mut ::std::str::SplitWhitespace| {
+                words.next().and_then(|w| w.parse::<u32>().ok())
+            };
+            match words.next() {
+                Some("ENDHDR") => break,
+                Some("WIDTH") => width = value(&mut words),
+                Some("HEIGHT") => height = value(&mut words),
+                Some("DEPTH") => depth = value(&mut words),
+                Some("MAXVAL") if value(&mut words) != Some(255) =>
+                    return Err(invalid("only a MAXVAL of 255 is supported")),
+                _ => (),
+            }
+        }
+        let (width, height) = match (width, height, depth) {
+            (Some(w), Some(h), Some(4)) => (w, h),
+            _ => return Err(invalid("expected a PAM image with WIDTH, HEIGHT and a DEPTH of 4")),
+        };
+        let mut image = Image::new(width, height);
+        reader.read_exact(&mut image.pixels)?;
+        Ok(image)
+    }
+
+}
+
+
+#[cfg(feature = "piston-backend")]
+impl Image {
+
+    /// Blend the given color over the pixel at the given position.
+    fn blend(&mut self, x: u32, y: u32, color: [f32; 4]) {
+        let i = (y as usize * self.width as usize + x as usize) * 4;
+        let src_a = clampf32(color[3]);
+        let dst_a = self.pixels[i+3] as f32 / 255.0;
+        let out_a = src_a + dst_a * (1.0 - src_a);
+        for (c, &src) in color[..3].iter().enumerate() {
+            let src = clampf32(src);
+            let dst = self.pixels[i+c] as f32 / 255.0;
+            let out = if out_a > 0.0 { (src * src_a + dst * dst_a * (1.0 - src_a)) / out_a }
+                      else { 0.0 };
+            self.pixels[i+c] = f32_to_byte(out);
+        }
+        self.pixels[i+3] = f32_to_byte(out_a);
+    }
+
+    /// Fill the triangle described by the given normalized device coordinates.
+    ///
+    /// A pixel is filled if its center lies within the triangle and within the draw state's
+    /// scissor `Rect` (if there is one). If some stencil buffer is given, pixels must also pass
+    /// the draw state's stencil test, which updates the buffer. Color is only written if the
+    /// draw state's color mask allows it.
+    fn fill_triangle(&mut self, vertices: &[f32], color: [f32; 4], draw_state: &DrawState,
+                     mut stencil: Option<&mut [u8]>) {
+        let scissor: Option<Rect> = draw_state.scissor;
+        let (w, h) = (self.width as f32, self.height as f32);
+        let to_px = |i: usize| ((vertices[i] + 1.0) * 0.5 * w, (1.0 - vertices[i+1]) * 0.5 * h);
+        let (a, b, c) = (to_px(0), to_px(2), to_px(4));
+        let edge = |(ax, ay): (f32, f32), (bx, by): (f32, f32), (px, py): (f32, f32)| {
+            (bx - ax) * (py - ay) - (by - ay) * (px - ax)
+        };
+        if edge(a, b, c) == 0.0 {
+            return;
+        }
+
+        let clamp_x = |x: f32| if x < 0.0 { 0 } else if x > w { self.width } else { x as u32 };
+        let clamp_y = |y: f32| if y < 0.0 { 0 } else if y > h { self.height } else { y as u32 };
+        let min_x = clamp_x(a.0.min(b.0).min(c.0).floor());
+        let max_x = clamp_x(a.0.max(b.0).max(c.0).ceil());
+        let min_y = clamp_y(a.1.min(b.1).min(c.1).floor());
+        let max_y = clamp_y(a.1.max(b.1).max(c.1).ceil());
+
+        for y in min_y..max_y {
+            for x in min_x..max_x {
+                // The scissor `Rect` uses a bottom-left origin.
+                if let Some(rect) = scissor {
+                    let (sx, sy) = (x as i64, (self.height - 1 - y) as i64);
+                    if sx < rect.x as i64 || sx >= rect.x as i64 + rect.w as i64
+                    || sy < rect.y as i64 || sy >= rect.y as i64 + rect.h as i64 {
+                        continue;
+                    }
+                }
+                let p = (x as f32 + 0.5, y as f32 + 0.5);
+                let (w0, w1, w2) = (edge(b, c, p), edge(c, a, p), edge(a, b, p));
+                let inside = (w0 >= 0.0 && w1 >= 0.0 && w2 >= 0.0)
+                    || (w0 <= 0.0 && w1 <= 0.0 && w2 <= 0.0);
+                if !inside {
+                    continue;
+                }
+                let test = draw_state.stencil;
+                if let (Some(ref mut stencil), Some(test)) = (stencil.as_mut(), test) {
+                    let i = y as usize * self.width as usize + x as usize;
+                    if !stencil_test(&test.front, &mut stencil[i]) {
+                        continue;
+                    }
+                }
+                if draw_state.color_mask != MASK_NONE {
+                    self.blend(x, y, color);
+                }
+            }
+        }
+    }
+
+}
+
+
+#[cfg(feature = "piston-backend")]
+impl Graphics for Image {
+    type Texture = Texture;
+
+    fn clear_color(&mut self, color: [f32; 4]) {
+        let bytes = [f32_to_byte(color[0]), f32_to_byte(color[1]),
+                     f32_to_byte(color[2]), f32_to_byte(color[3])];
+        for pixel in self.pixels.chunks_mut(4) {
+            pixel.copy_from_slice(&bytes);
+        }
+    }
+
+    /// The software renderer has no stencil buffer, so clip shapes drawn into it are ignored.
+    /// An `Image` has no stencil buffer, so stencil tests are ignored and clip shapes drawn into
+    /// the stencil buffer have no effect. `render` draws with a stencil buffer.
+    fn clear_stencil(&mut self, _value: u8) {}
+
+    fn tri_list<F>(&mut self, draw_state: &DrawState, color: &[f32; 4], mut f: F)
+        where F: FnMut(&mut dyn FnMut(&[f32]))
+    {
+        f(&mut |vertices: &[f32]| {
+            for triangle in vertices.chunks(6).filter(|t| t.len() == 6) {
+                self.fill_triangle(triangle, *color, draw_state, None);
+            }
+        });
+    }
+
+    fn tri_list_uv<F>(&mut self, draw_state: &DrawState, color: &[f32; 4], _texture: &Texture,
+                      mut f: F)
+        where F: FnMut(&mut dyn FnMut(&[f32], &[f32]))
+    {
+        // Textures are not yet sampled, so textured triangles are filled with their color.
+        f(&mut |vertices: &[f32], _uvs: &[f32]| {
+            for triangle in vertices.chunks(6).filter(|t| t.len() == 6) {
+                self.fill_triangle(triangle, *color, draw_state, None);
+            }
+        });
+    }
+}
+
+
+#[cfg(feature = "piston-backend")]
+impl<'a> Graphics for StencilTarget<'a> {
+    type Texture = Texture;
+
+    fn clear_color(&mut self, color: [f32; 4]) {
+        self.image.clear_color(color);
+    }
+
+    fn clear_stencil(&mut self, value: u8) {
+        for stencil in self.stencil.iter_mut() {
+            *stencil = value;
+        }
+    }
+
+    fn tri_list<F>(&mut self, draw_state: &DrawState, color: &[f32; 4], mut f: F)
+        where F: FnMut(&mut dyn FnMut(&[f32]))
+    {
+        let StencilTarget { ref mut image, ref mut stencil } = *self;
+        f(&mut |vertices: &[f32]| {
+            for triangle in vertices.chunks(6).filter(|t| t.len() == 6) {
+                image.fill_triangle(triangle, *color, draw_state, Some(&mut stencil[..]));
+            }
+        });
+    }
+
+    fn tri_list_uv<F>(&mut self, draw_state: &DrawState, color: &[f32; 4], _texture: &Texture,
+                      mut f: F)
+        where F: FnMut(&mut dyn FnMut(&[f32], &[f32]))
+    {
+        let StencilTarget { ref mut image, ref mut stencil } = *self;
+        f(&mut |vertices: &[f32], _uvs: &[f32]| {
+            for triangle in vertices.chunks(6).filter(|t| t.len() == 6) {
+                image.fill_triangle(triangle, *color, draw_state, Some(&mut stencil[..]));
+            }
+        });
+    }
+}
+
+
+/// Apply the given side of a stencil test to a pixel's stencil value, as a GPU would, returning
+/// whether the pixel passes.
+#[cfg(feature = "piston-backend")]
+fn stencil_test(side: &StencilSide, value: &mut u8) -> bool {
+    let (reference, current) = (side.value & side.mask_read, *value & side.mask_read);
+    let pass = match side.fun {
+        Comparison::Never => false,
+        Comparison::Less => reference < current,
+        Comparison::LessEqual => reference <= current,
+        Comparison::Equal => reference == current,
+        Comparison::GreaterEqual => reference >= current,
+        Comparison::Greater => reference > current,
+        Comparison::NotEqual => reference != current,
+        Comparison::Always => true,
+    };
+    let updated = match if pass { side.op_pass } else { side.op_fail } {
+        StencilOp::Keep => *value,
+        StencilOp::Zero => 0,
+        StencilOp::Replace => side.value,
+        StencilOp::IncrementClamp => value.saturating_add(1),
+        StencilOp::IncrementWrap => value.wrapping_add(1),
+        StencilOp::DecrementClamp => value.saturating_sub(1),
+        StencilOp::DecrementWrap => value.wrapping_sub(1),
+        StencilOp::Invert => !*value,
+    };
+    *value = (*value & !side.mask_write) | (updated & side.mask_write);
+    pass
+}
+
+
+#[cfg(feature = "piston-backend")]
+impl ImageSize for Texture {
+    fn get_size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+}
+
+
+#[cfg(feature = "piston-backend")]
+impl Default for NoGlyphs {
+    fn default() -> NoGlyphs {
+        NoGlyphs::new()
+    }
+}
+
+
+#[cfg(feature = "piston-backend")]
+impl NoGlyphs {
+    /// Construct the empty `CharacterCache`.
+    pub fn new() -> NoGlyphs {
+        NoGlyphs { texture: Texture { width: 0, height: 0 } }
+    }
+}
+
+
+#[cfg(feature = "piston-backend")]
+impl CharacterCache for NoGlyphs {
+    type Texture = Texture;
+
+    fn character<'a>(&'a mut self, _font_size: FontSize, _ch: char) -> Character<'a, Texture> {
+        Character { offset: [0.0, 0.0], size: [0.0, 0.0], texture: &self.texture }
+    }
+}
+
+
+impl Tolerance {
+
+    /// No differences are tolerated at all.
+    pub fn exact() -> Tolerance {
+        Tolerance { channel: 0, pixels: 0.0 }
+    }
+
+}
+
+
+/// A small tolerance suitable for absorbing rounding differences between platforms.
+impl Default for Tolerance {
+    fn default() -> Tolerance {
+        Tolerance { channel: 2, pixels: 0.001 }
+    }
+}
+
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Mismatch::Size { expected, found } =>
+                write!(f, "expected an image of size {:?} but found {:?}", expected, found),
+            Mismatch::Pixels { differing, total } =>
+                write!(f, "{} of {} pixels differed beyond the tolerance", differing, total),
+        }
+    }
+}
+
+
+/// Render the given `Element` into a new `Image` of the given size.
+///
+/// `Text` is not drawn as the software renderer has no character cache. Returns the `Error` of
+/// the first `Form` or `Element` that the piston backend can't draw (i.e. images), so that a
+/// golden image is never compared against a partially drawn frame.
+#[cfg(feature = "piston-backend")]
+pub fn render(element: &Element, width: u32, height: u32) -> Result<Image, Error> {
+    use element::Renderer;
+    use graphics::Context;
+    let mut image = Image::new(width, height);
+    {
+        let context = Context::new_abs(width as f64, height as f64);
+        let mut glyphs = NoGlyphs::new();
+        let stencil = vec![0; width as usize * height as usize];
+        let mut target = StencilTarget { image: &mut image, stencil: stencil };
+        let mut renderer = Renderer::new(context, &mut target).character_cache(&mut glyphs);
+        element.draw(&mut renderer)?;
+    }
+    Ok(image)
+}
+
+
+/// Render the given `Element` into a new `Image` of the given size using the tiny-skia backend.
+///
+/// `Text` is not drawn as the tiny-skia backend cannot yet load glyphs. Images that can't be
+/// loaded are skipped rather than failing the render.
+#[cfg(feature = "tiny-skia")]
+pub fn render_skia(element: &Element, width: u32, height: u32) -> Image {
+    let mut image = Image::new(width, height);
+    if let Some(pixmap) = ::backend::skia::render(element, width, height) {
+        for (pixel, color) in image.pixels.chunks_mut(4).zip(pixmap.pixels().iter()) {
+            let color = color.demultiply();
+            pixel.copy_from_slice(&[color.red(), color.green(), color.blue(), color.alpha()]);
+        }
+    }
+    image
+}
+
+
+/// Compare two images, returning a `Mismatch` if they differ by more than the given tolerance.
+pub fn compare(expected: &Image, found: &Image, tolerance: Tolerance) -> Result<(), Mismatch> {
+    if (expected.width, expected.height) != (found.width, found.height) {
+        return Err(Mismatch::Size {
+            expected: (expected.width, expected.height),
+            found: (found.width, found.height),
+        });
+    }
+    let differing = expected.pixels.chunks(4).zip(found.pixels.chunks(4))
+        .filter(|&(a, b)| {
+            a.iter().zip(b.iter()).any(|(&a, &b)| (a as i16 - b as i16).abs() > tolerance.channel as i16)
+        })
+        .count();
+    let total = expected.width as usize * expected.height as usize;
+    if differing as f32 > tolerance.pixels * total as f32 {
+        Err(Mismatch::Pixels { differing: differing, total: total })
+    } else {
+        Ok(())
+    }
+}
+
+
+/// Assert that the given image matches the golden image at the given path.
+///
+/// If the golden file does not exist or the `ELMESQUE_UPDATE_GOLDEN` environment variable is set,
+/// the image is written to the path instead. On a mismatch, the rendered image is written
+/// alongside the golden file with an `.actual.pam` extension to aid debugging before panicking.
+pub fn assert_golden<P: AsRef<Path>>(path: P, image: &Image, tolerance: Tolerance) {
+    let path = path.as_ref();
+    if !path.exists() || env::var_os(UPDATE_GOLDEN_VAR).is_some() {
+        write_image(path, image);
+        return;
+    }
+    let expected = File::open(path).and_then(Image::read_pam)
+        .unwrap_or_else(|e| panic!("failed to read golden image {:?}: {}", path, e));
+    if let Err(mismatch) = compare(&expected, image, tolerance) {
+        let actual_path: PathBuf = path.with_extension("actual.pam");
+        write_image(&actual_path, image);
+        panic!("rendered image does not match golden image {:?}: {} (written to {:?})",
+               path, mismatch, actual_path);
+    }
+}
+
+
+/// Write the image to the given path, creating any missing parent directories.
+fn write_image(path: &Path, image: &Image) {
+    if let Some(parent) = path.parent() {
+        ::std::fs::create_dir_all(parent)
+            .unwrap_or_else(|e| panic!("failed to create directory {:?}: {}", parent, e));
+    }
+    File::create(path).and_then(|file| image.write_pam(file))
+        .unwrap_or_else(|e| panic!("failed to write image {:?}: {}", path, e));
+}
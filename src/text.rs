@@ -1,24 +1,38 @@
 
+#[cfg(feature = "syntect")]
+use color::rgba_bytes;
 use color::{black, Color};
+#[cfg(feature = "rusttype")]
+use font::Font;
+#[cfg(feature = "rusttype")]
+use form::Shape;
+use std::hash::{Hash, Hasher};
 use std::path::PathBuf;
+#[cfg(feature = "unicode-bidi")]
+use unicode_bidi::BidiInfo;
+use utils::hash_f64;
 
 
 /// Drawable Text.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Text {
     pub sequence: Vec<TextUnit>,
     pub position: Position,
+    pub anchor: TextAnchor,
 }
 
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TextUnit {
     pub string: String,
     pub style: Style,
 }
 
 /// Styles for lines on text. This allows you to add an underline, an overline, or strike out text.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Line {
     Under,
     Over,
@@ -26,11 +40,63 @@ pub enum Line {
 }
 
 /// Text position relative to center point
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Position {
     Center,
+    /// The text extends to the left of the point, ending upon it.
     ToLeft,
-    ToRight
+    /// The text extends to the right of the point, starting upon it.
+    ToRight,
+    /// The given fraction of the text's width extends to the left of the point, where `0.0` is
+    /// equivalent to `ToRight`, `0.5` to `Center` and `1.0` to `ToLeft`.
+    Custom(f64),
+}
+
+impl Position {
+
+    /// The fraction of the text's width that extends to the left of the point.
+    pub fn fraction(&self) -> f64 {
+        match *self {
+            Position::Center => 0.5,
+            Position::ToLeft => 1.0,
+            Position::ToRight => 0.0,
+            Position::Custom(fraction) => fraction,
+        }
+    }
+
+}
+
+/// Positions are compared by value and custom fractions are hashed by their bit patterns, so `Eq`
+/// only holds for positions without a `NaN` fraction.
+impl Eq for Position {}
+
+impl Hash for Position {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match *self {
+            Position::Center => 0u8.hash(state),
+            Position::ToLeft => 1u8.hash(state),
+            Position::ToRight => 2u8.hash(state),
+            Position::Custom(fraction) => {
+                3u8.hash(state);
+                hash_f64(fraction, state);
+            },
+        }
+    }
+}
+
+/// The vertical placement of text relative to the origin of its form.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TextAnchor {
+    /// The baseline lies upon the origin, so the text sits above it as if written on a line.
+    Baseline,
+    /// The text is vertically centered upon the origin. This is the default.
+    Middle,
+    /// The top of the text (its ascent) lies upon the origin, so the text hangs below it.
+    Top,
+    /// The bottom of the text (its descent) lies upon the origin.
+    Bottom,
 }
 
 
@@ -41,36 +107,97 @@ pub enum Position {
 ///
 ///   Style {
 ///       type_face: Some("Times New Roman"),
+///       fallback_typefaces: vec![],
 ///       height: Some(16),
 ///       color: black(),
 ///       bold: false,
 ///       italic: false,
 ///       line: Some(Line::Under),
+///       baseline: 0.0,
+///       background: None,
 ///   }
 ///
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Style {
     pub typeface: Option<PathBuf>,
+    /// Typefaces to try in order for characters missing from the `typeface`.
+    pub fallback_typefaces: Vec<PathBuf>,
     pub height: Option<f64>,
     pub color: Color,
     pub bold: bool,
     pub italic: bool,
     pub line: Option<Line>,
     pub monospace: bool,
+    /// The distance in pixels by which the text is raised above the baseline, or lowered if
+    /// negative. See `Text::superscript` and `Text::subscript`.
+    pub baseline: f64,
+    /// A color drawn behind the text, like a highlighter. See `Text::background`.
+    pub background: Option<Color>,
+}
+
+/// Styles are compared by value and their heights are hashed by their bit patterns, so `Eq` only
+/// holds for styles without a `NaN` height.
+impl Eq for Style {}
+
+impl Hash for Style {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let Style {
+            ref typeface, ref fallback_typefaces, height, color, bold, italic, line, monospace,
+            baseline, background,
+        } = *self;
+        typeface.hash(state);
+        fallback_typefaces.hash(state);
+        height.is_some().hash(state);
+        if let Some(height) = height {
+            hash_f64(height, state);
+        }
+        color.hash(state);
+        bold.hash(state);
+        italic.hash(state);
+        line.hash(state);
+        monospace.hash(state);
+        hash_f64(baseline, state);
+        background.hash(state);
+    }
 }
 
 impl Style {
     pub fn default() -> Style {
         Style {
             typeface: None,
+            fallback_typefaces: Vec::new(),
             height: None,
             color: black(),
             bold: false,
             italic: false,
             line: None,
             monospace: false,
+            baseline: 0.0,
+            background: None,
         }
     }
+
+    /// Replace each field of this style with that of `overrides` wherever `overrides` differs from
+    /// `default`.
+    fn apply_overrides(&mut self, overrides: Style, default: &Style) {
+        let Style {
+            typeface, fallback_typefaces, height, color, bold, italic, line, monospace, baseline,
+            background,
+        } = overrides;
+        if typeface != default.typeface { self.typeface = typeface; }
+        if fallback_typefaces != default.fallback_typefaces {
+            self.fallback_typefaces = fallback_typefaces;
+        }
+        if height != default.height { self.height = height; }
+        if color != default.color { self.color = color; }
+        if bold != default.bold { self.bold = bold; }
+        if italic != default.italic { self.italic = italic; }
+        if line != default.line { self.line = line; }
+        if monospace != default.monospace { self.monospace = monospace; }
+        if baseline != default.baseline { self.baseline = baseline; }
+        if background != default.background { self.background = background; }
+    }
 }
 
 
@@ -80,7 +207,8 @@ impl Text {
     pub fn from_string(string: String) -> Text {
         Text {
             sequence: vec![TextUnit { string: string, style: Style::default(), }],
-            position: Position::Center
+            position: Position::Center,
+            anchor: TextAnchor::Middle,
         }
     }
 
@@ -92,26 +220,56 @@ impl Text {
     /// Put two chunks of text together.
     #[inline]
     pub fn append(mut self, other: Text) -> Text {
-        self.sequence.extend(other.sequence.into_iter());
+        self.sequence.extend(other.sequence);
         self
     }
 
     /// Put many chunks of text together.
     pub fn concat(texts: Vec<Text>) -> Text {
-        let position = texts.get(0).map(|t| t.position).unwrap_or(Position::Center);
+        let position = texts.first().map(|t| t.position).unwrap_or(Position::Center);
+        let anchor = texts.first().map(|t| t.anchor).unwrap_or(TextAnchor::Middle);
         Text {
             sequence: texts.into_iter()
-                .flat_map(|Text { sequence, position }| sequence.into_iter())
+                .flat_map(|Text { sequence, .. }| sequence.into_iter())
                 .collect(),
-            position: position
+            position: position,
+            anchor: anchor,
         }
     }
 
-    /// Put many chunks of text together with a separator.
+    /// Put many chunks of text together with a separator between each chunk.
+    ///
+    ///   join(from_string(", "), vec![from_string("a"), from_string("b")]) // "a, b"
+    ///
     pub fn join(separator: Text, texts: Vec<Text>) -> Text {
-        texts.into_iter().fold(Text::empty(), |texts, text| {
-            texts.append(text).append(separator.clone())
-        })
+        Text::intersperse(separator.clone(), separator, texts)
+    }
+
+    /// Put many chunks of text together with a separator between each chunk, using
+    /// `last_separator` between the final two chunks.
+    ///
+    ///   intersperse(from_string(", "), from_string(" and "), vec![a, b, c]) // "a, b and c"
+    ///
+    pub fn intersperse(separator: Text, last_separator: Text, texts: Vec<Text>) -> Text {
+        if texts.is_empty() {
+            return Text::empty();
+        }
+        let last = texts.len() - 1;
+        let mut chunks = Vec::with_capacity(texts.len() * 2);
+        for (i, text) in texts.into_iter().enumerate() {
+            if i == last && i > 0 {
+                chunks.push(last_separator.clone());
+            } else if i > 0 {
+                chunks.push(separator.clone());
+            }
+            chunks.push(text);
+        }
+        Text::concat(chunks)
+    }
+
+    /// Put many lines of text together, separated by newlines.
+    pub fn lines(lines: Vec<Text>) -> Text {
+        Text::join(Text::from_string("\n".to_string()), lines)
     }
 
     /// Set the style of some text. For example, if you design a `Style` called `foorter_style` that is
@@ -119,15 +277,28 @@ impl Text {
     ///
     ///   style(footer_style, from_string("the old prince / 2007"))
     ///
+    /// The style acts as a base for each unit: any field that a unit has explicitly set (i.e. that
+    /// differs from `Style::default()`) is kept, so bold words or colored spans survive.
     #[inline]
-    pub fn style(self, style: Style) -> Text {
-        let string = String::from_utf8(self.sequence.into_iter().flat_map(|unit| {
-            unit.string.into_bytes().into_iter()
-        }).collect()).unwrap();
-        Text {
-            sequence: vec![TextUnit { string: string, style: style }],
-            ..self
+    pub fn style(mut self, style: Style) -> Text {
+        let default = Style::default();
+        for unit in self.sequence.iter_mut() {
+            let overrides = ::std::mem::replace(&mut unit.style, style.clone());
+            unit.style.apply_overrides(overrides, &default);
         }
+        self
+    }
+
+    /// Modify the style of every unit with the given function.
+    ///
+    ///   text.restyle_with(|style| style.height = style.height.map(|h| h * 2.0))
+    ///
+    #[inline]
+    pub fn restyle_with<F: FnMut(&mut Style)>(mut self, mut f: F) -> Text {
+        for unit in self.sequence.iter_mut() {
+            f(&mut unit.style);
+        }
+        self
     }
 
     /// Provide a path of a typeface to be used for some text.
@@ -139,6 +310,16 @@ impl Text {
         self
     }
 
+    /// Provide the paths of typefaces to be tried in order for characters that are missing from
+    /// the text's typeface, i.e. to draw CJK or symbols within Latin text.
+    #[inline]
+    pub fn fallback_typefaces(mut self, paths: Vec<PathBuf>) -> Text {
+        for unit in self.sequence.iter_mut() {
+            unit.style.fallback_typefaces = paths.clone();
+        }
+        self
+    }
+
     /// Switch to a monospace typeface. Good for code snippets.
     ///
     ///   monospace(from_string("(0..3).fold(0, |a, b| a + b)"))
@@ -196,11 +377,296 @@ impl Text {
         self
     }
 
+    /// Highlight the text with a rectangle of the given color behind each unit, padded by
+    /// `BACKGROUND_PADDING` of the unit's height on every side.
+    ///
+    ///   from_string("important").background(yellow())
+    ///
+    #[inline]
+    pub fn background(mut self, color: Color) -> Text {
+        for unit in self.sequence.iter_mut() {
+            unit.style.background = Some(color);
+        }
+        self
+    }
+
+    /// Shrink the text and raise it above the baseline, i.e. for exponents.
+    ///
+    ///   from_string("x").append(from_string("2").superscript())
+    ///
+    #[inline]
+    pub fn superscript(self) -> Text {
+        self.script(SUPERSCRIPT_RISE)
+    }
+
+    /// Shrink the text and lower it below the baseline, i.e. for chemical formulas.
+    ///
+    ///   from_string("H").append(from_string("2").subscript()).append(from_string("O"))
+    ///
+    #[inline]
+    pub fn subscript(self) -> Text {
+        self.script(-SUBSCRIPT_DROP)
+    }
+
+    /// Scale each unit by `SCRIPT_SCALE` and shift its baseline by `rise` times its height.
+    fn script(mut self, rise: f64) -> Text {
+        for unit in self.sequence.iter_mut() {
+            let height = unit.style.height.unwrap_or(DEFAULT_HEIGHT);
+            unit.style.height = Some(height * SCRIPT_SCALE);
+            unit.style.baseline += height * rise;
+        }
+        self
+    }
+
+    /// Replace each tab with enough spaces to reach the next tab stop, where tab stops occur
+    /// every `tab_width` columns. Columns are counted across units, so the result lines up when
+    /// drawn with a monospace typeface.
+    pub fn expand_tabs(mut self, tab_width: usize) -> Text {
+        let mut column = 0;
+        for unit in self.sequence.iter_mut() {
+            unit.string = expand_tabs_from(&unit.string, tab_width, &mut column);
+        }
+        self
+    }
+
     /// Change the text position relative to it's center point
     #[inline]
     pub fn position(mut self, position: Position) -> Text {
         self.position = position;
         self
     }
+
+    /// Change the vertical placement of the text relative to the origin of its form. For example,
+    /// to label a point with text sitting upon it:
+    ///
+    ///   text(from_string("peak").anchor(TextAnchor::Baseline)).shift(x, y)
+    ///
+    #[inline]
+    pub fn anchor(mut self, anchor: TextAnchor) -> Text {
+        self.anchor = anchor;
+        self
+    }
+
+    /// Reorder the text from logical order (the order in which it is typed) into visual order
+    /// (the order in which it is displayed from left to right) using the Unicode bidirectional
+    /// algorithm, so that right-to-left scripts such as Hebrew and Arabic read correctly within
+    /// mixed-direction text.
+    ///
+    /// Units are split wherever they span runs of differing direction and the characters of each
+    /// right-to-left run are reversed. Text without any right-to-left characters is unchanged.
+    #[cfg(feature = "unicode-bidi")]
+    pub fn visual_order(&self) -> Text {
+        let string: String = self.sequence.iter().map(|unit| &unit.string[..]).collect();
+        let info = BidiInfo::new(&string, None);
+        if !info.has_rtl() {
+            return self.clone();
+        }
+
+        // The byte offset at which each unit begins within the whole string.
+        let starts: Vec<usize> = self.sequence.iter()
+            .scan(0, |offset, unit| {
+                let start = *offset;
+                *offset += unit.string.len();
+                Some(start)
+            })
+            .collect();
+
+        let mut sequence = Vec::new();
+        for para in info.paragraphs.iter() {
+            let (levels, runs) = info.visual_runs(para, para.range.clone());
+            for run in runs {
+                // Split the run wherever a new unit begins.
+                let mut units = Vec::new();
+                let mut start = run.start;
+                while start < run.end {
+                    let i = starts.iter().rposition(|&s| s <= start).unwrap_or(0);
+                    let end = starts.get(i + 1).map_or(run.end, |&s| ::std::cmp::min(s, run.end));
+                    let style = self.sequence[i].style.clone();
+                    units.push(TextUnit { string: string[start..end].to_string(), style: style });
+                    start = end;
+                }
+                if levels[run.start].is_rtl() {
+                    units.reverse();
+                    for unit in units.iter_mut() {
+                        unit.string = unit.string.chars().rev().collect();
+                    }
+                }
+                sequence.extend(units);
+            }
+        }
+        Text { sequence: sequence, position: self.position, anchor: self.anchor }
+    }
+
+    /// The width and height of the text when laid out with the given font.
+    ///
+    /// This requires no graphics backend, so may be used to size `Element`s up front.
+    #[cfg(feature = "rusttype")]
+    pub fn size(&self, font: &Font) -> (f64, f64) {
+        font.layout(self).size()
+    }
 }
 
+
+/// The height of text whose `Style` has no height.
+pub const DEFAULT_HEIGHT: f64 = 16.0;
+
+/// A typical number of columns between tab stops, for use with `expand_tabs`.
+pub const DEFAULT_TAB_WIDTH: usize = 4;
+
+/// The scale applied to the height of superscript and subscript text.
+pub const SCRIPT_SCALE: f64 = 0.6;
+
+/// The distance superscript text is raised, as a fraction of the original text height.
+pub const SUPERSCRIPT_RISE: f64 = 0.4;
+
+/// The distance subscript text is lowered, as a fraction of the original text height.
+pub const SUBSCRIPT_DROP: f64 = 0.2;
+
+/// The padding around the rectangle behind highlighted text, as a fraction of the text height.
+pub const BACKGROUND_PADDING: f64 = 0.1;
+
+/// The theme used by `highlight` when the requested theme isn't one of syntect's defaults.
+#[cfg(feature = "syntect")]
+pub const DEFAULT_HIGHLIGHT_THEME: &str = "InspiredGitHub";
+
+
+/// Syntax highlight some source code, producing monospace text with a unit per highlighted span.
+///
+/// `language` is a name or file extension known to syntect's default syntaxes (i.e. "rs" or
+/// "Rust"), falling back to plain text, and `theme` is the name of one of syntect's default
+/// themes (i.e. "base16-ocean.dark"), falling back to `DEFAULT_HIGHLIGHT_THEME`. Lines are kept
+/// separated by their newlines.
+///
+/// The default syntaxes and themes are loaded upon each call, so highlight snippets once rather
+/// than every frame.
+///
+///   text(highlight("fn main() {}", "rs", "base16-ocean.dark").height(14.0))
+///
+#[cfg(feature = "syntect")]
+pub fn highlight(source: &str, language: &str, theme: &str) -> Text {
+    use syntect::easy::HighlightLines;
+    use syntect::highlighting::{self, FontStyle, ThemeSet};
+    use syntect::parsing::SyntaxSet;
+    use syntect::util::LinesWithEndings;
+
+    let syntaxes = SyntaxSet::load_defaults_newlines();
+    let themes = ThemeSet::load_defaults();
+    let syntax = syntaxes.find_syntax_by_token(language)
+        .unwrap_or_else(|| syntaxes.find_syntax_plain_text());
+    let theme = themes.themes.get(theme)
+        .unwrap_or_else(|| &themes.themes[DEFAULT_HIGHLIGHT_THEME]);
+    let mut highlighter = HighlightLines::new(syntax, theme);
+    let mut sequence = Vec::new();
+    for line in LinesWithEndings::from(source) {
+        // Lines that syntect fails to parse are left plain rather than dropped.
+        let spans = highlighter.highlight_line(line, &syntaxes)
+            .unwrap_or_else(|_| vec![(highlighting::Style::default(), line)]);
+        for (span_style, string) in spans {
+            let highlighting::Color { r, g, b, a } = span_style.foreground;
+            let mut style = Style::default();
+            style.color = rgba_bytes(r, g, b, a as f32 / 255.0);
+            style.bold = span_style.font_style.contains(FontStyle::BOLD);
+            style.italic = span_style.font_style.contains(FontStyle::ITALIC);
+            if span_style.font_style.contains(FontStyle::UNDERLINE) {
+                style.line = Some(Line::Under);
+            }
+            style.monospace = true;
+            sequence.push(TextUnit { string: string.to_string(), style: style });
+        }
+    }
+    Text { sequence: sequence, position: Position::Center, anchor: TextAnchor::Middle }
+}
+
+
+/// Replace each tab in the string with enough spaces to reach the next tab stop, where tab stops
+/// occur every `tab_width` columns. Each character is assumed to occupy a single column, as in a
+/// monospace typeface.
+///
+///   assert_eq!(expand_tabs("a\tbc\td", 4), "a   bc  d");
+///
+pub fn expand_tabs(s: &str, tab_width: usize) -> String {
+    expand_tabs_from(s, tab_width, &mut 0)
+}
+
+/// Expand the tabs of a string beginning at the given column, leaving `column` at the end of it.
+fn expand_tabs_from(s: &str, tab_width: usize, column: &mut usize) -> String {
+    let tab_width = ::std::cmp::max(1, tab_width);
+    let mut expanded = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\t' => {
+                let spaces = tab_width - *column % tab_width;
+                expanded.extend(::std::iter::repeat_n(' ', spaces));
+                *column += spaces;
+            },
+            '\n' => {
+                expanded.push(c);
+                *column = 0;
+            },
+            _ => {
+                expanded.push(c);
+                *column += 1;
+            },
+        }
+    }
+    expanded
+}
+
+/// Align rows of cells into columns, padding each cell with spaces to the width of the widest
+/// cell in its column followed by `gap` spaces. Trailing padding is omitted.
+///
+/// The resulting lines line up when drawn with a monospace typeface:
+///
+///   align_columns(&[vec!["name", "size"], vec!["elmesque", "42"]], 2)
+///       == vec!["name      size", "elmesque  42"]
+///
+pub fn align_columns<S: AsRef<str>>(rows: &[Vec<S>], gap: usize) -> Vec<String> {
+    let mut widths: Vec<usize> = Vec::new();
+    for row in rows.iter() {
+        for (i, cell) in row.iter().enumerate() {
+            let width = cell.as_ref().chars().count();
+            if i < widths.len() {
+                widths[i] = ::std::cmp::max(widths[i], width);
+            } else {
+                widths.push(width);
+            }
+        }
+    }
+    rows.iter().map(|row| {
+        let mut line = String::new();
+        for (i, cell) in row.iter().enumerate() {
+            let cell = cell.as_ref();
+            line.push_str(cell);
+            if i + 1 < row.len() {
+                let padding = widths[i] - cell.chars().count() + gap;
+                line.extend(::std::iter::repeat_n(' ', padding));
+            }
+        }
+        line
+    }).collect()
+}
+
+/// Split each line at its tabs and align the resulting cells into columns with `align_columns`,
+/// in the manner of "elastic" tab stops.
+pub fn align_tabbed_lines(s: &str, gap: usize) -> Vec<String> {
+    let rows: Vec<Vec<&str>> = s.lines().map(|line| line.split('\t').collect()).collect();
+    align_columns(&rows, gap)
+}
+
+
+/// Convert some text into vector shapes (one per glyph) using the given font.
+///
+/// The glyphs are laid out upon a single line that is placed about the origin according to the
+/// text's `Position` and `TextAnchor`, matching where `form::text` would draw the text. The
+/// resulting shapes may be filled, outlined, warped or used as clipping masks. Each unit's
+/// `typeface` is ignored in favour of the given font.
+#[cfg(feature = "rusttype")]
+pub fn glyph_outlines(text: &Text, font: &Font) -> Vec<Shape> {
+    let layout = font.layout(text);
+    let (x, y) = layout.origin(text.position, text.anchor);
+    layout.glyphs.iter()
+        .filter_map(|glyph| {
+            font.glyph_shape(glyph.character, glyph.height, (x + glyph.x, y + glyph.baseline))
+        })
+        .collect()
+}
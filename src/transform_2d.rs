@@ -13,14 +13,28 @@
 //!
 
 
+use std::hash::{Hash, Hasher};
+use utils::hash_f64;
 use vecmath::{mat2x3_id, Matrix2x3, row_mat2x3_mul};
 
 pub type Matrix2d = Matrix2x3<f64>;
 
 /// Represents a 2D transform.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Transform2D(pub Matrix2d);
 
+/// Transforms are hashed by the bit patterns of their components.
+impl Hash for Transform2D {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for row in self.0.iter() {
+            for &f in row.iter() {
+                hash_f64(f, state);
+            }
+        }
+    }
+}
+
 impl Transform2D {
 
     /// Multiply two transforms together.
@@ -35,6 +49,18 @@ impl Transform2D {
         Transform2D(row_mat2x3_mul(m, n))
     }
 
+    /// The inverse of the transform, or `None` if the transform can't be inverted (i.e. it scales
+    /// by zero).
+    pub fn invert(&self) -> Option<Transform2D> {
+        let Transform2D([[a, b, x], [c, d, y]]) = *self;
+        let det = a * d - b * c;
+        if det == 0.0 {
+            return None;
+        }
+        let (ia, ib, ic, id) = (d / det, -b / det, -c / det, a / det);
+        Some(matrix(ia, ib, ic, id, -(ia * x + ib * y), -(ic * x + id * y)))
+    }
+
 }
 
 /// Create an identity transform. Transforming by the identity does not change anything, but it can
@@ -1,12 +1,15 @@
 
-use num::{Float, NumCast};
-use num::PrimInt as Int;
-use num::traits::cast;
+#[cfg(feature = "piston-backend")]
+use graphics::Context;
+use num_traits::{cast, Float, NumCast};
+use num_traits::PrimInt as Int;
+use rand::{SeedableRng, XorShiftRng};
 use std::f32::consts::PI;
+use std::hash::{Hash, Hasher};
 
 /// Clamp a f32 between 0f32 and 1f32.
 pub fn clampf32(f: f32) -> f32 {
-    if f < 0f32 { 0f32 } else if f > 1f32 { 1f32 } else { f }
+    f.clamp(0.0, 1.0)
 }
 
 /// Convert degrees to radians.
@@ -30,10 +33,24 @@ pub fn modulo<I: Int>(a: I, b: I) -> I {
     }
 }
 
-/// Modulo float.
-pub fn fmod(f: f32, n: i32) -> f32 {
-    let i = f.floor() as i32;
-    modulo(i, n) as f32 + f - i as f32
+/// The Euclidean modulo of two floats, i.e. the remainder of `f / n` within `[0, |n|)`, even for
+/// negative and fractional `f` and `n`.
+pub fn fmod<F: Float>(f: F, n: F) -> F {
+    let n = n.abs();
+    let r = f % n;
+    let r = if r < F::zero() { r + n } else { r };
+    // Adding `n` to a tiny negative remainder may round up to `n` itself.
+    if r >= n { F::zero() } else { r }
+}
+
+/// Wrap an angle in radians to within a single turn, i.e. `[0, 2π)`.
+pub fn wrap_angle<F: Float + NumCast>(radians: F) -> F {
+    fmod(radians, turns(F::one()))
+}
+
+/// Wrap an angle in degrees to within a single turn, i.e. `[0, 360)`.
+pub fn wrap_degrees<F: Float + NumCast>(degrees: F) -> F {
+    fmod(degrees, cast(360.0).unwrap())
 }
 
 /// Return the min between to floats.
@@ -53,6 +70,10 @@ pub fn clamp<T: PartialOrd>(val: T, min: T, max: T) -> T {
 }
 
 /// Map a value from a given range to a new given range.
+///
+/// The values are converted through `f64`, panicking if the result can't be represented as a `Y`
+/// (e.g. a `NaN` or an integer overflow). Prefer `map_range_float`, `map_range_int` or
+/// `map_range_clamped`, which never panic.
 pub fn map_range<X: NumCast, Y: NumCast>
 (val: X, in_min: X, in_max: X, out_min: Y, out_max: Y) -> Y {
     let val_f: f64 = NumCast::from(val).unwrap();
@@ -65,4 +86,173 @@ pub fn map_range<X: NumCast, Y: NumCast>
     ).unwrap()
 }
 
+/// Map a float from a given range to a new given range, without any conversions.
+#[inline]
+pub fn map_range_float<F: Float>(val: F, in_min: F, in_max: F, out_min: F, out_max: F) -> F {
+    (val - in_min) / (in_max - in_min) * (out_max - out_min) + out_min
+}
+
+/// Map an integer from a given range to a new given range, rounding toward zero.
+///
+/// The arithmetic is carried out in 128 bits so that it can't overflow, and the result saturates
+/// at the bounds of `i32`. An empty input range maps everything to `out_min`.
+pub fn map_range_int(val: i32, in_min: i32, in_max: i32, out_min: i32, out_max: i32) -> i32 {
+    if in_min == in_max {
+        return out_min;
+    }
+    let (val, in_min, in_max) = (val as i128, in_min as i128, in_max as i128);
+    let (out_min, out_max) = (out_min as i128, out_max as i128);
+    let mapped = (val - in_min) * (out_max - out_min) / (in_max - in_min) + out_min;
+    clamp(mapped, i32::MIN as i128, i32::MAX as i128) as i32
+}
+
+/// Map a float from a given range to a new given range, clamping the result to the new range.
+///
+/// The new range may run in either direction. A `NaN` result (e.g. from an empty input range)
+/// is clamped to the lesser of `out_min` and `out_max`.
+pub fn map_range_clamped<F: Float>(val: F, in_min: F, in_max: F, out_min: F, out_max: F) -> F {
+    let (lo, hi) = if out_min <= out_max { (out_min, out_max) } else { (out_max, out_min) };
+    map_range_float(val, in_min, in_max, out_min, out_max).max(lo).min(hi)
+}
+
+/// The scissor rectangle `[x, y, w, h]` in pixels of the render target that covers the given
+/// rectangle of the context's view, described by its center `x` and `y` along with its width and
+/// height in the same centered-origin coordinates as `Element::crop`.
+///
+/// The rectangle is clipped to the render target and to any scissor the context already has, so
+/// that the scissor may be given straight to `DrawState::scissor`, e.g. around a custom draw.
+/// Returns `None` if nothing of the rectangle remains visible.
+#[cfg(feature = "piston-backend")]
+pub fn view_rect_to_scissor(context: &Context, (x, y, w, h): (f64, f64, f64, f64))
+    -> Option<[u16; 4]>
+{
+    // Because `DrawState`'s `scissor` `Rect` uses bottom-left origin coords, we'll have to convert
+    // from our centered-origin coordinate system.
+    //
+    // We'll also need to stretch our coords to match the correct viewport.draw_size.
+
+    // Our view_dim is our virtual window size which is consistent no matter the display.
+    let view_dim = context.get_view_size();
+
+    // A view without area can't be mapped onto the target, and shows nothing anyway.
+    if !(view_dim[0] > 0.0 && view_dim[1] > 0.0) {
+        return None;
+    }
+
+    // Our draw_dim is the actual window size in pixels. Our target crop area must be
+    // represented in this size.
+    let draw_dim = match context.viewport {
+        Some(viewport) => [viewport.draw_size[0] as f64, viewport.draw_size[1] as f64],
+        None => view_dim,
+    };
+
+    // Calculate the distance to the edges of the window from the center.
+    let left = -view_dim[0] / 2.0;
+    let right = view_dim[0] / 2.0;
+    let bottom = -view_dim[1] / 2.0;
+    let top = view_dim[1] / 2.0;
+
+    // Map the edges of the crop area from view_dim to our draw_dim. The edges are clamped to
+    // the target, as the scissor can't represent negative coords (and anything beyond the target
+    // wouldn't be drawn anyway), so a crop area hanging off of the target loses the part that
+    // hangs off rather than being shifted back onto it.
+    let x0 = map_range_clamped(x - w / 2.0, left, right, 0.0, draw_dim[0]);
+    let x1 = map_range_clamped(x + w / 2.0, left, right, 0.0, draw_dim[0]);
+    let y0 = map_range_clamped(y - h / 2.0, bottom, top, 0.0, draw_dim[1]);
+    let y1 = map_range_clamped(y + h / 2.0, bottom, top, 0.0, draw_dim[1]);
+
+    // The edges are summed as `u32`s so that crops reaching beyond `u16::MAX` can't overflow.
+    let (mut l, mut r) = (x0 as u32, x0 as u32 + (x1 - x0) as u32);
+    let (mut b, mut t) = (y0 as u32, y0 as u32 + (y1 - y0) as u32);
+
+    // If there was already some scissor set, we must take the intersection.
+    if let Some(rect) = context.draw_state.scissor {
+        let (rect_x, rect_y, rect_w, rect_h) =
+            (rect.x as u32, rect.y as u32, rect.w as u32, rect.h as u32);
+        l = ::std::cmp::max(l, rect_x);
+        r = ::std::cmp::min(r, rect_x + rect_w);
+        b = ::std::cmp::max(b, rect_y);
+        t = ::std::cmp::min(t, rect_y + rect_h);
+    }
 
+    if r <= l || t <= b {
+        return None;
+    }
+    let to_u16 = |n: u32| ::std::cmp::min(n, u16::MAX as u32) as u16;
+    Some([to_u16(l), to_u16(b), to_u16(r - l), to_u16(t - b)])
+}
+
+/// A 64-bit FNV-1a `Hasher`.
+///
+/// Unlike the standard library's default hasher, its output is specified and never varies
+/// between runs or compiler versions, so it is used wherever hashes may be stored or compared
+/// between processes.
+#[derive(Copy, Clone, Debug)]
+pub struct FnvHasher(u64);
+
+impl Default for FnvHasher {
+    fn default() -> FnvHasher {
+        FnvHasher(0xcbf29ce484222325)
+    }
+}
+
+impl Hasher for FnvHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes.iter() {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(0x100000001b3);
+        }
+    }
+}
+
+/// A random number generator that produces the same sequence from the same seed on every run and
+/// platform, for reproducible generative output. Pass it to the helpers that take some `Rng`,
+/// i.e. `color::random_with`.
+pub fn seeded_rng(seed: u64) -> XorShiftRng {
+    // Spread the seed across the generator's state with SplitMix64, whose consecutive outputs
+    // are never both zero (an all-zero state would make the generator panic).
+    let mut state = seed;
+    let mut next = || {
+        state = state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    };
+    let (a, b) = (next(), next());
+    XorShiftRng::from_seed([a as u32, (a >> 32) as u32, b as u32, (b >> 32) as u32])
+}
+
+/// Feed a float to the given hasher by its bit pattern.
+///
+/// `0.0` and `-0.0` are hashed alike so that the hash agrees with `==`.
+pub fn hash_f32<H: Hasher>(f: f32, state: &mut H) {
+    let f = if f == 0.0 { 0.0 } else { f };
+    f.to_bits().hash(state);
+}
+
+/// Feed a float to the given hasher by its bit pattern. See `hash_f32`.
+pub fn hash_f64<H: Hasher>(f: f64, state: &mut H) {
+    let f = if f == 0.0 { 0.0 } else { f };
+    f.to_bits().hash(state);
+}
+
+/// The point at `t` along the quadratic bezier curve from `a` to `c` with the control point `b`.
+pub fn quadratic_bezier(a: (f64, f64), b: (f64, f64), c: (f64, f64), t: f64) -> (f64, f64) {
+    let u = 1.0 - t;
+    let (wa, wb, wc) = (u * u, 2.0 * u * t, t * t);
+    (wa * a.0 + wb * b.0 + wc * c.0, wa * a.1 + wb * b.1 + wc * c.1)
+}
+
+/// The point at `t` along the cubic bezier curve from `a` to `d` with the control points `b` and
+/// `c`.
+pub fn cubic_bezier(a: (f64, f64), b: (f64, f64), c: (f64, f64), d: (f64, f64), t: f64)
+    -> (f64, f64)
+{
+    let u = 1.0 - t;
+    let (wa, wb, wc, wd) = (u * u * u, 3.0 * u * u * t, 3.0 * u * t * t, t * t * t);
+    (wa * a.0 + wb * b.0 + wc * c.0 + wd * d.0, wa * a.1 + wb * b.1 + wc * c.1 + wd * d.1)
+}
@@ -0,0 +1,55 @@
+extern crate elmesque;
+
+use elmesque::form::{arc, PointPath};
+use std::f64::consts::PI;
+
+
+fn assert_point_eq(a: (f64, f64), b: (f64, f64)) {
+    assert!((a.0 - b.0).abs() < 1e-9 && (a.1 - b.1).abs() < 1e-9, "{:?} != {:?}", a, b);
+}
+
+
+#[test]
+fn arc_ends() {
+    let PointPath(points) = arc(2.0, 0.0, PI / 2.0);
+    assert!(points.len() > 2);
+    assert_point_eq(points[0], (2.0, 0.0));
+    assert_point_eq(*points.last().unwrap(), (0.0, 2.0));
+
+    // Clockwise when `end` is less than `start`.
+    let PointPath(points) = arc(1.0, 0.0, -PI / 2.0);
+    assert_point_eq(*points.last().unwrap(), (0.0, -1.0));
+    assert!(points[1].1 < 0.0);
+}
+
+
+#[test]
+fn arc_sweep_is_capped_at_one_turn() {
+    let PointPath(turn) = arc(1.0, 0.0, 2.0 * PI);
+    for &end in &[4.0 * PI, 1e300, f64::MAX] {
+        let PointPath(points) = arc(1.0, 0.0, end);
+        assert_eq!(points.len(), turn.len());
+        let PointPath(points) = arc(1.0, 0.0, -end);
+        assert_eq!(points.len(), turn.len());
+    }
+    // A sweep from one extreme to the other overflows to infinity.
+    let PointPath(points) = arc(1.0, f64::MIN, f64::MAX);
+    assert!(points.is_empty());
+}
+
+
+#[test]
+fn arc_of_non_finite_input_is_empty() {
+    let args = [
+        (f64::NAN, 0.0, PI),
+        (f64::INFINITY, 0.0, PI),
+        (1.0, f64::NAN, PI),
+        (1.0, 0.0, f64::NAN),
+        (1.0, 0.0, f64::INFINITY),
+        (1.0, f64::NEG_INFINITY, 0.0),
+    ];
+    for &(radius, start, end) in &args {
+        let PointPath(points) = arc(radius, start, end);
+        assert!(points.is_empty(), "arc({}, {}, {}) has points", radius, start, end);
+    }
+}
@@ -0,0 +1,167 @@
+extern crate elmesque;
+extern crate rand;
+
+use elmesque::color::{self, hsl_to_rgb, rgb_to_hsl};
+use elmesque::utils::{degrees, seeded_rng};
+use rand::Rng;
+use std::f32::consts::PI;
+
+
+/// The number of random colors checked by each round trip.
+const SAMPLES: usize = 10_000;
+
+/// The greatest difference allowed between a channel and its round-tripped value.
+const EPSILON: f32 = 1e-4;
+
+
+fn assert_rgb_eq((r1, g1, b1): (f32, f32, f32), (r2, g2, b2): (f32, f32, f32)) {
+    assert!((r1 - r2).abs() < EPSILON && (g1 - g2).abs() < EPSILON && (b1 - b2).abs() < EPSILON,
+            "{:?} != {:?}", (r1, g1, b1), (r2, g2, b2));
+}
+
+/// The distance between two hues in radians, the short way around.
+fn hue_distance(a: f32, b: f32) -> f32 {
+    let d = (a - b).abs() % (2.0 * PI);
+    d.min(2.0 * PI - d)
+}
+
+
+#[test]
+fn rgb_round_trips_through_hsl() {
+    let mut rng = seeded_rng(0);
+    for _ in 0..SAMPLES {
+        let rgb = (rng.gen::<f32>(), rng.gen::<f32>(), rng.gen::<f32>());
+        let (h, s, l) = rgb_to_hsl(rgb.0, rgb.1, rgb.2);
+        assert!((0.0..2.0 * PI).contains(&h), "hue {} of {:?} is outside of a turn", h, rgb);
+        assert!((0.0..=1.0 + EPSILON).contains(&s), "saturation {} of {:?}", s, rgb);
+        assert_rgb_eq(hsl_to_rgb(h, s, l), rgb);
+    }
+}
+
+#[test]
+fn hsl_round_trips_through_rgb() {
+    let mut rng = seeded_rng(1);
+    for _ in 0..SAMPLES {
+        let h = rng.gen::<f32>() * 2.0 * PI;
+        // Keep clear of the achromatic colors, whose hue is lost.
+        let s = 0.05 + rng.gen::<f32>() * 0.95;
+        let l = 0.05 + rng.gen::<f32>() * 0.9;
+        let (r, g, b) = hsl_to_rgb(h, s, l);
+        let (h2, s2, l2) = rgb_to_hsl(r, g, b);
+        assert!(hue_distance(h, h2) < 1e-3, "hue {} became {}", h, h2);
+        assert!((s - s2).abs() < 1e-3, "saturation {} became {}", s, s2);
+        assert!((l - l2).abs() < EPSILON, "lightness {} became {}", l, l2);
+    }
+}
+
+#[test]
+fn achromatic_colors_have_no_saturation() {
+    for &v in &[0.0, 0.25, 0.5, 1.0] {
+        let (h, s, l) = rgb_to_hsl(v, v, v);
+        assert_eq!((h, s, l), (0.0, 0.0, v));
+    }
+}
+
+#[test]
+fn hue_of_a_whole_turn_is_red() {
+    assert_rgb_eq(hsl_to_rgb(degrees(360.0), 1.0, 0.5), (1.0, 0.0, 0.0));
+    assert_rgb_eq(hsl_to_rgb(2.0 * PI, 1.0, 0.5), (1.0, 0.0, 0.0));
+}
+
+#[test]
+fn hues_outside_of_a_turn_wrap() {
+    assert_rgb_eq(hsl_to_rgb(degrees(-120.0), 1.0, 0.5), hsl_to_rgb(degrees(240.0), 1.0, 0.5));
+    assert_rgb_eq(hsl_to_rgb(degrees(480.0), 1.0, 0.5), hsl_to_rgb(degrees(120.0), 1.0, 0.5));
+    assert_rgb_eq(hsl_to_rgb(-1e-7, 1.0, 0.5), (1.0, 0.0, 0.0));
+}
+
+#[test]
+fn hues_just_below_red_stay_within_a_turn() {
+    // Blue a hair above green puts the hue just below zero before it is wrapped.
+    let (h, _, _) = rgb_to_hsl(1.0, 0.5, 0.5 + 1e-7);
+    assert!((0.0..2.0 * PI).contains(&h), "hue {}", h);
+}
+
+#[test]
+fn hsl_colors_convert_to_rgb() {
+    let color::Rgba(r, g, b, a) = color::hsl(degrees(-1e-6), 1.0, 0.5).to_rgb();
+    assert_rgb_eq((r, g, b), (1.0, 0.0, 0.0));
+    assert_eq!(a, 1.0);
+}
+
+/// Assert that two colors have the same RGBA channels, whether given as RGB or HSL.
+fn assert_color_eq(a: color::Color, b: color::Color) {
+    let (color::Rgba(r1, g1, b1, a1), color::Rgba(r2, g2, b2, a2)) = (a.to_rgb(), b.to_rgb());
+    assert_rgb_eq((r1, g1, b1), (r2, g2, b2));
+    assert!((a1 - a2).abs() < EPSILON, "alpha {} != {}", a1, a2);
+}
+
+#[test]
+fn parse_hex_colors() {
+    assert_eq!("#f80".parse(), Ok(color::rgb_bytes(0xff, 0x88, 0x00)));
+    assert_eq!("#F80".parse(), Ok(color::rgb_bytes(0xff, 0x88, 0x00)));
+    assert_eq!("#ff8000".parse(), Ok(color::rgb_bytes(0xff, 0x80, 0x00)));
+    assert_eq!("#ff800080".parse(), Ok(color::rgba_bytes(0xff, 0x80, 0x00, 128.0 / 255.0)));
+    assert_eq!("#f808".parse(), Ok(color::rgba_bytes(0xff, 0x88, 0x00, 136.0 / 255.0)));
+    assert_eq!("  #000000  ".parse(), Ok(color::rgb_bytes(0, 0, 0)));
+}
+
+#[test]
+fn parse_color_functions() {
+    let parse = |s: &str| s.parse::<color::Color>().unwrap();
+    assert_color_eq(parse("rgb(255, 128, 0)"), color::rgb_bytes(255, 128, 0));
+    assert_color_eq(parse("rgb(100%, 50%, 0%)"), color::rgb(1.0, 0.5, 0.0));
+    assert_color_eq(parse("RGB(255 128 0 / 50%)"), color::rgba_bytes(255, 128, 0, 0.5));
+    assert_color_eq(parse("rgba(255, 128, 0, 0.25)"), color::rgba_bytes(255, 128, 0, 0.25));
+    assert_color_eq(parse("rgb(300, -20, 0)"), color::rgb(1.0, 0.0, 0.0));
+    assert_color_eq(parse("hsl(120, 100%, 50%)"), color::rgb(0.0, 1.0, 0.0));
+    assert_color_eq(parse("hsl(240deg 100% 50%)"), color::rgb(0.0, 0.0, 1.0));
+    assert_color_eq(parse("hsl(0.5turn, 100%, 50%)"), color::rgb(0.0, 1.0, 1.0));
+    assert_color_eq(parse("hsla(3.14159265rad, 100%, 50%, 0.5)"), color::rgba(0.0, 1.0, 1.0, 0.5));
+}
+
+#[test]
+fn parse_color_errors() {
+    use elmesque::color::ParseColorError::*;
+    let parse = |s: &str| s.parse::<color::Color>();
+    assert_eq!(parse("#ff"), Err(InvalidHex));
+    assert_eq!(parse("#fffff"), Err(InvalidHex));
+    assert_eq!(parse("#ggg"), Err(InvalidHex));
+    assert_eq!(parse("#"), Err(InvalidHex));
+    assert_eq!(parse("red"), Err(UnknownSyntax));
+    assert_eq!(parse("rgb(1, 2, 3"), Err(UnknownSyntax));
+    assert_eq!(parse("cmyk(1, 2, 3, 4)"), Err(UnknownSyntax));
+    assert_eq!(parse("rgb(1, 2)"), Err(InvalidArgument));
+    assert_eq!(parse("rgba(1, 2, 3, 4, 5)"), Err(InvalidArgument));
+    assert_eq!(parse("rgb(1, two, 3)"), Err(InvalidArgument));
+    assert_eq!(parse("hsl(1grad, 50%, 50%)"), Err(InvalidArgument));
+}
+
+#[test]
+fn colors_round_trip_through_strings() {
+    let mut rng = seeded_rng(0);
+    for _ in 0..SAMPLES {
+        let (r, g, b) = (rng.gen::<u8>(), rng.gen::<u8>(), rng.gen::<u8>());
+        let colors = [
+            color::rgb_bytes(r, g, b),
+            color::rgba_bytes(r, g, b, rng.gen::<f32>()),
+            color::hsl(rng.gen::<f32>() * 2.0 * PI, rng.gen::<f32>(), rng.gen::<f32>()),
+            color::hsla(rng.gen::<f32>() * 2.0 * PI, rng.gen::<f32>(), rng.gen::<f32>(),
+                        rng.gen::<f32>()),
+        ];
+        for &color in colors.iter() {
+            let string = color.to_string();
+            let parsed: color::Color = string.parse()
+                .unwrap_or_else(|e| panic!("{:?} failed to parse: {}", string, e));
+            assert_color_eq(parsed, color);
+        }
+    }
+}
+
+#[test]
+fn display_uses_css_syntax() {
+    assert_eq!(color::rgb_bytes(255, 128, 0).to_string(), "#ff8000");
+    assert_eq!(color::rgba_bytes(255, 128, 0, 0.5).to_string(), "rgba(255, 128, 0, 0.5)");
+    assert_eq!(color::hsl(0.0, 1.0, 0.5).to_string(), "hsl(0, 100%, 50%)");
+    assert_eq!(color::hsla(0.0, 0.5, 0.25, 0.5).to_string(), "hsla(0, 50%, 25%, 0.5)");
+}
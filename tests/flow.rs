@@ -0,0 +1,219 @@
+extern crate elmesque;
+
+use elmesque::element::{self, down, flow_flex, flow_justified, right, spacer, Element, Justify};
+use elmesque::element::Prim;
+
+
+/// The ids of a flow's children that have one, along with the offsets of their centers from the
+/// center of the flow. Children without an id, such as the gaps added by `flow_justified`, are
+/// skipped.
+fn offsets_by_id(flow: &Element) -> Vec<(String, (f64, f64))> {
+    let (dir, children) = match flow.element {
+        Prim::Flow(dir, ref children) => (dir, children),
+        ref prim => panic!("expected a flow, found {:?}", prim),
+    };
+    let offsets = element::flow_offsets(dir, flow.get_size(), children);
+    children.iter().zip(offsets)
+        .filter_map(|(child, offset)| child.props.id.as_ref().map(|id| (id.to_string(), offset)))
+        .collect()
+}
+
+
+/// The widths of a horizontal flow's children.
+fn widths(flow: &Element) -> Vec<i32> {
+    match flow.element {
+        Prim::Flow(_, ref children) => children.iter().map(|child| child.get_width()).collect(),
+        ref prim => panic!("expected a flow, found {:?}", prim),
+    }
+}
+
+
+fn named(id: &str, w: i32, h: i32) -> Element {
+    spacer(w, h).id(id)
+}
+
+
+fn expected(offsets: &[(&str, (f64, f64))]) -> Vec<(String, (f64, f64))> {
+    offsets.iter().map(|&(id, offset)| (id.to_string(), offset)).collect()
+}
+
+
+fn row() -> Vec<Element> {
+    vec![named("a", 10, 10), named("b", 20, 10), named("c", 30, 10)]
+}
+
+
+#[test]
+fn justify_space_between() {
+    let element = flow_justified(right(), 100, Justify::SpaceBetween, row());
+    assert_eq!(element.get_size(), (100, 10));
+    // The leftover 40 pixels are split between the two inner gaps.
+    let offsets = expected(&[("a", (-45.0, 0.0)), ("b", (-10.0, 0.0)), ("c", (35.0, 0.0))]);
+    assert_eq!(offsets_by_id(&element), offsets);
+}
+
+
+#[test]
+fn justify_space_around() {
+    let element = flow_justified(right(), 100, Justify::SpaceAround, row());
+    assert_eq!(element.get_size(), (100, 10));
+    // The leftover 40 pixels in sixths, rounded to gaps of 7, 13, 13 and 7.
+    let offsets = expected(&[("a", (-38.0, 0.0)), ("b", (-10.0, 0.0)), ("c", (28.0, 0.0))]);
+    assert_eq!(offsets_by_id(&element), offsets);
+}
+
+
+#[test]
+fn justify_space_evenly() {
+    let element = flow_justified(right(), 100, Justify::SpaceEvenly, row());
+    assert_eq!(element.get_size(), (100, 10));
+    let offsets = expected(&[("a", (-35.0, 0.0)), ("b", (-10.0, 0.0)), ("c", (25.0, 0.0))]);
+    assert_eq!(offsets_by_id(&element), offsets);
+}
+
+
+#[test]
+fn justify_vertical() {
+    let column = vec![named("a", 10, 10), named("b", 20, 30)];
+    let element = flow_justified(down(), 100, Justify::SpaceEvenly, column);
+    assert_eq!(element.get_size(), (20, 100));
+    assert_eq!(offsets_by_id(&element), expected(&[("a", (0.0, 25.0)), ("b", (0.0, -15.0))]));
+}
+
+
+#[test]
+fn justify_single_child() {
+    // With nothing to space between, the child stays at the start of the flow.
+    let element = flow_justified(right(), 100, Justify::SpaceBetween, vec![named("a", 20, 10)]);
+    assert_eq!(element.get_size(), (100, 10));
+    assert_eq!(offsets_by_id(&element), expected(&[("a", (-40.0, 0.0))]));
+
+    for &justify in &[Justify::SpaceAround, Justify::SpaceEvenly] {
+        let element = flow_justified(right(), 100, justify, vec![named("a", 20, 10)]);
+        assert_eq!(element.get_size(), (100, 10));
+        assert_eq!(offsets_by_id(&element), expected(&[("a", (0.0, 0.0))]));
+    }
+}
+
+
+#[test]
+fn justify_skips_collapsed_children() {
+    let children = vec![
+        named("a", 10, 10),
+        named("b", 50, 10).collapsed(true),
+        named("empty", 0, 0),
+        named("c", 30, 10),
+    ];
+    let element = flow_justified(right(), 100, Justify::SpaceBetween, children);
+    assert_eq!(element.get_size(), (100, 10));
+    assert_eq!(offsets_by_id(&element), expected(&[("a", (-45.0, 0.0)), ("c", (35.0, 0.0))]));
+}
+
+
+#[test]
+fn justify_without_leftover_space() {
+    for &total in &[60, 40] {
+        let element = flow_justified(right(), total, Justify::SpaceEvenly, row());
+        assert_eq!(element.get_size(), (60, 10));
+        let offsets = expected(&[("a", (-25.0, 0.0)), ("b", (-10.0, 0.0)), ("c", (15.0, 0.0))]);
+        assert_eq!(offsets_by_id(&element), offsets);
+    }
+}
+
+
+#[test]
+fn justify_layers() {
+    let element = flow_justified(element::outward(), 100, Justify::SpaceEvenly, row());
+    assert_eq!(element.get_size(), (30, 10));
+    let offsets = expected(&[("a", (0.0, 0.0)), ("b", (0.0, 0.0)), ("c", (0.0, 0.0))]);
+    assert_eq!(offsets_by_id(&element), offsets);
+}
+
+
+#[test]
+fn flex_in_proportion_to_weights() {
+    let children = vec![
+        (0.0, named("a", 10, 10)),
+        (1.0, named("b", 10, 10)),
+        (3.0, named("c", 10, 10)),
+    ];
+    let element = flow_flex(right(), 100, children);
+    assert_eq!(element.get_size(), (100, 10));
+    // The leftover 70 pixels are split 17.5 to 52.5, rounded so that they still sum to 70.
+    assert_eq!(widths(&element), vec![10, 28, 62]);
+    let offsets = expected(&[("a", (-45.0, 0.0)), ("b", (-26.0, 0.0)), ("c", (19.0, 0.0))]);
+    assert_eq!(offsets_by_id(&element), offsets);
+}
+
+
+#[test]
+fn flex_leftover_pixels() {
+    // 10 pixels can't be split evenly three ways, so the middle child takes the spare pixel.
+    let children = vec![
+        (1.0, named("a", 10, 10)),
+        (1.0, named("b", 10, 10)),
+        (1.0, named("c", 10, 10)),
+    ];
+    let element = flow_flex(right(), 40, children);
+    assert_eq!(element.get_size(), (40, 10));
+    assert_eq!(widths(&element), vec![13, 14, 13]);
+    let offsets = expected(&[("a", (-13.5, 0.0)), ("b", (0.0, 0.0)), ("c", (13.5, 0.0))]);
+    assert_eq!(offsets_by_id(&element), offsets);
+}
+
+
+#[test]
+fn flex_vertical() {
+    let children = vec![(1.0, named("a", 10, 10)), (0.0, named("b", 20, 30))];
+    let element = flow_flex(down(), 100, children);
+    assert_eq!(element.get_size(), (20, 100));
+    assert_eq!(offsets_by_id(&element), expected(&[("a", (0.0, 15.0)), ("b", (0.0, -35.0))]));
+}
+
+
+#[test]
+fn flex_zero_weights() {
+    for &weights in &[(0.0, 0.0), (-1.0, 0.0)] {
+        let children = vec![(weights.0, named("a", 10, 10)), (weights.1, named("b", 20, 10))];
+        let element = flow_flex(right(), 100, children);
+        assert_eq!(element.get_size(), (30, 10));
+        assert_eq!(widths(&element), vec![10, 20]);
+    }
+}
+
+
+#[test]
+fn flex_total_smaller_than_fixed_children() {
+    for &total in &[30, 20, 0, -10] {
+        let children = vec![(0.0, named("a", 10, 10)), (1.0, named("b", 20, 10))];
+        let element = flow_flex(right(), total, children);
+        assert_eq!(element.get_size(), (30, 10));
+        assert_eq!(widths(&element), vec![10, 20]);
+        assert_eq!(offsets_by_id(&element), expected(&[("a", (-10.0, 0.0)), ("b", (5.0, 0.0))]));
+    }
+}
+
+
+#[test]
+fn flex_skips_collapsed_children() {
+    let children = vec![
+        (1.0, named("a", 10, 10)),
+        (1.0, named("b", 50, 10).collapsed(true)),
+        (1.0, named("c", 10, 10)),
+    ];
+    let element = flow_flex(right(), 100, children);
+    assert_eq!(element.get_size(), (100, 10));
+    // The collapsed child neither takes up space nor grows.
+    assert_eq!(widths(&element), vec![50, 50, 50]);
+    let offsets = expected(&[("a", (-25.0, 0.0)), ("b", (0.0, 0.0)), ("c", (25.0, 0.0))]);
+    assert_eq!(offsets_by_id(&element), offsets);
+}
+
+
+#[test]
+fn flex_layers() {
+    let children = vec![(1.0, named("a", 10, 10)), (1.0, named("b", 20, 10))];
+    let element = flow_flex(element::outward(), 100, children);
+    assert_eq!(element.get_size(), (20, 10));
+    assert_eq!(widths(&element), vec![10, 20]);
+}
@@ -0,0 +1,79 @@
+extern crate elmesque;
+
+use elmesque::color;
+use elmesque::element::{self, flow, middle, right, spacer, top_left, Element};
+use elmesque::form::{self, collage};
+
+
+/// An Element taking up no space of its own, so that only the given child may be over a point.
+fn overflowing(child: Element) -> Element {
+    child.container(0, 0, middle())
+}
+
+
+#[test]
+fn point_within_rect() {
+    let element = spacer(20, 10);
+    assert!(element.is_over(0, 0));
+    assert!(element.is_over(-9, 4));
+    assert!(!element.is_over(11, 0));
+    assert!(!element.is_over(0, -6));
+}
+
+
+#[test]
+fn flow_children() {
+    let row = flow(right(), vec![spacer(20, 10), spacer(20, 30)]);
+    let element = overflowing(row);
+    // Within the first child, and within the second where it is taller than the first.
+    assert!(element.is_over(-10, 0));
+    assert!(element.is_over(10, 12));
+    // Beyond the last child.
+    assert!(!element.is_over(25, 0));
+    assert!(!element.is_over(0, 20));
+}
+
+
+#[test]
+fn container_offset() {
+    let element = overflowing(spacer(10, 10).container(40, 40, top_left()));
+    // The container spans -20..20 along each axis, with its child in the top left corner.
+    assert!(element.is_over(-15, 15));
+    assert!(element.is_over(15, -15));
+    let element = overflowing(spacer(10, 10).container(0, 0, top_left()));
+    assert!(element.is_over(5, -5));
+    assert!(!element.is_over(-5, 5));
+}
+
+
+#[test]
+fn collage_forms() {
+    let forms = vec![form::rect(10.0, 10.0).filled(color::red()).shift(20.0, 0.0)];
+    let element = collage(10, 10, forms);
+    // Within the collage's own rectangle, and within the form extending beyond it.
+    assert!(element.is_over(0, 0));
+    assert!(element.is_over(20, 0));
+    assert!(!element.is_over(30, 0));
+    assert!(!element.is_over(10, 10));
+}
+
+
+#[test]
+fn cropped_child() {
+    let child = spacer(40, 10).crop(0.0, 0.0, 20.0, 10.0);
+    let element = overflowing(child);
+    assert!(element.is_over(5, 0));
+    assert!(!element.is_over(15, 0));
+
+    let child = spacer(40, 10).crop_local(10.0, 0.0, 20.0, 10.0);
+    let element = overflowing(child);
+    assert!(element.is_over(15, 0));
+    assert!(!element.is_over(-15, 0));
+}
+
+
+#[test]
+fn responsive_is_never_over() {
+    let element = element::responsive(spacer);
+    assert!(!element.is_over(0, 0));
+}
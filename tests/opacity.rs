@@ -0,0 +1,102 @@
+extern crate elmesque;
+
+use elmesque::backend::{self, Backend};
+use elmesque::color::{self, Color};
+use elmesque::element::{self, Element, ImageStyle};
+use elmesque::form::{self, FillStyle, LineStyle};
+use elmesque::text::Text;
+use elmesque::transform_2d::{self, Transform2D};
+use std::path::{Path, PathBuf};
+
+
+/// A `Backend` that records the opacity with which each primitive is drawn.
+#[derive(Default)]
+struct Recorder {
+    alphas: Vec<f32>,
+}
+
+impl Backend for Recorder {
+    fn clear(&mut self, color: Color) {
+        self.alphas.push(color.to_fsa()[3]);
+    }
+    fn fill_polygon(&mut self, _: &Transform2D, _: &[(f64, f64)], style: &FillStyle,
+                    alpha: f32) {
+        match *style {
+            FillStyle::Solid(color) => self.alphas.push(color.to_fsa()[3] * alpha),
+            _ => self.alphas.push(alpha),
+        }
+    }
+    fn stroke_path(&mut self, _: &Transform2D, _: &[(f64, f64)], _: bool, _: &LineStyle,
+                   alpha: f32) {
+        self.alphas.push(alpha);
+    }
+    fn draw_text(&mut self, _: &Transform2D, _: &Text, _: Option<&LineStyle>, alpha: f32) {
+        self.alphas.push(alpha);
+    }
+    fn draw_image(&mut self, _: &Transform2D, _: ImageStyle, _: (i32, i32), _: &Path,
+                  alpha: f32) {
+        self.alphas.push(alpha);
+    }
+    fn push_clip(&mut self, _: (f64, f64, f64, f64)) {}
+    fn push_clip_shape(&mut self, _: &Transform2D, _: &[(f64, f64)]) {}
+    fn pop_clip(&mut self) {}
+    fn target_size(&self) -> (f64, f64) { (100.0, 100.0) }
+}
+
+fn alphas(element: &Element) -> Vec<f32> {
+    let mut recorder = Recorder::default();
+    backend::draw_element(element, 1.0, transform_2d::identity(), &mut recorder, &mut None);
+    recorder.alphas
+}
+
+fn square() -> form::Form {
+    form::rect(10.0, 10.0).filled(color::red())
+}
+
+
+#[test]
+fn nested_element_opacity_multiplies() {
+    let inner = element::image(10, 10, PathBuf::from("a.png")).opacity(0.5);
+    let outer = element::layers(vec![inner]).opacity(0.5);
+    assert_eq!(alphas(&outer), vec![0.25]);
+}
+
+#[test]
+fn background_is_faded_with_its_element() {
+    let e = element::spacer(10, 10).color(color::red()).opacity(0.5);
+    let nested = element::layers(vec![e]).opacity(0.5);
+    assert_eq!(alphas(&nested), vec![0.25]);
+}
+
+#[test]
+fn clear_color_is_faded_by_own_opacity() {
+    let cleared = element::spacer(10, 10).clear(color::red().alpha(0.5)).opacity(0.5);
+    assert_eq!(alphas(&cleared), vec![0.25]);
+}
+
+#[test]
+fn nested_clear_color_is_faded_by_parent_opacity() {
+    let cleared = element::spacer(10, 10).clear(color::red().alpha(0.5)).opacity(0.5);
+    let nested = element::layers(vec![cleared]).opacity(0.5);
+    assert_eq!(alphas(&nested), vec![0.125]);
+}
+
+#[test]
+fn collage_forms_multiply_element_and_form_alpha() {
+    let forms = vec![square().alpha(0.5), form::group(vec![square().alpha(0.5)]).alpha(0.5)];
+    let collage = form::collage(20, 20, forms).opacity(0.5);
+    assert_eq!(alphas(&collage), vec![0.25, 0.125]);
+}
+
+#[test]
+fn elements_within_forms_inherit_form_alpha() {
+    let inner = element::spacer(10, 10).color(color::red()).opacity(0.5);
+    let collage = form::collage(20, 20, vec![form::to_form(inner).alpha(0.5)]).opacity(0.5);
+    assert_eq!(alphas(&collage), vec![0.125]);
+}
+
+#[test]
+fn invisible_forms_are_skipped() {
+    let collage = form::collage(20, 20, vec![square()]).opacity(0.0);
+    assert!(alphas(&collage).is_empty());
+}
@@ -0,0 +1,46 @@
+#![cfg(feature = "piston-backend")]
+
+extern crate elmesque;
+
+use elmesque::color;
+use elmesque::element::{flow, right, top_left, Element};
+use elmesque::form::{collage, rect};
+use elmesque::testing;
+
+
+/// A collage filled entirely with red, so that any pixel left unclipped shows.
+fn filled(w: i32, h: i32) -> Element {
+    collage(w, h, vec![rect(w as f64, h as f64).filled(color::red())])
+}
+
+
+#[test]
+fn rounded_corners_clip_content() {
+    let image = testing::render(&filled(40, 40).corner_radius(15.0), 40, 40).unwrap();
+    assert_eq!(image.pixel(0, 0)[3], 0);
+    assert_eq!(image.pixel(39, 39)[3], 0);
+    assert_eq!(image.pixel(20, 20), [204, 0, 0, 255]);
+    assert_eq!(image.pixel(20, 0), [204, 0, 0, 255]);
+}
+
+
+#[test]
+fn nested_rounded_corners_clip_to_both() {
+    // The inner Element sits in the top left corner, so each clip cuts away a different part.
+    let inner = filled(30, 30).corner_radius(10.0).container(40, 40, top_left());
+    let image = testing::render(&inner.corner_radius(15.0), 40, 40).unwrap();
+    assert_eq!(image.pixel(2, 5)[3], 0);
+    assert_eq!(image.pixel(28, 28)[3], 0);
+    assert_eq!(image.pixel(15, 15), [204, 0, 0, 255]);
+}
+
+
+#[test]
+fn clips_are_restored_once_popped() {
+    let element = flow(right(), vec![filled(20, 20).corner_radius(10.0), filled(20, 20)]);
+    let image = testing::render(&element, 40, 20).unwrap();
+    assert_eq!(image.pixel(0, 0)[3], 0);
+    // The second Element isn't rounded, so its corners are drawn.
+    assert_eq!(image.pixel(39, 0), [204, 0, 0, 255]);
+    assert_eq!(image.pixel(21, 19), [204, 0, 0, 255]);
+}
@@ -0,0 +1,74 @@
+#![cfg(feature = "piston-backend")]
+
+extern crate elmesque;
+extern crate graphics;
+
+use elmesque::utils::view_rect_to_scissor;
+use graphics::{Context, Viewport};
+
+
+/// A context for a 200x100 view.
+fn context() -> Context {
+    Context::new_abs(200.0, 100.0)
+}
+
+
+#[test]
+fn centered_rect() {
+    assert_eq!(view_rect_to_scissor(&context(), (0.0, 0.0, 100.0, 50.0)), Some([50, 25, 100, 50]));
+}
+
+#[test]
+fn whole_view() {
+    assert_eq!(view_rect_to_scissor(&context(), (0.0, 0.0, 200.0, 100.0)), Some([0, 0, 200, 100]));
+}
+
+#[test]
+fn rect_hanging_off_the_bottom_left_is_clipped() {
+    assert_eq!(view_rect_to_scissor(&context(), (-100.0, -50.0, 100.0, 50.0)),
+               Some([0, 0, 50, 25]));
+}
+
+#[test]
+fn rect_hanging_off_the_top_right_is_clipped() {
+    assert_eq!(view_rect_to_scissor(&context(), (100.0, 50.0, 100.0, 50.0)),
+               Some([150, 75, 50, 25]));
+}
+
+#[test]
+fn rect_larger_than_the_view_covers_the_view() {
+    assert_eq!(view_rect_to_scissor(&context(), (0.0, 0.0, 1.0e9, 1.0e9)), Some([0, 0, 200, 100]));
+}
+
+#[test]
+fn rect_outside_of_the_view_has_no_scissor() {
+    assert_eq!(view_rect_to_scissor(&context(), (500.0, 0.0, 10.0, 10.0)), None);
+    assert_eq!(view_rect_to_scissor(&context(), (0.0, -500.0, 10.0, 10.0)), None);
+    assert_eq!(view_rect_to_scissor(&context(), (-1.0e9, 0.0, 10.0, 10.0)), None);
+}
+
+#[test]
+fn rect_without_area_has_no_scissor() {
+    assert_eq!(view_rect_to_scissor(&context(), (0.0, 0.0, 0.0, 50.0)), None);
+    assert_eq!(view_rect_to_scissor(&context(), (0.0, 0.0, -100.0, 50.0)), None);
+}
+
+#[test]
+fn rect_is_stretched_to_the_draw_size() {
+    let mut context = context();
+    context.viewport = Some(Viewport {
+        rect: [0, 0, 400, 200],
+        draw_size: [400, 200],
+        window_size: [200, 100],
+    });
+    assert_eq!(view_rect_to_scissor(&context, (0.0, 0.0, 100.0, 50.0)), Some([100, 50, 200, 100]));
+}
+
+#[test]
+fn rect_is_intersected_with_the_current_scissor() {
+    let mut context = context();
+    context.draw_state = context.draw_state.scissor(0, 0, 60, 60);
+    assert_eq!(view_rect_to_scissor(&context, (0.0, 0.0, 100.0, 50.0)), Some([50, 25, 10, 35]));
+    context.draw_state = context.draw_state.scissor(160, 0, 40, 100);
+    assert_eq!(view_rect_to_scissor(&context, (0.0, 0.0, 100.0, 50.0)), None);
+}
@@ -0,0 +1,139 @@
+extern crate elmesque;
+
+use elmesque::svg::{self, parse_path, Error, SubPath, CURVE_SEGMENTS};
+
+
+/// The points of the only subpath within the given path data.
+fn points(data: &str) -> Vec<(f64, f64)> {
+    let subpaths = parse_path(data).unwrap();
+    assert_eq!(subpaths.len(), 1, "expected a single subpath within {:?}", data);
+    subpaths[0].points.clone()
+}
+
+
+/// Whether or not every point lies the given distance from the center.
+fn all_at_distance(points: &[(f64, f64)], (cx, cy): (f64, f64), distance: f64) -> bool {
+    points.iter().all(|&(x, y)| ((x - cx).hypot(y - cy) - distance).abs() < 1e-9)
+}
+
+
+#[test]
+fn relative_and_absolute_commands() {
+    let expected = vec![(10.0, 10.0), (20.0, 10.0), (20.0, 20.0)];
+    assert_eq!(points("M 10 10 L 20 10 L 20 20"), expected);
+    assert_eq!(points("m 10 10 l 10 0 l 0 10"), expected);
+    assert_eq!(points("M10,10 l10,0 L20,20"), expected);
+}
+
+
+#[test]
+fn implicit_line_tos_after_move_to() {
+    assert_eq!(points("M 0 0 10 0 10 10"), vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0)]);
+    assert_eq!(points("m 1 1 2 0 0 2"), vec![(1.0, 1.0), (3.0, 1.0), (3.0, 3.0)]);
+    assert_eq!(points("M 0 0 L 1 0 2 0"), vec![(0.0, 0.0), (1.0, 0.0), (2.0, 0.0)]);
+}
+
+
+#[test]
+fn horizontal_and_vertical_line_tos() {
+    let expected = vec![(0.0, 0.0), (10.0, 0.0), (10.0, 5.0), (7.0, 5.0), (7.0, 3.0)];
+    assert_eq!(points("M 0 0 H 10 V 5 h -3 v -2"), expected);
+}
+
+
+#[test]
+fn smooth_cubic_curves() {
+    // The first control point of `S` reflects the last control point of the previous curve.
+    assert_eq!(points("M 0 0 C 0 10 10 10 10 0 S 20 -10 20 0"),
+               points("M 0 0 C 0 10 10 10 10 0 C 10 -10 20 -10 20 0"));
+    assert_eq!(points("M 0 0 C 0 10 10 10 10 0 s 10 -10 10 0"),
+               points("M 0 0 C 0 10 10 10 10 0 C 10 -10 20 -10 20 0"));
+    // Without a previous cubic curve, the first control point is the current point.
+    assert_eq!(points("M 0 0 L 5 0 S 10 10 10 0"), points("M 0 0 L 5 0 C 5 0 10 10 10 0"));
+    let curve = points("M 0 0 S 10 10 10 0");
+    assert_eq!(curve.len(), CURVE_SEGMENTS + 1);
+    assert_eq!(curve.last(), Some(&(10.0, 0.0)));
+}
+
+
+#[test]
+fn smooth_quadratic_curves() {
+    assert_eq!(points("M 0 0 Q 5 10 10 0 T 20 0"), points("M 0 0 Q 5 10 10 0 Q 15 -10 20 0"));
+    assert_eq!(points("M 0 0 Q 5 10 10 0 t 10 0"), points("M 0 0 Q 5 10 10 0 Q 15 -10 20 0"));
+    // A cubic curve's control point isn't reflected by `T`.
+    assert_eq!(points("M 0 0 C 0 10 10 10 10 0 T 20 0"),
+               points("M 0 0 C 0 10 10 10 10 0 Q 10 0 20 0"));
+}
+
+
+#[test]
+fn arcs_follow_the_sweep_flag() {
+    // A semicircle from (0, 0) to (20, 0) about (10, 0), with one segment per point.
+    let positive = points("M 0 0 A 10 10 0 0 1 20 0");
+    let negative = points("M 0 0 A 10 10 0 0 0 20 0");
+    for arc in [&positive, &negative].iter() {
+        assert_eq!(arc.len(), 2 * CURVE_SEGMENTS + 1);
+        assert!(all_at_distance(arc, (10.0, 0.0), 10.0));
+        assert_eq!(arc.last(), Some(&(20.0, 0.0)));
+    }
+    assert!(positive[CURVE_SEGMENTS].1 < -9.9);
+    assert!(negative[CURVE_SEGMENTS].1 > 9.9);
+}
+
+
+#[test]
+fn arcs_follow_the_large_arc_flag() {
+    // Circles of radius 10 pass through both (0, 0) and (10, 10) about (10, 0) and (0, 10).
+    let small = points("M 0 0 A 10 10 0 0 1 10 10");
+    let large = points("M 0 0 A 10 10 0 1 1 10 10");
+    assert_eq!(small.len(), CURVE_SEGMENTS + 1);
+    assert_eq!(large.len(), 3 * CURVE_SEGMENTS + 1);
+    assert!(all_at_distance(&small, (0.0, 10.0), 10.0));
+    assert!(all_at_distance(&large, (10.0, 0.0), 10.0));
+}
+
+
+#[test]
+fn degenerate_arcs() {
+    // Zero radii draw a straight line, as do radii of either sign.
+    assert_eq!(points("M 0 0 A 0 5 0 0 1 10 10"), vec![(0.0, 0.0), (10.0, 10.0)]);
+    assert_eq!(points("M 0 0 A 5 0 0 0 1 10 10"), vec![(0.0, 0.0), (10.0, 10.0)]);
+    assert_eq!(points("M 0 0 A -10 -10 0 0 1 20 0"), points("M 0 0 A 10 10 0 0 1 20 0"));
+    // Radii too small to span the endpoints are scaled up until they do.
+    let arc = points("M 0 0 A 1 1 0 0 1 20 0");
+    assert!(all_at_distance(&arc, (10.0, 0.0), 10.0));
+    // An arc ending where it starts draws nothing.
+    assert_eq!(parse_path("M 5 5 A 10 10 0 0 1 5 5").unwrap(), vec![]);
+    // Flags need no separators.
+    assert_eq!(points("M0 0A10 10 0 0120 0"), points("M 0 0 A 10 10 0 0 1 20 0"));
+}
+
+
+#[test]
+fn commands_after_close_path() {
+    let subpaths = parse_path("M 0 0 L 10 0 L 10 10 Z L 0 10").unwrap();
+    assert_eq!(subpaths, vec![
+        SubPath { points: vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0)], closed: true },
+        SubPath { points: vec![(0.0, 0.0), (0.0, 10.0)], closed: false },
+    ]);
+    // Relative commands after a close-path are relative to the start of the closed subpath.
+    let subpaths = parse_path("M 1 1 L 10 1 L 10 10 z m 5 5 l 1 0").unwrap();
+    assert_eq!(subpaths[1], SubPath { points: vec![(6.0, 6.0), (7.0, 6.0)], closed: false });
+}
+
+
+#[test]
+fn errors_with_their_byte_index() {
+    assert_eq!(parse_path("L 10 10"), Err(Error::MissingMoveTo));
+    assert_eq!(parse_path("10 10"), Err(Error::MissingMoveTo));
+    assert_eq!(parse_path("M 0 0 X 1 2"), Err(Error::UnexpectedChar('X', 6)));
+    assert_eq!(parse_path("M 0 0 L 1 2 #"), Err(Error::UnexpectedChar('#', 12)));
+    assert_eq!(parse_path("M 0 0 Z 1 2"), Err(Error::UnexpectedChar('1', 8)));
+    assert_eq!(parse_path("M 0 0 L 10"), Err(Error::ExpectedNumber(10)));
+    assert_eq!(parse_path("M 0 0 L 1 -"), Err(Error::ExpectedNumber(10)));
+    assert_eq!(parse_path("M 0 0 A 5 5 0 2 1 10 10"), Err(Error::ExpectedNumber(14)));
+    assert_eq!(svg::import("<svg width=\"10\"").err(), Some(Error::UnterminatedTag(0)));
+    let unterminated = "<svg><path d=\"M 0 0 L 1 1";
+    assert_eq!(svg::import(unterminated).err(), Some(Error::UnterminatedTag(5)));
+    assert_eq!(svg::import("").err(), Some(Error::Empty));
+}
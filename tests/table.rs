@@ -0,0 +1,131 @@
+extern crate elmesque;
+
+use elmesque::element::{self, spacer, table, ColumnWidth, Element, Prim};
+
+
+/// The offsets of the centers of each cell of a table from the center of the table, by row.
+fn cell_offsets(table: &Element) -> Vec<Vec<(f64, f64)>> {
+    let rows = match table.element {
+        Prim::Flow(_, ref rows) => rows,
+        ref prim => panic!("expected the table to be a flow of rows, found {:?}", prim),
+    };
+    let row_offsets = element::flow_offsets(element::down(), table.get_size(), rows);
+    rows.iter().zip(row_offsets).map(|(row, (_, y))| {
+        let cells = match row.element {
+            Prim::Flow(_, ref cells) => cells,
+            ref prim => panic!("expected a row to be a flow of cells, found {:?}", prim),
+        };
+        let offsets = element::flow_offsets(element::right(), row.get_size(), cells);
+        offsets.into_iter().map(|(x, _)| (x, y)).collect()
+    }).collect()
+}
+
+
+#[test]
+fn fit_columns() {
+    let rows = vec![
+        vec![spacer(10, 5), spacer(30, 5)],
+        vec![spacer(20, 15), spacer(5, 5)],
+    ];
+    let table = table(rows);
+    assert_eq!(table.cell_sizes(), (vec![20, 30], vec![5, 15]));
+    let element = table.into_element();
+    assert_eq!(element.get_size(), (50, 20));
+    assert_eq!(cell_offsets(&element), vec![
+        vec![(-15.0, 7.5), (10.0, 7.5)],
+        vec![(-15.0, -2.5), (10.0, -2.5)],
+    ]);
+}
+
+
+#[test]
+fn fit_columns_with_padding() {
+    let rows = vec![vec![spacer(10, 5), spacer(30, 5)]];
+    let table = table(rows).padding(2);
+    assert_eq!(table.cell_sizes(), (vec![14, 34], vec![9]));
+    let element = table.into_element();
+    assert_eq!(element.get_size(), (48, 9));
+    assert_eq!(cell_offsets(&element), vec![vec![(-17.0, 0.0), (7.0, 0.0)]]);
+}
+
+
+#[test]
+fn fixed_columns() {
+    let rows = vec![
+        vec![spacer(10, 10), spacer(30, 10), spacer(10, 10)],
+        vec![spacer(60, 10), spacer(10, 10), spacer(20, 10)],
+    ];
+    // Fixed widths ignore the width of the cells, cropping those that are wider, and columns
+    // beyond the end of the given widths fit their cells.
+    let columns = vec![ColumnWidth::Fixed(40), ColumnWidth::Fixed(-5)];
+    let table = table(rows).columns(columns).padding(1);
+    assert_eq!(table.cell_sizes(), (vec![40, 0, 22], vec![12, 12]));
+    let element = table.into_element();
+    assert_eq!(element.get_size(), (62, 24));
+    assert_eq!(cell_offsets(&element), vec![
+        vec![(-11.0, 6.0), (9.0, 6.0), (20.0, 6.0)],
+        vec![(-11.0, -6.0), (9.0, -6.0), (20.0, -6.0)],
+    ]);
+}
+
+
+#[test]
+fn weighted_columns() {
+    let rows = vec![vec![spacer(10, 10), spacer(10, 10), spacer(10, 10)]];
+    let columns = vec![ColumnWidth::Fit, ColumnWidth::Weighted(1.0), ColumnWidth::Weighted(3.0)];
+    let table = table(rows).columns(columns);
+
+    // Without a total width, weighted columns fit their cells.
+    assert_eq!(table.clone().cell_sizes(), (vec![10, 10, 10], vec![10]));
+
+    // The leftover 70 pixels are split 17.5 to 52.5, rounded so that they still sum to 70.
+    let table = table.width(100);
+    assert_eq!(table.cell_sizes(), (vec![10, 28, 62], vec![10]));
+    let element = table.into_element();
+    assert_eq!(element.get_size(), (100, 10));
+    assert_eq!(cell_offsets(&element), vec![vec![(-45.0, 0.0), (-26.0, 0.0), (19.0, 0.0)]]);
+}
+
+
+#[test]
+fn weighted_columns_without_leftover_space() {
+    let rows = vec![vec![spacer(30, 10), spacer(30, 10)]];
+    let columns = vec![ColumnWidth::Weighted(1.0), ColumnWidth::Weighted(0.0)];
+    let table = element::table(rows).columns(columns).width(40);
+    assert_eq!(table.cell_sizes(), (vec![30, 30], vec![10]));
+
+    // Columns without a positive weight never grow.
+    let rows = vec![vec![spacer(10, 10), spacer(10, 10)]];
+    let columns = vec![ColumnWidth::Weighted(-1.0), ColumnWidth::Weighted(0.0)];
+    let table = element::table(rows).columns(columns).width(40);
+    assert_eq!(table.cell_sizes(), (vec![10, 10], vec![10]));
+}
+
+
+#[test]
+fn ragged_rows() {
+    let rows = vec![
+        vec![spacer(10, 10), spacer(20, 10), spacer(30, 10)],
+        vec![spacer(40, 20)],
+        vec![],
+    ];
+    // Missing cells take up no space of their own, and an empty row has no height.
+    let table = table(rows);
+    assert_eq!(table.cell_sizes(), (vec![40, 20, 30], vec![10, 20, 0]));
+    let element = table.into_element();
+    assert_eq!(element.get_size(), (90, 30));
+    // Short rows are padded with empty cells, so every row has a cell for every column.
+    assert_eq!(cell_offsets(&element), vec![
+        vec![(-25.0, 10.0), (5.0, 10.0), (30.0, 10.0)],
+        vec![(-25.0, -5.0), (5.0, -5.0), (30.0, -5.0)],
+        vec![(-25.0, -15.0), (5.0, -15.0), (30.0, -15.0)],
+    ]);
+}
+
+
+#[test]
+fn empty_table() {
+    let table = table(vec![]);
+    assert_eq!(table.cell_sizes(), (vec![], vec![]));
+    assert_eq!(table.into_element().get_size(), (0, 0));
+}
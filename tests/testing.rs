@@ -0,0 +1,83 @@
+extern crate elmesque;
+
+use elmesque::testing::{self, Image, Mismatch, Tolerance};
+
+
+/// A small image with a distinct value in every channel of every pixel.
+fn gradient(width: u32, height: u32) -> Image {
+    let mut image = Image::new(width, height);
+    for (i, channel) in image.pixels.iter_mut().enumerate() {
+        *channel = (i * 7 % 256) as u8;
+    }
+    image
+}
+
+
+#[test]
+fn pam_round_trip() {
+    let image = gradient(5, 3);
+    let mut bytes = Vec::new();
+    image.write_pam(&mut bytes).unwrap();
+    assert!(bytes.starts_with(b"P7\nWIDTH 5\nHEIGHT 3\nDEPTH 4\nMAXVAL 255\n"));
+    let read = Image::read_pam(&bytes[..]).unwrap();
+    assert_eq!(read, image);
+    assert_eq!(read.content_hash(), image.content_hash());
+}
+
+
+#[test]
+fn pam_rejects_unsupported_images() {
+    assert!(Image::read_pam(&b"P6\n5 3\n255\n"[..]).is_err());
+    let depth_3 = b"P7\nWIDTH 1\nHEIGHT 1\nDEPTH 3\nMAXVAL 255\nENDHDR\n\0\0\0";
+    assert!(Image::read_pam(&depth_3[..]).is_err());
+    let maxval = b"P7\nWIDTH 1\nHEIGHT 1\nDEPTH 4\nMAXVAL 65535\nENDHDR\n";
+    assert!(Image::read_pam(&maxval[..]).is_err());
+    let truncated = b"P7\nWIDTH 2\nHEIGHT 1\nDEPTH 4\nMAXVAL 255\nENDHDR\n\0\0\0\0";
+    assert!(Image::read_pam(&truncated[..]).is_err());
+}
+
+
+#[test]
+fn compare_within_tolerance() {
+    let expected = gradient(10, 10);
+    let mut found = expected.clone();
+    found.pixels[0] = found.pixels[0].wrapping_add(2);
+    assert!(testing::compare(&expected, &expected, Tolerance::exact()).is_ok());
+    assert!(testing::compare(&expected, &found, Tolerance { channel: 2, pixels: 0.0 }).is_ok());
+    // Differing beyond the channel tolerance is still fine while few enough pixels differ.
+    assert!(testing::compare(&expected, &found, Tolerance { channel: 0, pixels: 0.01 }).is_ok());
+}
+
+
+#[test]
+fn compare_outside_tolerance() {
+    let expected = gradient(10, 10);
+    let mut found = expected.clone();
+    found.pixels[0] = found.pixels[0].wrapping_add(3);
+    found.pixels[4 * 50 + 1] = found.pixels[4 * 50 + 1].wrapping_add(3);
+    match testing::compare(&expected, &found, Tolerance { channel: 2, pixels: 0.01 }) {
+        Err(Mismatch::Pixels { differing, total }) => assert_eq!((differing, total), (2, 100)),
+        other => panic!("expected a pixel mismatch, found {:?}", other),
+    }
+    match testing::compare(&expected, &gradient(10, 9), Tolerance::default()) {
+        Err(Mismatch::Size { expected, found }) =>
+            assert_eq!((expected, found), ((10, 10), (10, 9))),
+        other => panic!("expected a size mismatch, found {:?}", other),
+    }
+}
+
+
+#[cfg(feature = "tiny-skia")]
+#[test]
+fn skia_golden() {
+    use elmesque::color;
+    use elmesque::form::{circle, collage, ngon, rect, solid};
+    let forms = vec![
+        rect(48.0, 24.0).filled(color::blue()).shift(0.0, -12.0),
+        circle(14.0).filled(color::red()).shift(-12.0, 8.0),
+        ngon(3, 14.0).outlined(solid(color::green())).shift(14.0, 10.0),
+    ];
+    let element = collage(64, 64, forms).clear(color::white());
+    let image = testing::render_skia(&element, 64, 64);
+    testing::assert_golden("tests/golden/skia_shapes.pam", &image, Tolerance::default());
+}
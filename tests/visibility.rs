@@ -0,0 +1,107 @@
+extern crate elmesque;
+
+use elmesque::color;
+use elmesque::element::{self, flow, middle, right, spacer, Element};
+use elmesque::form::{self, collage, Tag};
+
+
+/// A collage filled with a form carrying the given tag.
+fn tagged(w: i32, h: i32, tag: u64) -> Element {
+    let form = form::rect(w as f64, h as f64).filled(color::red()).tag(Tag(tag));
+    collage(w, h, vec![form])
+}
+
+
+#[test]
+fn resolve_hidden_root() {
+    let resolved = spacer(20, 10).color(color::red()).visible(false).resolve(100.0, 100.0);
+    assert!(resolved.display_list.commands.is_empty());
+    assert!(!resolved.root.visible);
+    assert!(resolved.node_at(0.0, 0.0).is_none());
+}
+
+
+#[test]
+fn resolve_hidden_child() {
+    let hidden = spacer(20, 10).color(color::red()).id("hidden").visible(false);
+    let element = flow(right(), vec![hidden, spacer(20, 10).id("shown")]);
+    let resolved = element.resolve(100.0, 100.0);
+    assert_eq!(resolved.root.children.len(), 2);
+    assert!(resolved.root.children[0].commands.is_empty());
+    assert!(resolved.id_at(-10.0, 0.0).is_none());
+    assert_eq!(resolved.id_at(10.0, 0.0).map(|id| id.to_string()), Some("shown".to_string()));
+}
+
+
+#[test]
+fn hidden_keeps_its_place_in_flows() {
+    let shown = flow(right(), vec![spacer(20, 10), spacer(30, 10)]);
+    let hidden = flow(right(), vec![spacer(20, 10).visible(false), spacer(30, 10)]);
+    assert_eq!(hidden.get_size(), shown.get_size());
+    assert_eq!(hidden.get_size(), (50, 10));
+}
+
+
+#[test]
+fn hidden_is_never_over() {
+    let element = spacer(20, 10).visible(false);
+    assert!(!element.is_over(0, 0));
+    let element = spacer(20, 10).container(0, 0, middle()).visible(false);
+    assert!(!element.is_over(0, 0));
+    let element = spacer(20, 10).visible(false).container(0, 0, middle());
+    assert!(!element.is_over(0, 0));
+}
+
+
+#[test]
+fn hidden_is_never_picked() {
+    let element = element::layers(vec![tagged(20, 20, 1), tagged(20, 20, 2).visible(false)]);
+    assert_eq!(element.pick_top(0.0, 0.0), Some(Tag(1)));
+    let element = element.visible(false);
+    assert_eq!(element.pick_top(0.0, 0.0), None);
+
+    let forms = vec![form::rect(20.0, 20.0).filled(color::red()).tag(Tag(3)).visible(false)];
+    assert_eq!(collage(20, 20, forms).pick_top(0.0, 0.0), None);
+}
+
+
+#[test]
+fn collapsed_takes_no_space_in_flows() {
+    let element = flow(right(), vec![spacer(20, 10).collapsed(true), spacer(30, 10)]);
+    assert_eq!(element.get_size(), (30, 10));
+    // The collapsed Element keeps its own size, so it takes up its space again once expanded.
+    let collapsed = spacer(20, 10).collapsed(true);
+    assert!(collapsed.is_empty());
+    assert_eq!(collapsed.get_size(), (20, 10));
+    let element = flow(right(), vec![collapsed.collapsed(false), spacer(30, 10)]);
+    assert_eq!(element.get_size(), (50, 10));
+}
+
+
+#[test]
+fn collapsed_offsets_in_flows() {
+    let children = vec![spacer(20, 10).collapsed(true), spacer(30, 10)];
+    let offsets = element::flow_offsets(right(), (30, 10), &children);
+    assert_eq!(offsets, vec![(-15.0, 0.0), (0.0, 0.0)]);
+}
+
+
+#[test]
+fn collapsed_is_neither_over_nor_picked() {
+    let collapsed = tagged(20, 20, 1).collapsed(true);
+    assert!(collapsed.is_hidden());
+    assert!(!collapsed.is_over(0, 0));
+    assert_eq!(collapsed.pick_top(0.0, 0.0), None);
+
+    let element = flow(right(), vec![tagged(20, 20, 1).collapsed(true), tagged(20, 20, 2)]);
+    assert!(element.is_over(0, 0));
+    assert_eq!(element.pick_top(0.0, 0.0), Some(Tag(2)));
+}
+
+
+#[test]
+fn resolve_collapsed_root() {
+    let resolved = spacer(20, 10).color(color::red()).collapsed(true).resolve(100.0, 100.0);
+    assert!(resolved.display_list.commands.is_empty());
+    assert!(!resolved.root.visible);
+}